@@ -20,8 +20,8 @@ _just() {
 
     case "${cmd}" in
         just)
-            opts=" -q -v -e -l -h -V -f -d -s  --dry-run --highlight --no-highlight --quiet --clear-shell-args --verbose --dump --edit --evaluate --init --list --summary --variables --help --version --color --justfile --set --shell --shell-arg --working-directory --completions --show  <ARGUMENTS>... "
-            if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
+            opts=" -g -q -u -v -e -l -h -V -f -d -s  --dry-run --global-fallback --global-justfile --highlight --no-highlight --quiet --check --clear-shell-args --unsorted --verbose --choose --docs --dump --edit --evaluate --fmt --groups --init --lint --list --lsp --plan --summary --variables --help --version --color --deny --format --graph-format --justfile --list-heading --list-prefix --message-format --set --shell --shell-arg --timeout --watch --working-directory --completions --graph --show --usage  <ARGUMENTS>... "
+            if [[ ${cur} == -* ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
             fi
@@ -31,6 +31,18 @@ _just() {
                     COMPREPLY=($(compgen -W "auto always never" -- "${cur}"))
                     return 0
                     ;;
+                --deny)
+                    COMPREPLY=($(compgen -W "warnings" -- "${cur}"))
+                    return 0
+                    ;;
+                --format)
+                    COMPREPLY=($(compgen -W "just json" -- "${cur}"))
+                    return 0
+                    ;;
+                --graph-format)
+                    COMPREPLY=($(compgen -W "dot mermaid" -- "${cur}"))
+                    return 0
+                    ;;
                 --justfile)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -39,6 +51,18 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --list-heading)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --list-prefix)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --message-format)
+                    COMPREPLY=($(compgen -W "human json" -- "${cur}"))
+                    return 0
+                    ;;
                 --set)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -51,31 +75,40 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
-                --working-directory)
+                --timeout)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
-                    -d)
+                --watch)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
-                --completions)
-                    COMPREPLY=($(compgen -W "zsh bash fish powershell elvish" -- "${cur}"))
+                --working-directory)
+                    COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
-                --show)
+                    -d)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
-                    -s)
-                    COMPREPLY=($(compgen -f "${cur}"))
+                --completions)
+                    COMPREPLY=($(compgen -W "zsh bash fish powershell elvish" -- "${cur}"))
                     return 0
                     ;;
                 *)
                     COMPREPLY=()
                     ;;
             esac
-            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            local words line="${COMP_LINE:0:COMP_POINT}"
+            read -ra words <<< "${line}"
+            if [[ ${line} == *[[:space:]] ]]; then
+                words+=("")
+            fi
+            local IFS=$'\n'
+            COMPREPLY=( $(just --complete bash -- "${words[@]:1}" 2> /dev/null) )
+            if [[ ${#COMPREPLY[@]} -eq 1 && ${COMPREPLY[0]} == *[/=] ]]; then
+                compopt -o nospace
+            fi
             return 0
             ;;
         
@@ -1,33 +1,53 @@
-function __fish_just_complete_recipes
-    just --summary 2> /dev/null | tr " " "\n" || echo ""
+function __fish_just_complete
+    set -l words (commandline -opc)
+    set -e words[1]
+    just --complete fish -- $words (commandline -ct) 2> /dev/null
 end
 
-# don't suggest files right off
-complete -c just -n "__fish_is_first_arg" --no-files
-
-# complete recipes
-complete -c just -a '(__fish_just_complete_recipes)'
+# complete recipes, parameters, and variables
+complete -c just -f -a '(__fish_just_complete)'
 
 # autogenerated completions
 complete -c just -n "__fish_use_subcommand" -l color -d 'Print colorful output' -r -f -a "auto always never"
-complete -c just -n "__fish_use_subcommand" -s f -l justfile -d 'Use <JUSTFILE> as justfile.'
+complete -c just -n "__fish_use_subcommand" -l deny -d 'Treat <KIND> as errors, e.g. `--deny warnings`' -r -f -a "warnings"
+complete -c just -n "__fish_use_subcommand" -l format -d 'Print justfile with `--dump` in <FORMAT>' -r -f -a "just json"
+complete -c just -n "__fish_use_subcommand" -l graph-format -d 'Print dependency graph with `--graph` in <FORMAT>' -r -f -a "dot mermaid"
+complete -c just -n "__fish_use_subcommand" -s f -l justfile -d 'Use <JUSTFILE> as justfile, or read it from standard input if <JUSTFILE> is `-`'
+complete -c just -n "__fish_use_subcommand" -l list-heading -d 'Print <TEXT> before recipes with `--list`'
+complete -c just -n "__fish_use_subcommand" -l list-prefix -d 'Print <TEXT> before each recipe with `--list`'
+complete -c just -n "__fish_use_subcommand" -l message-format -d 'Print errors and warnings in <FORMAT>' -r -f -a "human json"
 complete -c just -n "__fish_use_subcommand" -l set -d 'Override <VARIABLE> with <VALUE>'
 complete -c just -n "__fish_use_subcommand" -l shell -d 'Invoke <SHELL> to run recipes'
 complete -c just -n "__fish_use_subcommand" -l shell-arg -d 'Invoke shell with <SHELL-ARG> as an argument'
+complete -c just -n "__fish_use_subcommand" -l timeout -d 'Terminate recipes that run for longer than <DURATION>'
+complete -c just -n "__fish_use_subcommand" -l watch -d 'Rerun recipes when files matching <PATTERN> or recipe inputs change'
 complete -c just -n "__fish_use_subcommand" -s d -l working-directory -d 'Use <WORKING-DIRECTORY> as working directory. --justfile must also be set'
 complete -c just -n "__fish_use_subcommand" -l completions -d 'Print shell completion script for <SHELL>' -r -f -a "zsh bash fish powershell elvish"
+complete -c just -n "__fish_use_subcommand" -l graph -d 'Print dependency graph of <RECIPE>, or of all recipes'
 complete -c just -n "__fish_use_subcommand" -s s -l show -d 'Show information about <RECIPE>'
+complete -c just -n "__fish_use_subcommand" -l usage -d 'Print how to invoke <RECIPE>'
 complete -c just -n "__fish_use_subcommand" -l dry-run -d 'Print what just would do without doing it'
+complete -c just -n "__fish_use_subcommand" -l global-fallback -d 'Use global justfile if no justfile is found'
+complete -c just -n "__fish_use_subcommand" -s g -l global-justfile -d 'Use global justfile, `$XDG_CONFIG_HOME/just/justfile` or `~/.justfile`'
 complete -c just -n "__fish_use_subcommand" -l highlight -d 'Highlight echoed recipe lines in bold'
 complete -c just -n "__fish_use_subcommand" -l no-highlight -d 'Don\'t highlight echoed recipe lines in bold'
 complete -c just -n "__fish_use_subcommand" -s q -l quiet -d 'Suppress all output'
+complete -c just -n "__fish_use_subcommand" -l check -d 'Don\'t write the formatted justfile with `--fmt`, and fail if it isn\'t formatted'
 complete -c just -n "__fish_use_subcommand" -l clear-shell-args -d 'Clear shell arguments'
+complete -c just -n "__fish_use_subcommand" -s u -l unsorted -d 'List recipes and groups in the order they appear in the justfile'
 complete -c just -n "__fish_use_subcommand" -s v -l verbose -d 'Use verbose output'
+complete -c just -n "__fish_use_subcommand" -l choose -d 'Select one or more recipes to run using `$JUST_CHOOSER`, `fzf`, or a prompt'
+complete -c just -n "__fish_use_subcommand" -l docs -d 'Print Markdown documentation for recipes and variables'
 complete -c just -n "__fish_use_subcommand" -l dump -d 'Print entire justfile'
 complete -c just -n "__fish_use_subcommand" -s e -l edit -d 'Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`'
 complete -c just -n "__fish_use_subcommand" -l evaluate -d 'Print evaluated variables'
+complete -c just -n "__fish_use_subcommand" -l fmt -d 'Format justfile in place'
+complete -c just -n "__fish_use_subcommand" -l groups -d 'List recipe groups'
 complete -c just -n "__fish_use_subcommand" -l init -d 'Initialize new justfile in project root'
+complete -c just -n "__fish_use_subcommand" -l lint -d 'Check justfile for likely mistakes, like unused variables and parameters'
 complete -c just -n "__fish_use_subcommand" -s l -l list -d 'List available recipes and their arguments'
+complete -c just -n "__fish_use_subcommand" -l lsp -d 'Run a language server over stdin and stdout'
+complete -c just -n "__fish_use_subcommand" -l plan -d 'Print the recipes that would run, in order, with their arguments, and exit'
 complete -c just -n "__fish_use_subcommand" -l summary -d 'List names of available recipes'
 complete -c just -n "__fish_use_subcommand" -l variables -d 'List names of variables'
 complete -c just -n "__fish_use_subcommand" -s h -l help -d 'Print help information'
@@ -3,8 +3,9 @@ use crate::common::*;
 /// An alias, e.g. `name := target`
 #[derive(Debug, PartialEq)]
 pub(crate) struct Alias<'src, T = Rc<Recipe<'src>>> {
-  pub(crate) name:   Name<'src>,
-  pub(crate) target: T,
+  pub(crate) attributes: Vec<Attribute<'src>>,
+  pub(crate) name:       Name<'src>,
+  pub(crate) target:     T,
 }
 
 impl<'src> Alias<'src, Name<'src>> {
@@ -16,6 +17,7 @@ impl<'src> Alias<'src, Name<'src>> {
     assert_eq!(self.target.lexeme(), target.name.lexeme());
 
     Alias {
+      attributes: self.attributes,
       name: self.name,
       target,
     }
@@ -36,6 +38,10 @@ impl<'src, T> Keyed<'src> for Alias<'src, T> {
 
 impl<'src> Display for Alias<'src, Name<'src>> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for attribute in &self.attributes {
+      writeln!(f, "{}", attribute)?;
+    }
+
     write!(
       f,
       "alias {} := {}",
@@ -47,6 +53,10 @@ impl<'src> Display for Alias<'src, Name<'src>> {
 
 impl<'src> Display for Alias<'src> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for attribute in &self.attributes {
+      writeln!(f, "{}", attribute)?;
+    }
+
     write!(
       f,
       "alias {} := {}",
@@ -10,10 +10,15 @@ pub(crate) struct Analyzer<'src> {
 }
 
 impl<'src> Analyzer<'src> {
-  pub(crate) fn analyze(module: Module<'src>) -> CompilationResult<'src, Justfile> {
+  pub(crate) fn analyze(
+    module: Module<'src>,
+  ) -> Result<Justfile<'src>, Vec<CompilationError<'src>>> {
     let analyzer = Analyzer::new();
 
-    analyzer.justfile(module)
+    analyzer.justfile(module).map_err(|mut errors| {
+      errors.sort_by_key(|error| error.token.offset);
+      errors
+    })
   }
 
   pub(crate) fn new() -> Analyzer<'src> {
@@ -25,41 +30,52 @@ impl<'src> Analyzer<'src> {
     }
   }
 
+  /// Analyze `module`, continuing after errors so that as many as possible
+  /// are reported
   pub(crate) fn justfile(
     mut self,
     module: Module<'src>,
-  ) -> CompilationResult<'src, Justfile<'src>> {
+  ) -> Result<Justfile<'src>, Vec<CompilationError<'src>>> {
+    let mut errors = Vec::new();
+
     for item in module.items {
       match item {
-        Item::Alias(alias) => {
-          self.analyze_alias(&alias)?;
-          self.aliases.insert(alias);
+        Item::Alias(alias) => match self.analyze_alias(&alias) {
+          Ok(()) => self.aliases.insert(alias),
+          Err(error) => errors.push(error),
         },
-        Item::Assignment(assignment) => {
-          self.analyze_assignment(&assignment)?;
-          self.assignments.insert(assignment);
+        Item::Assignment(assignment) => match self.analyze_assignment(&assignment) {
+          Ok(()) => self.assignments.insert(assignment),
+          Err(error) => errors.push(error),
         },
         Item::Recipe(recipe) => {
-          self.analyze_recipe(&recipe)?;
-          self.recipes.insert(recipe);
+          let duplicate = self.recipes.contains_key(recipe.name());
+
+          self.analyze_recipe(&recipe, &mut errors);
+
+          if !duplicate {
+            self.recipes.insert(recipe);
+          }
         },
-        Item::Set(set) => {
-          self.analyze_set(&set)?;
-          self.sets.insert(set);
+        Item::Set(set) => match self.analyze_set(&set) {
+          Ok(()) => self.sets.insert(set),
+          Err(error) => errors.push(error),
         },
       }
     }
 
     let assignments = self.assignments;
 
-    AssignmentResolver::resolve_assignments(&assignments)?;
+    if let Err(assignment_errors) = AssignmentResolver::resolve_assignments(&assignments) {
+      errors.extend(assignment_errors);
+    }
 
-    let recipes = RecipeResolver::resolve_recipes(self.recipes, &assignments)?;
+    let recipes = RecipeResolver::resolve_recipes(self.recipes, &assignments, &mut errors);
 
     for recipe in recipes.values() {
       for parameter in &recipe.parameters {
         if assignments.contains_key(parameter.name.lexeme()) {
-          return Err(parameter.name.token().error(ParameterShadowsVariable {
+          errors.push(parameter.name.token().error(ParameterShadowsVariable {
             parameter: parameter.name.lexeme(),
           }));
         }
@@ -68,13 +84,31 @@ impl<'src> Analyzer<'src> {
 
     let mut aliases = Table::new();
     while let Some(alias) = self.aliases.pop() {
-      aliases.insert(Self::resolve_alias(&recipes, alias)?);
+      match Self::resolve_alias(&recipes, alias) {
+        Ok(alias) => aliases.insert(alias),
+        Err(error) => errors.push(error),
+      }
     }
 
     let mut settings = Settings::new();
 
     for (_, set) in self.sets {
       match set.value {
+        Setting::After(hook) => {
+          if let Err(error) = Self::resolve_hook(&recipes, set.name, &hook) {
+            errors.push(error);
+          }
+          settings.after = Some(hook.recipe);
+        },
+        Setting::Before(hook) => {
+          if let Err(error) = Self::resolve_hook(&recipes, set.name, &hook) {
+            errors.push(error);
+          }
+          settings.before = Some(hook.recipe);
+        },
+        Setting::Fallback(fallback) => {
+          settings.fallback = fallback;
+        },
         Setting::Shell(shell) => {
           assert!(settings.shell.is_none());
           settings.shell = Some(shell);
@@ -82,6 +116,10 @@ impl<'src> Analyzer<'src> {
       }
     }
 
+    if !errors.is_empty() {
+      return Err(errors);
+    }
+
     Ok(Justfile {
       warnings: module.warnings,
       aliases,
@@ -91,9 +129,40 @@ impl<'src> Analyzer<'src> {
     })
   }
 
-  fn analyze_recipe(&self, recipe: &UnresolvedRecipe<'src>) -> CompilationResult<'src, ()> {
+  /// Check that a hook names a known recipe which can be run without
+  /// arguments
+  fn resolve_hook(
+    recipes: &Table<'src, Rc<Recipe<'src>>>,
+    setting: Name<'src>,
+    hook: &setting::Hook<'src>,
+  ) -> CompilationResult<'src, ()> {
+    let recipe = if let Some(recipe) = recipes.get(hook.recipe) {
+      recipe
+    } else {
+      return Err(hook.token.error(UnknownHookRecipe {
+        setting: setting.lexeme(),
+        recipe:  hook.recipe,
+      }));
+    };
+
+    if recipe.min_arguments() > 0 {
+      return Err(hook.token.error(HookRecipeRequiresArguments {
+        setting:       setting.lexeme(),
+        recipe:        hook.recipe,
+        min_arguments: recipe.min_arguments(),
+      }));
+    }
+
+    Ok(())
+  }
+
+  fn analyze_recipe(
+    &self,
+    recipe: &UnresolvedRecipe<'src>,
+    errors: &mut Vec<CompilationError<'src>>,
+  ) {
     if let Some(original) = self.recipes.get(recipe.name.lexeme()) {
-      return Err(recipe.name.token().error(DuplicateRecipe {
+      errors.push(recipe.name.token().error(DuplicateRecipe {
         recipe: original.name(),
         first:  original.line_number(),
       }));
@@ -103,18 +172,17 @@ impl<'src> Analyzer<'src> {
     let mut passed_default = false;
 
     for parameter in &recipe.parameters {
-      if parameters.contains(parameter.name.lexeme()) {
-        return Err(parameter.name.token().error(DuplicateParameter {
+      if !parameters.insert(parameter.name.lexeme()) {
+        errors.push(parameter.name.token().error(DuplicateParameter {
           recipe:    recipe.name.lexeme(),
           parameter: parameter.name.lexeme(),
         }));
       }
-      parameters.insert(parameter.name.lexeme());
 
       if parameter.default.is_some() {
         passed_default = true;
       } else if passed_default {
-        return Err(
+        errors.push(
           parameter
             .name
             .token()
@@ -125,6 +193,29 @@ impl<'src> Analyzer<'src> {
       }
     }
 
+    for attribute in &recipe.attributes {
+      let (parameter, token) = match attribute {
+        Attribute::Choices {
+          parameter, token, ..
+        }
+        | Attribute::Doc {
+          parameter: Some(parameter),
+          token,
+          ..
+        }
+        | Attribute::Path { parameter, token } => (parameter, token),
+        _ => continue,
+      };
+
+      if !parameters.contains(parameter) {
+        errors.push(token.error(UnknownAttributeParameter {
+          attribute: attribute.name(),
+          recipe:    recipe.name.lexeme(),
+          parameter,
+        }));
+      }
+    }
+
     let mut continued = false;
     for line in &recipe.body {
       if !recipe.shebang && !continued {
@@ -132,15 +223,13 @@ impl<'src> Analyzer<'src> {
           let text = token.lexeme();
 
           if text.starts_with(' ') || text.starts_with('\t') {
-            return Err(token.error(ExtraLeadingWhitespace));
+            errors.push(token.error(ExtraLeadingWhitespace));
           }
         }
       }
 
       continued = line.is_continuation();
     }
-
-    Ok(())
   }
 
   fn analyze_assignment(&self, assignment: &Assignment<'src>) -> CompilationResult<'src, ()> {
@@ -206,7 +295,7 @@ mod tests {
 
   analysis_error! {
     name: duplicate_alias,
-    input: "alias foo = bar\nalias foo = baz",
+    input: "alias foo = bar\nalias foo = baz\nbar:",
     offset: 22,
     line: 1,
     column: 6,
@@ -214,6 +303,34 @@ mod tests {
     kind: DuplicateAlias { alias: "foo", first: 0 },
   }
 
+  analysis_error! {
+    name: attribute_unknown_parameter,
+    input: "[path('file')]\nfoo path:",
+    offset: 6,
+    line: 0,
+    column: 6,
+    width: 6,
+    kind: UnknownAttributeParameter {
+      attribute: "path",
+      recipe: "foo",
+      parameter: "file",
+    },
+  }
+
+  analysis_error! {
+    name: attribute_doc_unknown_parameter,
+    input: "[doc('file', 'the file')]\nfoo path:",
+    offset: 5,
+    line: 0,
+    column: 5,
+    width: 6,
+    kind: UnknownAttributeParameter {
+      attribute: "doc",
+      recipe: "foo",
+      parameter: "file",
+    },
+  }
+
   analysis_error! {
     name: unknown_alias_target,
     input: "alias foo = bar\n",
@@ -313,4 +430,34 @@ mod tests {
     width:  6,
     kind:   ExtraLeadingWhitespace,
   }
+
+  analysis_error! {
+    name:   unknown_hook_recipe,
+    input:  "set before := 'banner'",
+    offset: 14,
+    line:   0,
+    column: 14,
+    width:  8,
+    kind:   UnknownHookRecipe{setting: "before", recipe: "banner"},
+  }
+
+  analysis_error! {
+    name:   before_hook_requires_arguments,
+    input:  "set before := 'banner'\nbanner x:",
+    offset: 14,
+    line:   0,
+    column: 14,
+    width:  8,
+    kind:   HookRecipeRequiresArguments{setting: "before", recipe: "banner", min_arguments: 1},
+  }
+
+  analysis_error! {
+    name:   after_hook_requires_arguments,
+    input:  "set after := 'report'\nreport x y:",
+    offset: 13,
+    line:   0,
+    column: 13,
+    width:  8,
+    kind:   HookRecipeRequiresArguments{setting: "after", recipe: "report", min_arguments: 2},
+  }
 }
@@ -0,0 +1,785 @@
+//! A library interface for inspecting and running justfiles.
+//!
+//! Unlike the rest of the crate, the contents of this module are covered by
+//! semver: breaking changes will only be made in major releases.
+//!
+//! A `Justfile` is compiled from a string or a path, after which its recipes
+//! and assignments may be inspected. All types are owned, so a `Justfile` can
+//! be stored and passed around freely. Assignments are evaluated, and recipes
+//! run, using a `Config`, which plays the role of the command line
+//! arguments of the `just` binary:
+//!
+//! ```
+//! use just::api::{Config, Justfile};
+//!
+//! let justfile: Justfile = "
+//! target := 'debug'
+//!
+//! ## build the project
+//! build mode=target:
+//!   cargo build --{{mode}}
+//! "
+//! .parse()
+//! .unwrap();
+//!
+//! let build = justfile.recipe("build").unwrap();
+//! assert_eq!(build.doc(), Some("build the project"));
+//! assert_eq!(build.parameters()[0].default(), Some("target"));
+//!
+//! let config = Config::new().set("target", "release");
+//! let variables = justfile.evaluate(&config).unwrap();
+//! assert_eq!(variables["target"], "release");
+//! ```
+//!
+//! Output from recipes, and the messages printed while running them, are
+//! inherited from the current process unless sinks are given with
+//! `Config::stdout` and `Config::stderr`. As with the binary, a `.env` file
+//! in the current directory of the process is loaded before assignments are
+//! evaluated.
+
+use std::{
+  collections::BTreeMap,
+  env,
+  error,
+  fmt::{self, Display, Formatter},
+  fs, io,
+  io::Write,
+  path::{Path, PathBuf},
+  str::FromStr,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use crate::{error::Error as _, search::FILENAME};
+
+mod full {
+  pub(crate) use crate::{
+    assignment::Assignment, color::Color, compiler::Compiler, config::Config,
+    dependency::Dependency, justfile::Justfile, message_format::MessageFormat,
+    parameter::Parameter, recipe::Recipe, runtime_error::RuntimeError, search::Search,
+    search_config::SearchConfig, sinks::Sinks, subcommand::Subcommand, verbosity::Verbosity,
+  };
+}
+
+/// A compiled justfile
+#[derive(Debug, Clone)]
+pub struct Justfile {
+  src:         String,
+  path:        Option<PathBuf>,
+  recipes:     Vec<Recipe>,
+  assignments: Vec<Assignment>,
+}
+
+impl Justfile {
+  /// Read and compile the justfile at `path`. When run, recipes use the
+  /// directory containing `path` as their working directory, unless
+  /// `Config::working_directory` is given.
+  pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+    let path = path.as_ref();
+
+    let src = fs::read_to_string(path).map_err(|io_error| Error::Io {
+      path: path.to_owned(),
+      io_error,
+    })?;
+
+    Self::new(src, Some(path.to_owned()))
+  }
+
+  /// Compile the justfile `src`. When run, recipes use the current directory
+  /// of the process as their working directory, unless
+  /// `Config::working_directory` is given.
+  pub fn parse(src: &str) -> Result<Self, Error> {
+    Self::new(src.to_owned(), None)
+  }
+
+  fn new(src: String, path: Option<PathBuf>) -> Result<Self, Error> {
+    let justfile = full::Compiler::compile(&src).map_err(|errors| Error::Compile {
+      errors: errors.iter().map(ToString::to_string).collect(),
+    })?;
+
+    let mut aliases = BTreeMap::new();
+
+    for alias in justfile.aliases.values() {
+      aliases
+        .entry(alias.target.name())
+        .or_insert_with(Vec::new)
+        .push(alias.name.lexeme().to_owned());
+    }
+
+    let recipes = justfile
+      .recipes
+      .values()
+      .map(|recipe| Recipe::new(recipe, aliases.remove(recipe.name()).unwrap_or_default()))
+      .collect();
+
+    let assignments = justfile.assignments.values().map(Assignment::new).collect();
+
+    Ok(Self {
+      recipes,
+      assignments,
+      src,
+      path,
+    })
+  }
+
+  /// The path this justfile was read from, if any
+  pub fn path(&self) -> Option<&Path> {
+    self.path.as_deref()
+  }
+
+  /// Recipes, ordered by name
+  pub fn recipes(&self) -> &[Recipe] {
+    &self.recipes
+  }
+
+  /// The recipe called `name`, or the target of the alias called `name`
+  pub fn recipe(&self, name: &str) -> Option<&Recipe> {
+    self
+      .recipes
+      .iter()
+      .find(|recipe| recipe.name == name)
+      .or_else(|| {
+        self
+          .recipes
+          .iter()
+          .find(|recipe| recipe.aliases.iter().any(|alias| alias == name))
+      })
+  }
+
+  /// Assignments, ordered by name
+  pub fn assignments(&self) -> &[Assignment] {
+    &self.assignments
+  }
+
+  /// Evaluate all assignments, with the overrides in `config` taking
+  /// precedence, returning the value of each variable by name
+  pub fn evaluate(&self, config: &Config) -> Result<BTreeMap<String, String>, Error> {
+    let justfile = self.compile();
+    let search = self.search(config)?;
+    let full = config.full(&search, Vec::new());
+
+    justfile
+      .evaluate(&full, &search, &config.overrides)
+      .map_err(|error| Error::run(&error))
+  }
+
+  /// Run the recipes and arguments in `arguments`, as if they had been
+  /// passed on the command line, or the default recipe if `arguments` is
+  /// empty
+  pub fn run(&self, config: &Config, arguments: &[&str]) -> Result<(), Error> {
+    let justfile = self.compile();
+    let search = self.search(config)?;
+    let arguments = arguments
+      .iter()
+      .map(|argument| (*argument).to_owned())
+      .collect::<Vec<String>>();
+    let full = config.full(&search, arguments.clone());
+
+    justfile
+      .run(&full, &search, &config.overrides, &arguments)
+      .map_err(|error| Error::run(&error))
+  }
+
+  fn compile(&self) -> full::Justfile<'_> {
+    full::Compiler::compile(&self.src).expect("justfile failed to recompile")
+  }
+
+  fn search(&self, config: &Config) -> Result<full::Search, Error> {
+    let working_directory = match (&config.working_directory, &self.path) {
+      (Some(working_directory), _) => working_directory.clone(),
+      (None, Some(path)) => path.parent().map(Path::to_owned).unwrap_or_default(),
+      (None, None) => env::current_dir().map_err(|io_error| Error::Io {
+        path: PathBuf::from("."),
+        io_error,
+      })?,
+    };
+
+    let justfile = match &self.path {
+      Some(path) => path.clone(),
+      None => working_directory.join(FILENAME),
+    };
+
+    Ok(full::Search {
+      justfile,
+      working_directory,
+    })
+  }
+}
+
+impl FromStr for Justfile {
+  type Err = Error;
+
+  fn from_str(src: &str) -> Result<Self, Error> {
+    Self::parse(src)
+  }
+}
+
+/// A recipe, e.g. `build mode='debug': test`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipe {
+  name:         String,
+  doc:          Option<String>,
+  parameters:   Vec<Parameter>,
+  dependencies: Vec<Dependency>,
+  aliases:      Vec<String>,
+  groups:       Vec<String>,
+  private:      bool,
+  quiet:        bool,
+}
+
+impl Recipe {
+  fn new(recipe: &full::Recipe, aliases: Vec<String>) -> Self {
+    Self {
+      name: recipe.name().to_owned(),
+      doc: recipe.doc.as_deref().map(str::to_owned),
+      parameters: recipe
+        .parameters
+        .iter()
+        .map(|parameter| Parameter::new(recipe, parameter))
+        .collect(),
+      dependencies: recipe.dependencies.iter().map(Dependency::new).collect(),
+      groups: recipe.groups().map(str::to_owned).collect(),
+      private: recipe.private,
+      quiet: recipe.quiet,
+      aliases,
+    }
+  }
+
+  /// The recipe name
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// The recipe's documentation, from the `[doc]` attribute, or the doc
+  /// comment lines before the recipe, without the leading `#`s
+  pub fn doc(&self) -> Option<&str> {
+    self.doc.as_deref()
+  }
+
+  /// Parameters, in order
+  pub fn parameters(&self) -> &[Parameter] {
+    &self.parameters
+  }
+
+  /// Dependencies, in the order that they run
+  pub fn dependencies(&self) -> &[Dependency] {
+    &self.dependencies
+  }
+
+  /// Names of aliases for this recipe
+  pub fn aliases(&self) -> &[String] {
+    &self.aliases
+  }
+
+  /// Groups from `[group]` attributes, in order
+  pub fn groups(&self) -> &[String] {
+    &self.groups
+  }
+
+  /// Whether the recipe is private, i.e. its name starts with `_`
+  pub fn private(&self) -> bool {
+    self.private
+  }
+
+  /// Whether the recipe is quiet, i.e. its name is preceded by `@`
+  pub fn quiet(&self) -> bool {
+    self.quiet
+  }
+}
+
+/// A recipe parameter, e.g. `mode='debug'`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+  name:     String,
+  doc:      Option<String>,
+  default:  Option<String>,
+  variadic: bool,
+}
+
+impl Parameter {
+  fn new(recipe: &full::Recipe, parameter: &full::Parameter) -> Self {
+    Self {
+      name:     parameter.name.lexeme().to_owned(),
+      doc:      recipe
+        .parameter_doc(parameter.name.lexeme())
+        .map(str::to_owned),
+      default:  parameter.default.as_ref().map(ToString::to_string),
+      variadic: parameter.variadic,
+    }
+  }
+
+  /// The parameter name
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Documentation from a `[doc("PARAMETER", "TEXT")]` attribute
+  pub fn doc(&self) -> Option<&str> {
+    self.doc.as_deref()
+  }
+
+  /// The default value expression, as justfile source, e.g. `'debug'`
+  pub fn default(&self) -> Option<&str> {
+    self.default.as_deref()
+  }
+
+  /// Whether the parameter is variadic, i.e. preceded by `+`
+  pub fn variadic(&self) -> bool {
+    self.variadic
+  }
+}
+
+/// A dependency of a recipe, e.g. `(test 'debug')`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+  recipe:    String,
+  arguments: Vec<String>,
+}
+
+impl Dependency {
+  fn new(dependency: &full::Dependency) -> Self {
+    Self {
+      recipe:    dependency.recipe.name().to_owned(),
+      arguments: dependency.arguments.iter().map(ToString::to_string).collect(),
+    }
+  }
+
+  /// The name of the recipe depended on
+  pub fn recipe(&self) -> &str {
+    &self.recipe
+  }
+
+  /// Argument expressions, as justfile source
+  pub fn arguments(&self) -> &[String] {
+    &self.arguments
+  }
+}
+
+/// An assignment, e.g. `export target := 'debug'`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+  name:     String,
+  value:    String,
+  exported: bool,
+}
+
+impl Assignment {
+  fn new(assignment: &full::Assignment) -> Self {
+    Self {
+      name:     assignment.name.lexeme().to_owned(),
+      value:    assignment.value.to_string(),
+      exported: assignment.export,
+    }
+  }
+
+  /// The variable name
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// The value expression, as justfile source, e.g. `'debug'`
+  pub fn value(&self) -> &str {
+    &self.value
+  }
+
+  /// Whether the variable is exported to recipes as an environment variable
+  pub fn exported(&self) -> bool {
+    self.exported
+  }
+}
+
+/// Options for evaluating and running a justfile, equivalent to the command
+/// line options of the same names
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+  dry_run:           bool,
+  overrides:         BTreeMap<String, String>,
+  quiet:             bool,
+  shell:             Option<String>,
+  shell_args:        Option<Vec<String>>,
+  sinks:             full::Sinks,
+  timeout:           Option<Duration>,
+  verbose:           bool,
+  working_directory: Option<PathBuf>,
+}
+
+impl Config {
+  /// A config with the same defaults as the `just` binary
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Print recipe lines instead of running them, like `--dry-run`
+  pub fn dry_run(mut self, dry_run: bool) -> Self {
+    self.dry_run = dry_run;
+    self
+  }
+
+  /// Suppress recipe output and echoed lines, like `--quiet`
+  pub fn quiet(mut self, quiet: bool) -> Self {
+    self.quiet = quiet;
+    self
+  }
+
+  /// Print which recipes are running, like `--verbose`
+  pub fn verbose(mut self, verbose: bool) -> Self {
+    self.verbose = verbose;
+    self
+  }
+
+  /// Override the value of the variable `name` with `value`, like `--set`
+  pub fn set(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+    self.overrides.insert(name.into(), value.into());
+    self
+  }
+
+  /// Invoke `shell` to run recipe lines and backticks, overriding
+  /// `set shell`, like `--shell`
+  pub fn shell(mut self, shell: impl Into<String>) -> Self {
+    self.shell = Some(shell.into());
+    self
+  }
+
+  /// Pass `shell_args` to the shell, overriding `set shell`, like
+  /// `--shell-arg`
+  pub fn shell_args<I, S>(mut self, shell_args: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    self.shell_args = Some(shell_args.into_iter().map(Into::into).collect());
+    self
+  }
+
+  /// Fail recipes which take longer than `timeout`, like `--timeout`
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Run recipes in `working_directory`, like `--working-directory`
+  pub fn working_directory(mut self, working_directory: impl Into<PathBuf>) -> Self {
+    self.working_directory = Some(working_directory.into());
+    self
+  }
+
+  /// Write the output of recipes to `stdout`, instead of inheriting the
+  /// standard output of the current process
+  pub fn stdout<W: Write + Send + 'static>(mut self, stdout: Arc<Mutex<W>>) -> Self {
+    self.sinks.stdout = Some(stdout);
+    self
+  }
+
+  /// Write the error output of recipes, and the messages printed while
+  /// running them, like echoed recipe lines, to `stderr`, instead of the
+  /// standard error of the current process
+  pub fn stderr<W: Write + Send + 'static>(mut self, stderr: Arc<Mutex<W>>) -> Self {
+    self.sinks.stderr = Some(stderr);
+    self
+  }
+
+  fn full(&self, search: &full::Search, arguments: Vec<String>) -> full::Config {
+    full::Config {
+      color: full::Color::never(),
+      deny_warnings: false,
+      dry_run: self.dry_run,
+      global_fallback: false,
+      highlight: false,
+      invocation_directory: search.working_directory.clone(),
+      message_format: full::MessageFormat::Human,
+      quiet: self.quiet,
+      search_config: full::SearchConfig::WithJustfileAndWorkingDirectory {
+        justfile:          search.justfile.clone(),
+        working_directory: search.working_directory.clone(),
+      },
+      shell: self
+        .shell
+        .clone()
+        .unwrap_or_else(|| crate::config::DEFAULT_SHELL.to_owned()),
+      shell_args: self
+        .shell_args
+        .clone()
+        .unwrap_or_else(|| vec![crate::config::DEFAULT_SHELL_ARG.to_owned()]),
+      shell_present: self.shell.is_some() || self.shell_args.is_some(),
+      sinks: self.sinks.clone(),
+      subcommand: full::Subcommand::Run {
+        overrides: self.overrides.clone(),
+        arguments,
+      },
+      timeout: self.timeout,
+      verbosity: if self.verbose {
+        full::Verbosity::Loquacious
+      } else {
+        full::Verbosity::Taciturn
+      },
+      watch: None,
+    }
+  }
+}
+
+/// An error compiling, evaluating, or running a justfile
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+  /// The justfile, or the current directory, could not be read
+  Io { path: PathBuf, io_error: io::Error },
+  /// The justfile failed to compile. Each error is formatted as by `just`,
+  /// including the offending source line.
+  Compile { errors: Vec<String> },
+  /// Evaluating assignments or running recipes failed. `code` is the exit
+  /// code that `just` would have exited with.
+  Run { message: String, code: i32 },
+}
+
+impl Error {
+  fn run(error: &full::RuntimeError) -> Self {
+    Self::Run {
+      message: error.message(),
+      code:    error.code(),
+    }
+  }
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Io { path, io_error } => write!(f, "I/O error at `{}`: {}", path.display(), io_error),
+      Self::Compile { errors } => write!(f, "{}", errors.join("\n")),
+      Self::Run { message, .. } => write!(f, "{}", message),
+    }
+  }
+}
+
+impl error::Error for Error {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      Self::Io { io_error, .. } => Some(io_error),
+      Self::Compile { .. } | Self::Run { .. } => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::testing;
+
+  use pretty_assertions::assert_eq;
+
+  const JUSTFILE: &str = "
+target := 'debug'
+export TOKEN := 'x' + target
+
+# build the project
+[doc('mode', 'debug or release')]
+build mode=target +flags='': (test mode)
+  echo {{mode}} {{flags}}
+
+alias b := build
+
+[group('ci')]
+@test mode:
+  echo test {{mode}}
+
+_helper:
+";
+
+  /// A sink which can be read after being passed to a `Config`
+  fn sink() -> Arc<Mutex<Vec<u8>>> {
+    Arc::new(Mutex::new(Vec::new()))
+  }
+
+  fn contents(sink: &Arc<Mutex<Vec<u8>>>) -> String {
+    String::from_utf8(sink.lock().unwrap().clone()).unwrap()
+  }
+
+  #[test]
+  fn recipes() {
+    let justfile = JUSTFILE.parse::<Justfile>().unwrap();
+
+    let names = justfile
+      .recipes()
+      .iter()
+      .map(Recipe::name)
+      .collect::<Vec<&str>>();
+    assert_eq!(names, &["_helper", "build", "test"]);
+
+    let build = justfile.recipe("build").unwrap();
+    assert_eq!(build.doc(), Some("build the project"));
+    assert_eq!(build.aliases(), &["b"]);
+    assert!(!build.private());
+    assert!(!build.quiet());
+
+    let parameters = build.parameters();
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters[0].name(), "mode");
+    assert_eq!(parameters[0].default(), Some("target"));
+    assert_eq!(parameters[0].doc(), Some("debug or release"));
+    assert!(!parameters[0].variadic());
+    assert_eq!(parameters[1].name(), "flags");
+    assert_eq!(parameters[1].default(), Some("''"));
+    assert_eq!(parameters[1].doc(), None);
+    assert!(parameters[1].variadic());
+
+    let dependencies = build.dependencies();
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(dependencies[0].recipe(), "test");
+    assert_eq!(dependencies[0].arguments(), &["mode"]);
+
+    assert_eq!(justfile.recipe("b"), Some(build));
+    assert!(build.groups().is_empty());
+    assert!(justfile.recipe("test").unwrap().quiet());
+    assert_eq!(justfile.recipe("test").unwrap().groups(), &["ci"]);
+    assert!(justfile.recipe("_helper").unwrap().private());
+    assert_eq!(justfile.recipe("bogus"), None);
+  }
+
+  #[test]
+  fn assignments() {
+    let justfile = JUSTFILE.parse::<Justfile>().unwrap();
+
+    let assignments = justfile.assignments();
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments[0].name(), "TOKEN");
+    assert_eq!(assignments[0].value(), "'x' + target");
+    assert!(assignments[0].exported());
+    assert_eq!(assignments[1].name(), "target");
+    assert!(!assignments[1].exported());
+  }
+
+  #[test]
+  fn evaluate() {
+    let justfile = JUSTFILE.parse::<Justfile>().unwrap();
+
+    let variables = justfile
+      .evaluate(&Config::new().set("target", "release"))
+      .unwrap();
+
+    let want = vec![("TOKEN", "xrelease"), ("target", "release")]
+      .into_iter()
+      .map(|(name, value)| (name.to_owned(), value.to_owned()))
+      .collect::<BTreeMap<String, String>>();
+
+    assert_eq!(variables, want);
+  }
+
+  #[test]
+  fn evaluate_unknown_override() {
+    let justfile = JUSTFILE.parse::<Justfile>().unwrap();
+
+    match justfile.evaluate(&Config::new().set("bogus", "x")) {
+      Err(Error::Run { message, code }) => {
+        assert_eq!(message, "Variable `bogus` overridden on the command line but not present \
+                             in justfile");
+        assert_eq!(code, 1);
+      },
+      result => panic!("unexpected result: {:?}", result),
+    }
+  }
+
+  #[test]
+  fn run_with_sinks() {
+    let justfile = JUSTFILE.parse::<Justfile>().unwrap();
+
+    let stdout = sink();
+    let stderr = sink();
+
+    let config = Config::new()
+      .stdout(stdout.clone())
+      .stderr(stderr.clone());
+
+    justfile.run(&config, &["build", "a", "b"]).unwrap();
+
+    assert_eq!(contents(&stdout), "test a\na b\n");
+    assert_eq!(contents(&stderr), "echo a b\n");
+  }
+
+  #[test]
+  fn run_dry_run() {
+    let justfile = JUSTFILE.parse::<Justfile>().unwrap();
+
+    let stdout = sink();
+    let stderr = sink();
+
+    let config = Config::new()
+      .dry_run(true)
+      .stdout(stdout.clone())
+      .stderr(stderr.clone());
+
+    justfile.run(&config, &["b"]).unwrap();
+
+    assert_eq!(contents(&stdout), "");
+    assert_eq!(contents(&stderr), "echo test debug\necho debug \n");
+  }
+
+  #[test]
+  fn run_failure() {
+    let justfile = "foo:\n  exit 7\n".parse::<Justfile>().unwrap();
+
+    let stderr = sink();
+
+    match justfile.run(&Config::new().stderr(stderr.clone()), &[]) {
+      Err(Error::Run { message, code }) => {
+        assert_eq!(message, "Recipe `foo` failed on line 2 with exit code 7");
+        assert_eq!(code, 7);
+      },
+      result => panic!("unexpected result: {:?}", result),
+    }
+
+    assert_eq!(contents(&stderr), "exit 7\n");
+  }
+
+  #[test]
+  fn run_in_working_directory() {
+    let tmp = testing::tempdir();
+
+    let stdout = sink();
+
+    let config = Config::new()
+      .working_directory(tmp.path())
+      .stdout(stdout.clone());
+
+    "@foo:\n  pwd\n"
+      .parse::<Justfile>()
+      .unwrap()
+      .run(&config, &[])
+      .unwrap();
+
+    assert_eq!(
+      PathBuf::from(contents(&stdout).trim_end()).canonicalize().unwrap(),
+      tmp.path().canonicalize().unwrap()
+    );
+  }
+
+  #[test]
+  fn compile_error() {
+    match "foo:\n  echo {{bar}}\n".parse::<Justfile>() {
+      Err(Error::Compile { errors }) => {
+        assert_eq!(errors.len(), 1);
+        assert!(
+          errors[0].contains("Variable `bar` not defined"),
+          "unexpected error: {}",
+          errors[0]
+        );
+      },
+      result => panic!("unexpected result: {:?}", result),
+    }
+  }
+
+  #[test]
+  fn from_path() {
+    let tmp = testing::tempdir();
+    let path = tmp.path().join("justfile");
+    fs::write(&path, "foo:\n  echo foo\n").unwrap();
+
+    let justfile = Justfile::from_path(&path).unwrap();
+    assert_eq!(justfile.path(), Some(path.as_path()));
+    assert_eq!(justfile.recipes().len(), 1);
+
+    match Justfile::from_path(tmp.path().join("missing")) {
+      Err(Error::Io { path, .. }) => assert_eq!(path, tmp.path().join("missing")),
+      result => panic!("unexpected result: {:?}", result),
+    }
+  }
+}
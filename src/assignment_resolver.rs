@@ -12,7 +12,7 @@ pub(crate) struct AssignmentResolver<'src: 'run, 'run> {
 impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
   pub(crate) fn resolve_assignments(
     assignments: &Table<'src, Assignment<'src>>,
-  ) -> CompilationResult<'src, ()> {
+  ) -> Result<(), Vec<CompilationError<'src>>> {
     let mut resolver = AssignmentResolver {
       stack: empty(),
       seen: empty(),
@@ -20,11 +20,22 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
       assignments,
     };
 
+    let mut errors = Vec::new();
+
     for name in assignments.keys() {
-      resolver.resolve_assignment(name)?;
+      if let Err(error) = resolver.resolve_assignment(name) {
+        errors.push(error);
+        // don't report the same error again for assignments that depend on
+        // the ones that were being resolved
+        resolver.evaluated.extend(resolver.stack.drain(..));
+      }
     }
 
-    Ok(())
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
   }
 
   fn resolve_assignment(&mut self, name: &'src str) -> CompilationResult<'src, ()> {
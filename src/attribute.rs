@@ -0,0 +1,133 @@
+use crate::common::*;
+
+/// An item attribute, e.g. `[timeout("10m")]`
+#[derive(Debug, PartialEq)]
+pub(crate) enum Attribute<'src> {
+  /// Don't report `lints` for this item
+  Allow { lints: Vec<Lint> },
+  /// Complete `parameter` with `choices` in shell completion scripts
+  Choices {
+    parameter: &'src str,
+    token:     Token<'src>,
+    choices:   Vec<StringLiteral<'src>>,
+  },
+  /// Document the recipe, or `parameter` if given, with `doc`, overriding
+  /// the recipe's doc comment
+  Doc {
+    parameter: Option<&'src str>,
+    token:     Token<'src>,
+    doc:       StringLiteral<'src>,
+  },
+  /// Run `recipe` after this recipe, even if it fails
+  Finally {
+    recipe: StringLiteral<'src>,
+    token:  Token<'src>,
+  },
+  /// List the recipe under `group` with `--list`
+  Group { group: StringLiteral<'src> },
+  /// Files that `--watch` should watch for changes when running the recipe
+  Inputs { patterns: Vec<StringLiteral<'src>> },
+  /// Complete `parameter` with file paths in shell completion scripts
+  Path {
+    parameter: &'src str,
+    token:     Token<'src>,
+  },
+  /// Rerun failing lines or shebang scripts
+  Retry(Retry),
+  /// Terminate the recipe if it runs for longer than `duration`
+  Timeout { duration: HumanDuration },
+}
+
+impl<'src> Attribute<'src> {
+  /// Whether this attribute may be applied to items other than recipes
+  pub(crate) fn applies_to_any_item(&self) -> bool {
+    match self {
+      Self::Allow { .. } => true,
+      Self::Choices { .. }
+      | Self::Doc { .. }
+      | Self::Finally { .. }
+      | Self::Group { .. }
+      | Self::Inputs { .. }
+      | Self::Path { .. }
+      | Self::Retry(_)
+      | Self::Timeout { .. } => false,
+    }
+  }
+
+  /// Whether an item may have more than one attribute with this name. Only
+  /// parameter docs may be repeated, not the recipe doc.
+  pub(crate) fn repeatable(&self) -> bool {
+    match self {
+      Self::Choices { .. } | Self::Group { .. } | Self::Path { .. } => true,
+      Self::Doc { parameter, .. } => parameter.is_some(),
+      Self::Allow { .. }
+      | Self::Finally { .. }
+      | Self::Inputs { .. }
+      | Self::Retry(_)
+      | Self::Timeout { .. } => false,
+    }
+  }
+
+  pub(crate) fn name(&self) -> &'static str {
+    match self {
+      Self::Allow { .. } => keyword::ALLOW,
+      Self::Choices { .. } => keyword::CHOICES,
+      Self::Doc { .. } => keyword::DOC,
+      Self::Finally { .. } => keyword::FINALLY,
+      Self::Group { .. } => keyword::GROUP,
+      Self::Inputs { .. } => keyword::INPUTS,
+      Self::Path { .. } => keyword::PATH,
+      Self::Retry(_) => keyword::RETRY,
+      Self::Timeout { .. } => keyword::TIMEOUT,
+    }
+  }
+}
+
+impl<'src> Display for Attribute<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "[{}", self.name())?;
+
+    match self {
+      Self::Allow { lints } => {
+        write!(f, "(")?;
+        for (i, lint) in lints.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "\"{}\"", lint)?;
+        }
+        write!(f, ")")?;
+      },
+      Self::Choices {
+        parameter, choices, ..
+      } => {
+        write!(f, "(\"{}\"", parameter)?;
+        for choice in choices {
+          write!(f, ", {}", choice)?;
+        }
+        write!(f, ")")?;
+      },
+      Self::Doc { parameter, doc, .. } => match parameter {
+        Some(parameter) => write!(f, "(\"{}\", {})", parameter, doc)?,
+        None => write!(f, "({})", doc)?,
+      },
+      Self::Finally { recipe, .. } => write!(f, "({})", recipe)?,
+      Self::Group { group } => write!(f, "({})", group)?,
+      Self::Inputs { patterns } => {
+        write!(f, "(")?;
+        for (i, pattern) in patterns.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{}", pattern)?;
+        }
+        write!(f, ")")?;
+      },
+      Self::Path { parameter, .. } => write!(f, "(\"{}\")", parameter)?,
+      Self::Retry(retry) => write!(f, "({})", retry)?,
+      Self::Timeout { duration } => write!(f, "(\"{}\")", duration)?,
+    }
+
+    write!(f, "]")
+  }
+}
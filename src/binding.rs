@@ -3,12 +3,14 @@ use crate::common::*;
 /// A binding of `name` to `value`
 #[derive(Debug, PartialEq)]
 pub(crate) struct Binding<'src, V = String> {
+  /// Binding attributes
+  pub(crate) attributes: Vec<Attribute<'src>>,
   /// Export binding as an environment variable to child processes
-  pub(crate) export: bool,
+  pub(crate) export:     bool,
   /// Binding name
-  pub(crate) name:   Name<'src>,
+  pub(crate) name:       Name<'src>,
   /// Binding value
-  pub(crate) value:  V,
+  pub(crate) value:      V,
 }
 
 impl<'src, V> Keyed<'src> for Binding<'src, V> {
@@ -0,0 +1,267 @@
+use crate::common::*;
+
+use std::process::Stdio;
+
+use unicode_width::UnicodeWidthStr;
+
+/// Lets the user pick recipes to run with `--choose`, using the command in
+/// `$JUST_CHOOSER`, `fzf` if it is installed, or a numbered prompt
+pub(crate) struct Chooser<'src: 'run, 'run> {
+  config:  &'run Config,
+  recipes: Vec<&'run Recipe<'src>>,
+}
+
+impl<'src: 'run, 'run> Chooser<'src, 'run> {
+  pub(crate) fn new(config: &'run Config, justfile: &'run Justfile<'src>) -> Self {
+    Self {
+      recipes: justfile.public_recipes(false),
+      config,
+    }
+  }
+
+  /// Ask the user to choose one or more recipes, and then to give values for
+  /// their required parameters, returning the name and arguments of each
+  /// chosen recipe
+  pub(crate) fn choose(&self) -> Result<Vec<Vec<String>>, i32> {
+    if self.recipes.is_empty() {
+      eprintln!("Justfile contains no recipes to choose from.");
+      return Err(EXIT_FAILURE);
+    }
+
+    let names = match self.external()? {
+      Some(names) => names,
+      None => Self::prompt(&self.recipes)?,
+    };
+
+    if names.is_empty() {
+      eprintln!("No recipes chosen.");
+      return Err(EXIT_FAILURE);
+    }
+
+    let mut chosen = Vec::new();
+
+    for name in names {
+      let recipe = self
+        .recipes
+        .iter()
+        .find(|recipe| recipe.name() == name)
+        .ok_or_else(|| {
+          eprintln!("Chooser returned unknown recipe `{}`", name);
+          EXIT_FAILURE
+        })?;
+
+      let mut invocation = vec![recipe.name().to_owned()];
+      invocation.extend(Self::arguments(recipe)?);
+      chosen.push(invocation);
+    }
+
+    Ok(chosen)
+  }
+
+  /// Pipe recipes to the chooser command, returning the names of the recipes
+  /// it prints, or `None` if `$JUST_CHOOSER` isn't set and `fzf` isn't
+  /// installed
+  fn external(&self) -> Result<Option<Vec<String>>, i32> {
+    let chooser = env::var_os("JUST_CHOOSER");
+
+    let mut command = match &chooser {
+      Some(chooser) => {
+        let mut command = Command::new(&self.config.shell);
+        command.args(&self.config.shell_args).arg(chooser);
+        command
+      },
+      None => {
+        let mut command = Command::new("fzf");
+        command.args(&["--multi", "--prompt", "just> "]);
+        command
+      },
+    };
+
+    let child = command
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn();
+
+    let name = chooser
+      .as_ref()
+      .map(|chooser| chooser.to_string_lossy())
+      .unwrap_or_else(|| "fzf".into());
+
+    let mut child = match child {
+      Ok(child) => child,
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound && chooser.is_none() =>
+        return Ok(None),
+      Err(io_error) => {
+        eprintln!("Chooser `{}` invocation failed: {}", name, io_error);
+        return Err(EXIT_FAILURE);
+      },
+    };
+
+    {
+      let mut stdin = child.stdin.take().expect("chooser stdin was not piped");
+
+      for line in Self::lines(&self.recipes) {
+        // the chooser may exit before reading all recipes
+        if writeln!(stdin, "{}", line).is_err() {
+          break;
+        }
+      }
+    }
+
+    let output = child.wait_with_output().map_err(|io_error| {
+      eprintln!("Chooser `{}` invocation failed: {}", name, io_error);
+      EXIT_FAILURE
+    })?;
+
+    if !output.status.success() {
+      eprintln!("Chooser `{}` failed: {}", name, output.status);
+      return Err(output.status.code().unwrap_or(EXIT_FAILURE));
+    }
+
+    Ok(Some(
+      String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_owned)
+        .collect(),
+    ))
+  }
+
+  /// Print numbered `candidates` and read recipe numbers or names from
+  /// stdin. Any other input narrows `candidates` to those whose name or doc
+  /// contains it, and prompts again.
+  fn prompt(candidates: &[&Recipe]) -> Result<Vec<String>, i32> {
+    let width = candidates.len().to_string().len();
+
+    for (i, line) in Self::lines(candidates).iter().enumerate() {
+      eprintln!("{:>width$}) {}", i + 1, line, width = width);
+    }
+
+    let input = Self::read_line("Choose recipes by number or name, or type to filter: ")?;
+
+    let words = input.split_whitespace().collect::<Vec<&str>>();
+
+    let names = words
+      .iter()
+      .map(|word| match word.parse::<usize>() {
+        Ok(n) if n >= 1 && n <= candidates.len() => Some(candidates[n - 1].name()),
+        _ => candidates
+          .iter()
+          .map(|recipe| recipe.name())
+          .find(|name| name == word),
+      })
+      .collect::<Option<Vec<&str>>>();
+
+    if let Some(names) = names {
+      return Ok(names.into_iter().map(str::to_owned).collect());
+    }
+
+    let filter = input.trim().to_lowercase();
+
+    let filtered = candidates
+      .iter()
+      .filter(|recipe| {
+        recipe.name().to_lowercase().contains(&filter)
+          || recipe
+            .doc
+            .as_deref()
+            .map(|doc| doc.to_lowercase().contains(&filter))
+            .unwrap_or(false)
+      })
+      .copied()
+      .collect::<Vec<&Recipe>>();
+
+    if filtered.is_empty() {
+      eprintln!("No recipes match `{}`", input.trim());
+      Self::prompt(candidates)
+    } else {
+      Self::prompt(&filtered)
+    }
+  }
+
+  /// Read values for the required parameters of `recipe` from stdin
+  fn arguments(recipe: &Recipe) -> Result<Vec<String>, i32> {
+    let mut arguments = Vec::new();
+
+    for parameter in &recipe.parameters {
+      if parameter.default.is_some() {
+        continue;
+      }
+
+      let prompt = format!("{} {}: ", recipe.name(), parameter);
+
+      loop {
+        let input = Self::read_line(&prompt)?;
+
+        if parameter.variadic {
+          let values = input.split_whitespace().collect::<Vec<&str>>();
+
+          if !values.is_empty() {
+            arguments.extend(values.into_iter().map(str::to_owned));
+            break;
+          }
+        } else if !input.is_empty() {
+          arguments.push(input);
+          break;
+        }
+      }
+    }
+
+    Ok(arguments)
+  }
+
+  /// Print `prompt` to stderr, and read a line from stdin, without the
+  /// trailing newline
+  fn read_line(prompt: &str) -> Result<String, i32> {
+    eprint!("{}", prompt);
+
+    let mut line = String::new();
+
+    match io::stdin().read_line(&mut line) {
+      Ok(0) => {
+        eprintln!();
+        eprintln!("Stdin closed before a choice was made");
+        Err(EXIT_FAILURE)
+      },
+      Ok(_) => Ok(line.trim_end_matches(&['\r', '\n'][..]).to_owned()),
+      Err(io_error) => {
+        eprintln!("Failed to read from stdin: {}", io_error);
+        Err(EXIT_FAILURE)
+      },
+    }
+  }
+
+  /// Recipe names and parameters, followed by aligned docs
+  fn lines(recipes: &[&Recipe]) -> Vec<String> {
+    let signatures = recipes
+      .iter()
+      .map(|recipe| {
+        let mut signature = recipe.name().to_owned();
+
+        for parameter in &recipe.parameters {
+          signature += &format!(" {}", parameter);
+        }
+
+        signature
+      })
+      .collect::<Vec<String>>();
+
+    let width = signatures
+      .iter()
+      .map(|signature| UnicodeWidthStr::width(signature.as_str()))
+      .max()
+      .unwrap_or(0);
+
+    recipes
+      .iter()
+      .zip(signatures)
+      .map(|(recipe, signature)| match recipe.doc_summary() {
+        Some(doc) => {
+          let padding = width - UnicodeWidthStr::width(signature.as_str());
+          format!("{}{} # {}", signature, " ".repeat(padding), doc)
+        },
+        None => signature,
+      })
+      .collect()
+  }
+}
@@ -1,9 +1,31 @@
 use crate::common::*;
 
+use std::{
+  process::{Child, ExitStatus},
+  thread,
+};
+
+/// How long a child is given to exit after being asked to terminate, before it
+/// is killed
+const TERMINATION_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// How often a child with a deadline is checked for having exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 pub(crate) trait CommandExt {
   fn export(&mut self, dotenv: &BTreeMap<String, String>, scope: &Scope);
 
   fn export_scope(&mut self, scope: &Scope);
+
+  /// Run the command to completion, like `Command::status`, forwarding its
+  /// output to `sinks`. If `deadline` is reached first, the child and its
+  /// descendants are asked to terminate, killed if they haven't exited after a
+  /// grace period, and `None` is returned.
+  fn status_until(
+    &mut self,
+    deadline: Option<Instant>,
+    sinks: &Sinks,
+  ) -> io::Result<Option<ExitStatus>>;
 }
 
 impl CommandExt for Command {
@@ -28,4 +50,107 @@ impl CommandExt for Command {
       }
     }
   }
+
+  fn status_until(
+    &mut self,
+    deadline: Option<Instant>,
+    sinks: &Sinks,
+  ) -> io::Result<Option<ExitStatus>> {
+    if deadline.is_none() && !sinks.active() {
+      return self.status().map(Some);
+    }
+
+    sinks.pipe(self);
+
+    let deadline = if let Some(deadline) = deadline {
+      deadline
+    } else {
+      let mut child = self.spawn()?;
+      let forwarders = sinks.forward(&mut child);
+      let exit_status = child.wait()?;
+      join(forwarders);
+      return Ok(Some(exit_status));
+    };
+
+    // the child is run in its own process group, so that its descendants can
+    // be terminated along with it
+    let foreground = Platform::new_process_group(self);
+
+    let result = self
+      .spawn()
+      .and_then(|mut child| wait_until(&mut child, deadline, sinks));
+
+    if foreground {
+      Platform::restore_foreground().ok();
+
+      if let Ok(Some(exit_status)) = result {
+        if Platform::signal_from_exit_status(exit_status) == Some(libc::SIGINT) {
+          InterruptHandler::instance().interrupt();
+        }
+      }
+    }
+
+    result
+  }
+}
+
+/// Wait for `child` to exit, forwarding its output to `sinks`. If `deadline`
+/// is reached first, terminate its process group and return `None`. If an
+/// interrupt is received first, which the child may not have seen, since it
+/// is in its own process group, terminate its process group and return its
+/// exit status.
+fn wait_until(
+  child: &mut Child,
+  deadline: Instant,
+  sinks: &Sinks,
+) -> io::Result<Option<ExitStatus>> {
+  let forwarders = sinks.forward(child);
+
+  // forwarders are not joined after the child is terminated, since the
+  // child's own children may still be holding its output open
+  loop {
+    if let Some(exit_status) = child.try_wait()? {
+      join(forwarders);
+      return Ok(Some(exit_status));
+    }
+
+    if InterruptHandler::instance().interrupted() {
+      return terminate(child).map(Some);
+    }
+
+    let now = Instant::now();
+
+    if now >= deadline {
+      terminate(child)?;
+      return Ok(None);
+    }
+
+    thread::sleep(cmp::min(POLL_INTERVAL, deadline - now));
+  }
+}
+
+/// Ask the process group of `child` to terminate, kill it if `child` has not
+/// exited after a grace period, and wait for `child` to exit
+pub(crate) fn terminate(child: &mut Child) -> io::Result<ExitStatus> {
+  if Platform::terminate_process_group(child, false).is_ok() {
+    let grace_deadline = Instant::now() + TERMINATION_GRACE_PERIOD;
+
+    while Instant::now() < grace_deadline {
+      if let Ok(Some(exit_status)) = child.try_wait() {
+        return Ok(exit_status);
+      }
+
+      thread::sleep(POLL_INTERVAL);
+    }
+  }
+
+  Platform::terminate_process_group(child, true).ok();
+  child.wait()
+}
+
+/// Wait for all output to be copied to sinks
+fn join(forwarders: Vec<thread::JoinHandle<()>>) {
+  for forwarder in forwarders {
+    forwarder.join().ok();
+  }
 }
@@ -14,6 +14,7 @@ pub(crate) use std::{
   rc::Rc,
   str::{self, Chars},
   sync::{Mutex, MutexGuard},
+  time::{Duration, Instant},
   usize, vec,
 };
 
@@ -30,6 +31,7 @@ pub(crate) use derivative::Derivative;
 pub(crate) use edit_distance::edit_distance;
 pub(crate) use libc::EXIT_FAILURE;
 pub(crate) use log::{info, warn};
+pub(crate) use similar::{ChangeTag, TextDiff};
 pub(crate) use snafu::{ResultExt, Snafu};
 pub(crate) use unicode_width::UnicodeWidthChar;
 
@@ -48,23 +50,28 @@ pub(crate) use crate::{
 
 // structs and enums
 pub(crate) use crate::{
-  alias::Alias, analyzer::Analyzer, assignment::Assignment,
-  assignment_resolver::AssignmentResolver, binding::Binding, color::Color,
+  alias::Alias, analyzer::Analyzer, assignment::Assignment, assignment_resolver::AssignmentResolver,
+  attribute::Attribute, binding::Binding, chooser::Chooser, color::Color,
   compilation_error::CompilationError, compilation_error_kind::CompilationErrorKind,
-  compiler::Compiler, config::Config, config_error::ConfigError, count::Count,
-  dependency::Dependency, enclosure::Enclosure, evaluator::Evaluator, expression::Expression,
-  fragment::Fragment, function::Function, function_context::FunctionContext,
-  interrupt_guard::InterruptGuard, interrupt_handler::InterruptHandler, item::Item,
-  justfile::Justfile, lexer::Lexer, line::Line, list::List, load_error::LoadError, module::Module,
-  name::Name, output_error::OutputError, parameter::Parameter, parser::Parser, platform::Platform,
+  compiler::Compiler, completer::Completer, config::Config, config_error::ConfigError, count::Count,
+  dependency::Dependency, diagnostic::Diagnostic, docs::Docs, document::Document, dump::Dump,
+  dump_format::DumpFormat, enclosure::Enclosure, evaluator::Evaluator, expression::Expression,
+  file_pattern::FilePattern, fragment::Fragment, function::Function,
+  function_context::FunctionContext, graph::Graph, graph_format::GraphFormat,
+  human_duration::HumanDuration, interrupt_guard::InterruptGuard,
+  interrupt_handler::InterruptHandler, item::Item, justfile::Justfile,
+  language_server::LanguageServer, lexer::Lexer, line::Line, lint::Lint, linter::Linter, list::List,
+  load_error::LoadError, message_format::MessageFormat, module::Module, name::Name,
+  output_error::OutputError, parameter::Parameter, parser::Parser, plan::Plan, platform::Platform,
   position::Position, positional::Positional, recipe::Recipe, recipe_context::RecipeContext,
-  recipe_resolver::RecipeResolver, runtime_error::RuntimeError, scope::Scope, search::Search,
-  search_config::SearchConfig, search_error::SearchError, set::Set, setting::Setting,
-  settings::Settings, shebang::Shebang, show_whitespace::ShowWhitespace,
-  string_literal::StringLiteral, subcommand::Subcommand, suggestion::Suggestion, table::Table,
-  thunk::Thunk, token::Token, token_kind::TokenKind, unresolved_dependency::UnresolvedDependency,
-  unresolved_recipe::UnresolvedRecipe, use_color::UseColor, variables::Variables,
-  verbosity::Verbosity, warning::Warning,
+  recipe_resolver::RecipeResolver, reformatter::Reformatter, retry::Retry,
+  runtime_error::RuntimeError, scope::Scope, search::Search, search_config::SearchConfig,
+  search_error::SearchError, set::Set, setting::Setting, settings::Settings, severity::Severity,
+  shebang::Shebang, show_whitespace::ShowWhitespace, sinks::Sinks, string_literal::StringLiteral,
+  subcommand::Subcommand, suggestion::Suggestion, table::Table, thunk::Thunk, token::Token,
+  token_kind::TokenKind, unresolved_dependency::UnresolvedDependency,
+  unresolved_recipe::UnresolvedRecipe, usage::Usage, use_color::UseColor, variables::Variables,
+  verbosity::Verbosity, warning::Warning, watcher::Watcher,
 };
 
 // type aliases
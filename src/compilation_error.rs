@@ -8,12 +8,18 @@ pub(crate) struct CompilationError<'src> {
 
 impl Error for CompilationError<'_> {}
 
-impl Display for CompilationError<'_> {
-  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    use CompilationErrorKind::*;
-    let message = Color::fmt(f).message();
+impl<'src> CompilationError<'src> {
+  /// The error message, without source context
+  pub(crate) fn message(&self) -> String {
+    let mut message = String::new();
+    self
+      .write_message(&mut message)
+      .expect("writing to a string cannot fail");
+    message.trim_end().to_owned()
+  }
 
-    write!(f, "{}", message.prefix())?;
+  fn write_message(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+    use CompilationErrorKind::*;
 
     match self.kind {
       AliasShadowsRecipe { alias, recipe_line } => {
@@ -26,6 +32,23 @@ impl Display for CompilationError<'_> {
           recipe_line.ordinal(),
         )?;
       },
+      AttributeArgumentCountMismatch {
+        attribute,
+        found,
+        expected,
+      } => {
+        writeln!(
+          f,
+          "Attribute `{}` got {} {} but takes {}",
+          attribute,
+          found,
+          Count("argument", found),
+          expected
+        )?;
+      },
+      AttributeWithoutRecipe { attribute } => {
+        writeln!(f, "Attribute `{}` is not followed by a recipe", attribute)?;
+      },
       CircularRecipeDependency { recipe, ref circle } =>
         if circle.len() == 2 {
           writeln!(f, "Recipe `{}` depends on itself", recipe)?;
@@ -37,6 +60,17 @@ impl Display for CompilationError<'_> {
             circle.join(" -> ")
           )?;
         },
+      CircularFinallyRecipe { recipe, ref circle } =>
+        if circle.len() == 2 {
+          writeln!(f, "Recipe `{}` is its own finally recipe", recipe)?;
+        } else {
+          writeln!(
+            f,
+            "Recipe `{}` has circular finally recipes `{}`",
+            recipe,
+            circle.join(" -> ")
+          )?;
+        },
       CircularVariableDependency {
         variable,
         ref circle,
@@ -52,6 +86,24 @@ impl Display for CompilationError<'_> {
           )?;
         },
 
+      InvalidAttributeArgument {
+        attribute,
+        value,
+        expected,
+      } => {
+        writeln!(
+          f,
+          "Invalid argument `{}` to attribute `{}`, expected {}",
+          value, attribute, expected
+        )?;
+      },
+      InvalidDuration { duration } => {
+        writeln!(
+          f,
+          "Invalid duration `{}`, durations are an integer followed by `ms`, `s`, `m`, or `h`",
+          duration
+        )?;
+      },
       InvalidEscapeSequence { character } => {
         let representation = match character {
           '`' => r"\`".to_string(),
@@ -87,6 +139,25 @@ impl Display for CompilationError<'_> {
           self.token.line.ordinal(),
         )?;
       },
+      DuplicateAttributeArgument {
+        attribute,
+        argument,
+      } => {
+        writeln!(
+          f,
+          "Attribute `{}` argument `{}` is given more than once",
+          attribute, argument
+        )?;
+      },
+      DuplicateAttribute { attribute, first } => {
+        writeln!(
+          f,
+          "Attribute `{}` first used on line {} is duplicated on line {}",
+          attribute,
+          first.ordinal(),
+          self.token.line.ordinal(),
+        )?;
+      },
       DuplicateRecipe { recipe, first } => {
         writeln!(
           f,
@@ -128,6 +199,37 @@ impl Display for CompilationError<'_> {
           writeln!(f, "at most {} {}", max, Count("argument", max))?;
         }
       },
+      FinallyRecipeRequiresArguments {
+        recipe,
+        min_arguments,
+      } => {
+        writeln!(
+          f,
+          "Finally recipe `{}` requires {} {}, but finally recipes are run without arguments",
+          recipe,
+          min_arguments,
+          Count("argument", min_arguments),
+        )?;
+      },
+      HookRecipeRequiresArguments {
+        setting,
+        recipe,
+        min_arguments,
+      } => {
+        writeln!(
+          f,
+          "Recipe `{}` requires {} {}, but `{}` hooks are run {}",
+          recipe,
+          min_arguments,
+          Count("argument", min_arguments),
+          setting,
+          if setting == keyword::AFTER {
+            "with at most one argument"
+          } else {
+            "without arguments"
+          },
+        )?;
+      },
       ParameterShadowsVariable { parameter } => {
         writeln!(
           f,
@@ -153,6 +255,14 @@ impl Display for CompilationError<'_> {
           ShowWhitespace(whitespace)
         )?;
       },
+      ExpectedKeyword { ref expected, found } => {
+        writeln!(
+          f,
+          "Expected keyword {} but found identifier `{}`",
+          List::or_ticked(expected),
+          found
+        )?;
+      },
       ExtraLeadingWhitespace => {
         writeln!(f, "Recipe line has extra leading whitespace")?;
       },
@@ -179,6 +289,30 @@ impl Display for CompilationError<'_> {
           ShowWhitespace(found)
         )?;
       },
+      UnknownAttribute { attribute } => {
+        writeln!(f, "Unknown attribute `{}`", attribute)?;
+      },
+      UnknownAttributeArgument {
+        attribute,
+        argument,
+      } => {
+        writeln!(
+          f,
+          "Attribute `{}` has no argument named `{}`",
+          attribute, argument
+        )?;
+      },
+      UnknownAttributeParameter {
+        attribute,
+        recipe,
+        parameter,
+      } => {
+        writeln!(
+          f,
+          "Attribute `{}` on recipe `{}` names unknown parameter `{}`",
+          attribute, recipe, parameter
+        )?;
+      },
       UnknownAliasTarget { alias, target } => {
         writeln!(f, "Alias `{}` has an unknown target `{}`", alias, target)?;
       },
@@ -192,6 +326,16 @@ impl Display for CompilationError<'_> {
       UndefinedVariable { variable } => {
         writeln!(f, "Variable `{}` not defined", variable)?;
       },
+      UnknownFinallyRecipe { recipe, ref unknown } => {
+        writeln!(
+          f,
+          "Recipe `{}` has unknown finally recipe `{}`",
+          recipe, unknown
+        )?;
+      },
+      UnknownHookRecipe { setting, recipe } => {
+        writeln!(f, "Setting `{}` names unknown recipe `{}`", setting, recipe)?;
+      },
       UnknownFunction { function } => {
         writeln!(f, "Call to unknown function `{}`", function)?;
       },
@@ -223,6 +367,18 @@ impl Display for CompilationError<'_> {
       },
     }
 
+    Ok(())
+  }
+}
+
+impl Display for CompilationError<'_> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    let message = Color::fmt(f).message();
+
+    write!(f, "{}", message.prefix())?;
+
+    self.write_message(f)?;
+
     write!(f, "{}", message.suffix())?;
 
     self.token.write_context(f, Color::fmt(f).error())
@@ -6,10 +6,22 @@ pub(crate) enum CompilationErrorKind<'src> {
     alias:       &'src str,
     recipe_line: usize,
   },
+  AttributeArgumentCountMismatch {
+    attribute: &'src str,
+    found:     usize,
+    expected:  usize,
+  },
+  AttributeWithoutRecipe {
+    attribute: &'src str,
+  },
   CircularRecipeDependency {
     recipe: &'src str,
     circle: Vec<&'src str>,
   },
+  CircularFinallyRecipe {
+    recipe: &'src str,
+    circle: Vec<&'src str>,
+  },
   CircularVariableDependency {
     variable: &'src str,
     circle:   Vec<&'src str>,
@@ -24,6 +36,14 @@ pub(crate) enum CompilationErrorKind<'src> {
     alias: &'src str,
     first: usize,
   },
+  DuplicateAttribute {
+    attribute: &'src str,
+    first:     usize,
+  },
+  DuplicateAttributeArgument {
+    attribute: &'src str,
+    argument:  &'src str,
+  },
   DuplicateParameter {
     recipe:    &'src str,
     parameter: &'src str,
@@ -39,12 +59,25 @@ pub(crate) enum CompilationErrorKind<'src> {
     setting: &'src str,
     first:   usize,
   },
+  ExpectedKeyword {
+    expected: Vec<&'src str>,
+    found:    &'src str,
+  },
   ExtraLeadingWhitespace,
+  FinallyRecipeRequiresArguments {
+    recipe:        &'src str,
+    min_arguments: usize,
+  },
   FunctionArgumentCountMismatch {
     function: &'src str,
     found:    usize,
     expected: usize,
   },
+  HookRecipeRequiresArguments {
+    setting:       &'src str,
+    recipe:        &'src str,
+    min_arguments: usize,
+  },
   InconsistentLeadingWhitespace {
     expected: &'src str,
     found:    &'src str,
@@ -52,6 +85,14 @@ pub(crate) enum CompilationErrorKind<'src> {
   Internal {
     message: String,
   },
+  InvalidAttributeArgument {
+    attribute: &'src str,
+    value:     &'src str,
+    expected:  &'static str,
+  },
+  InvalidDuration {
+    duration: &'src str,
+  },
   InvalidEscapeSequence {
     character: char,
   },
@@ -74,6 +115,18 @@ pub(crate) enum CompilationErrorKind<'src> {
     expected: Vec<TokenKind>,
     found:    TokenKind,
   },
+  UnknownAttribute {
+    attribute: &'src str,
+  },
+  UnknownAttributeArgument {
+    attribute: &'src str,
+    argument:  &'src str,
+  },
+  UnknownAttributeParameter {
+    attribute: &'src str,
+    recipe:    &'src str,
+    parameter: &'src str,
+  },
   UnknownAliasTarget {
     alias:  &'src str,
     target: &'src str,
@@ -82,9 +135,17 @@ pub(crate) enum CompilationErrorKind<'src> {
     recipe:  &'src str,
     unknown: &'src str,
   },
+  UnknownFinallyRecipe {
+    recipe:  &'src str,
+    unknown: String,
+  },
   UnknownFunction {
     function: &'src str,
   },
+  UnknownHookRecipe {
+    setting: &'src str,
+    recipe:  &'src str,
+  },
   UnknownStartOfToken,
   UnknownSetting {
     setting: &'src str,
@@ -94,3 +155,73 @@ pub(crate) enum CompilationErrorKind<'src> {
   UnterminatedString,
   UnterminatedBacktick,
 }
+
+impl<'src> CompilationErrorKind<'src> {
+  /// A stable code identifying this kind of error, used in diagnostics
+  /// printed with `--message-format json`
+  pub(crate) fn diagnostic_code(&self) -> &'static str {
+    use CompilationErrorKind::*;
+
+    match self {
+      AliasShadowsRecipe { .. } => "E0001",
+      AttributeArgumentCountMismatch { .. } => "E0002",
+      AttributeWithoutRecipe { .. } => "E0003",
+      CircularRecipeDependency { .. } => "E0004",
+      CircularFinallyRecipe { .. } => "E0005",
+      CircularVariableDependency { .. } => "E0006",
+      DependencyArgumentCountMismatch { .. } => "E0007",
+      DuplicateAlias { .. } => "E0008",
+      DuplicateAttribute { .. } => "E0009",
+      DuplicateAttributeArgument { .. } => "E0010",
+      DuplicateParameter { .. } => "E0011",
+      DuplicateRecipe { .. } => "E0012",
+      DuplicateVariable { .. } => "E0013",
+      DuplicateSet { .. } => "E0014",
+      ExtraLeadingWhitespace => "E0015",
+      FinallyRecipeRequiresArguments { .. } => "E0016",
+      FunctionArgumentCountMismatch { .. } => "E0017",
+      HookRecipeRequiresArguments { .. } => "E0018",
+      InconsistentLeadingWhitespace { .. } => "E0019",
+      Internal { .. } => "E0020",
+      InvalidAttributeArgument { .. } => "E0021",
+      InvalidDuration { .. } => "E0022",
+      InvalidEscapeSequence { .. } => "E0023",
+      MixedLeadingWhitespace { .. } => "E0024",
+      ParameterFollowsVariadicParameter { .. } => "E0025",
+      ParameterShadowsVariable { .. } => "E0026",
+      RequiredParameterFollowsDefaultParameter { .. } => "E0027",
+      UndefinedVariable { .. } => "E0028",
+      UnexpectedToken { .. } => "E0029",
+      UnknownAttribute { .. } => "E0030",
+      UnknownAttributeArgument { .. } => "E0031",
+      UnknownAliasTarget { .. } => "E0032",
+      UnknownDependency { .. } => "E0033",
+      UnknownFinallyRecipe { .. } => "E0034",
+      UnknownFunction { .. } => "E0035",
+      UnknownHookRecipe { .. } => "E0036",
+      UnknownStartOfToken => "E0037",
+      UnknownSetting { .. } => "E0038",
+      UnpairedCarriageReturn => "E0039",
+      UnterminatedInterpolation => "E0040",
+      UnterminatedString => "E0041",
+      UnterminatedBacktick => "E0042",
+      UnknownAttributeParameter { .. } => "E0043",
+      ExpectedKeyword { .. } => "E0044",
+    }
+  }
+
+  /// Whether this error refers to a recipe or variable by name, and so may
+  /// be caused by an item that failed to parse
+  pub(crate) fn is_unresolved_reference(&self) -> bool {
+    use CompilationErrorKind::*;
+
+    match self {
+      UndefinedVariable { .. }
+      | UnknownAliasTarget { .. }
+      | UnknownDependency { .. }
+      | UnknownFinallyRecipe { .. }
+      | UnknownHookRecipe { .. } => true,
+      _ => false,
+    }
+  }
+}
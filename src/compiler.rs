@@ -3,11 +3,40 @@ use crate::common::*;
 pub(crate) struct Compiler;
 
 impl Compiler {
-  pub(crate) fn compile(src: &str) -> CompilationResult<Justfile> {
-    let tokens = Lexer::lex(src)?;
+  /// The maximum number of errors printed for a single justfile
+  pub(crate) const MAX_ERRORS: usize = 32;
 
-    let ast = Parser::parse(&tokens)?;
+  pub(crate) fn compile(src: &str) -> Result<Justfile, Vec<CompilationError>> {
+    let tokens = Lexer::lex(src).map_err(|error| vec![error])?;
 
-    Analyzer::analyze(ast)
+    let (module, errors) = Parser::parse_partial(&tokens);
+
+    Self::analyze(module, errors)
+  }
+
+  /// Analyze `module`, the items of a justfile that could be parsed, and
+  /// combine the resulting errors with `errors` from parsing, sorted by
+  /// position. Errors referring to recipes or variables by name are omitted if
+  /// parsing failed, since the item referred to may be one that failed to
+  /// parse.
+  pub(crate) fn analyze<'src>(
+    module: Module<'src>,
+    mut errors: Vec<CompilationError<'src>>,
+  ) -> Result<Justfile<'src>, Vec<CompilationError<'src>>> {
+    let parsed = errors.is_empty();
+
+    match Analyzer::analyze(module) {
+      Ok(justfile) if parsed => return Ok(justfile),
+      Ok(_) => {},
+      Err(analysis_errors) => errors.extend(
+        analysis_errors
+          .into_iter()
+          .filter(|error| parsed || !error.kind.is_unresolved_reference()),
+      ),
+    }
+
+    errors.sort_by_key(|error| error.token.offset);
+
+    Err(errors)
   }
 }
@@ -0,0 +1,346 @@
+use crate::common::*;
+
+/// Completions for the word under the cursor, printed by the hidden
+/// `--complete` subcommand, which shell completion scripts call
+pub(crate) struct Completer<'src: 'run, 'run> {
+  /// Directory that relative paths are completed in
+  directory: &'run Path,
+  justfile:  &'run Justfile<'src>,
+}
+
+/// A completion, and a description for shells that show them
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Completion {
+  value:       String,
+  description: Option<String>,
+}
+
+impl<'src: 'run, 'run> Completer<'src, 'run> {
+  pub(crate) fn new(justfile: &'run Justfile<'src>, directory: &'run Path) -> Self {
+    Self {
+      directory,
+      justfile,
+    }
+  }
+
+  /// Complete `current`, the word after positional `arguments`, which are
+  /// grouped into recipes and their arguments like `Justfile::run` does
+  pub(crate) fn complete(&self, arguments: &[String], current: &str) -> Vec<Completion> {
+    let mut last = None;
+    let mut rest = arguments;
+
+    while let Some((name, tail)) = rest.split_first() {
+      let recipe = match self.justfile.get_recipe(name) {
+        Some(recipe) => recipe,
+        None => return Vec::new(),
+      };
+
+      let count = cmp::min(tail.len(), recipe.max_arguments());
+      last = Some((recipe, count));
+      rest = &tail[count..];
+    }
+
+    let mut completions = Vec::new();
+
+    match last {
+      None => {
+        completions.extend(self.recipes(current));
+        completions.extend(self.variables(current));
+      },
+      Some((recipe, count)) => {
+        if count < recipe.max_arguments() {
+          let parameter = &recipe.parameters[cmp::min(count, recipe.parameters.len() - 1)];
+          completions.extend(self.parameter(recipe, parameter.name.lexeme(), current));
+        }
+
+        if count >= recipe.min_arguments() {
+          completions.extend(self.recipes(current));
+        }
+      },
+    }
+
+    completions
+  }
+
+  /// Public recipes and aliases starting with `current`, with their docs
+  pub(crate) fn recipes(&self, current: &str) -> Vec<Completion> {
+    let recipes = self
+      .justfile
+      .public_recipes(false)
+      .into_iter()
+      .map(|recipe| Completion {
+        value:       recipe.name().to_owned(),
+        description: recipe.doc_summary().map(str::to_owned),
+      });
+
+    let aliases = self
+      .justfile
+      .aliases
+      .values()
+      .filter(|alias| !alias.is_private())
+      .map(|alias| Completion {
+        value:       alias.name.lexeme().to_owned(),
+        description: Some(format!("alias for `{}`", alias.target.name.lexeme())),
+      });
+
+    let mut completions = recipes
+      .chain(aliases)
+      .filter(|completion| completion.value.starts_with(current))
+      .collect::<Vec<Completion>>();
+
+    completions.sort();
+
+    completions
+  }
+
+  /// `NAME=` overrides for variables starting with `current`
+  fn variables(&self, current: &str) -> Vec<Completion> {
+    self
+      .justfile
+      .assignments
+      .keys()
+      .map(|name| format!("{}=", name))
+      .filter(|value| value.starts_with(current))
+      .map(|value| Completion {
+        description: Some("variable".to_owned()),
+        value,
+      })
+      .collect()
+  }
+
+  /// Choices for `parameter` starting with `current`, and file paths if it is
+  /// declared to be a path
+  fn parameter(&self, recipe: &Recipe, parameter: &str, current: &str) -> Vec<Completion> {
+    let mut completions = recipe
+      .choices(parameter)
+      .filter(|choice| choice.starts_with(current))
+      .map(|choice| Completion {
+        value:       choice.to_owned(),
+        description: None,
+      })
+      .collect::<Vec<Completion>>();
+
+    if recipe.is_path(parameter) {
+      completions.extend(self.paths(current));
+    }
+
+    completions
+  }
+
+  /// Paths starting with `current`, with a trailing `/` on directories.
+  /// Hidden files are only completed if `current` names one.
+  fn paths(&self, current: &str) -> Vec<Completion> {
+    let (directory, prefix) = match current.rfind('/') {
+      Some(i) => current.split_at(i + 1),
+      None => ("", current),
+    };
+
+    let entries = match fs::read_dir(self.directory.join(directory)) {
+      Ok(entries) => entries,
+      Err(_) => return Vec::new(),
+    };
+
+    let mut completions = Vec::new();
+
+    for entry in entries.filter_map(Result::ok) {
+      let name = entry.file_name();
+
+      let name = match name.to_str() {
+        Some(name) => name,
+        None => continue,
+      };
+
+      if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+        continue;
+      }
+
+      let mut value = format!("{}{}", directory, name);
+
+      if entry.path().is_dir() {
+        value.push('/');
+      }
+
+      completions.push(Completion {
+        description: None,
+        value,
+      });
+    }
+
+    completions.sort();
+
+    completions
+  }
+
+  /// Format `completions` for `shell`, one per line. Bash doesn't show
+  /// descriptions, Zsh expects `VALUE:DESCRIPTION`, and Fish expects
+  /// `VALUE<TAB>DESCRIPTION`.
+  pub(crate) fn render(completions: &[Completion], shell: &str) -> String {
+    let mut output = String::new();
+
+    for completion in completions {
+      match (shell, &completion.description) {
+        ("zsh", Some(description)) => output += &format!(
+          "{}:{}",
+          completion.value.replace(':', "\\:"),
+          description
+        ),
+        ("zsh", None) => output += &completion.value.replace(':', "\\:"),
+        ("fish", Some(description)) =>
+          output += &format!("{}\t{}", completion.value, description),
+        _ => output += &completion.value,
+      }
+
+      output.push('\n');
+    }
+
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  macro_rules! test {
+    {
+      name: $name:ident,
+      justfile: $justfile:expr,
+      arguments: [$($argument:expr),*],
+      current: $current:expr,
+      completions: [$($completion:expr),*],
+    } => {
+      #[test]
+      fn $name() {
+        let src = testing::unindent($justfile);
+        let justfile = testing::compile(&src);
+        let directory = env::current_dir().unwrap();
+
+        let arguments: &[&str] = &[$($argument),*];
+
+        let arguments = arguments
+          .iter()
+          .map(|argument| (*argument).to_owned())
+          .collect::<Vec<String>>();
+
+        let have = Completer::new(&justfile, &directory)
+          .complete(&arguments, $current)
+          .into_iter()
+          .map(|completion| completion.value)
+          .collect::<Vec<String>>();
+
+        let want: &[&str] = &[$($completion),*];
+
+        assert_eq!(have, want);
+      }
+    }
+  }
+
+  const JUSTFILE: &str = "
+    mode = 'debug'
+
+    # build the project
+    build profile='dev':
+
+    [choices('target', 'staging', 'production')]
+    deploy target:
+
+    test +flags:
+
+    _setup:
+
+    alias b := build
+  ";
+
+  test! {
+    name: first_word,
+    justfile: JUSTFILE,
+    arguments: [],
+    current: "",
+    completions: ["b", "build", "deploy", "test", "mode="],
+  }
+
+  test! {
+    name: prefix,
+    justfile: JUSTFILE,
+    arguments: [],
+    current: "b",
+    completions: ["b", "build"],
+  }
+
+  test! {
+    name: choices,
+    justfile: JUSTFILE,
+    arguments: ["deploy"],
+    current: "",
+    completions: ["staging", "production"],
+  }
+
+  test! {
+    name: choices_prefix,
+    justfile: JUSTFILE,
+    arguments: ["deploy"],
+    current: "p",
+    completions: ["production"],
+  }
+
+  test! {
+    name: optional_parameter,
+    justfile: JUSTFILE,
+    arguments: ["build"],
+    current: "",
+    completions: ["b", "build", "deploy", "test"],
+  }
+
+  test! {
+    name: after_all_arguments,
+    justfile: JUSTFILE,
+    arguments: ["deploy", "staging"],
+    current: "t",
+    completions: ["test"],
+  }
+
+  test! {
+    name: variadic_parameter,
+    justfile: JUSTFILE,
+    arguments: ["test", "--all"],
+    current: "b",
+    completions: ["b", "build"],
+  }
+
+  test! {
+    name: unknown_recipe,
+    justfile: JUSTFILE,
+    arguments: ["bogus"],
+    current: "",
+    completions: [],
+  }
+
+  #[test]
+  fn render() {
+    let completions = vec![
+      Completion {
+        value:       "build".to_owned(),
+        description: Some("build the project".to_owned()),
+      },
+      Completion {
+        value:       "a:b".to_owned(),
+        description: None,
+      },
+    ];
+
+    assert_eq!(
+      Completer::render(&completions, "bash"),
+      "build\na:b\n"
+    );
+    assert_eq!(
+      Completer::render(&completions, "zsh"),
+      "build:build the project\na\\:b\n"
+    );
+    assert_eq!(
+      Completer::render(&completions, "fish"),
+      "build\tbuild the project\na:b\n"
+    );
+  }
+}
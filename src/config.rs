@@ -1,6 +1,7 @@
 use crate::common::*;
 
 use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, ArgSettings};
+use std::io::Read;
 use unicode_width::UnicodeWidthStr;
 
 pub(crate) const DEFAULT_SHELL: &str = "sh";
@@ -10,76 +11,157 @@ pub(crate) const INIT_JUSTFILE: &str = "default:\n\techo 'Hello, world!'\n";
 #[derive(Debug, PartialEq)]
 pub(crate) struct Config {
   pub(crate) color:                Color,
+  pub(crate) deny_warnings:        bool,
   pub(crate) dry_run:              bool,
+  pub(crate) global_fallback:      bool,
   pub(crate) highlight:            bool,
   pub(crate) invocation_directory: PathBuf,
+  pub(crate) message_format:       MessageFormat,
   pub(crate) quiet:                bool,
   pub(crate) search_config:        SearchConfig,
   pub(crate) shell:                String,
   pub(crate) shell_args:           Vec<String>,
   pub(crate) shell_present:        bool,
+  pub(crate) sinks:                Sinks,
   pub(crate) subcommand:           Subcommand,
+  pub(crate) timeout:              Option<Duration>,
   pub(crate) verbosity:            Verbosity,
+  pub(crate) watch:                Option<Vec<String>>,
 }
 
 mod cmd {
+  pub(crate) const CHOOSE: &str = "CHOOSE";
+  pub(crate) const COMPLETE: &str = "COMPLETE";
   pub(crate) const COMPLETIONS: &str = "COMPLETIONS";
+  pub(crate) const DOCS: &str = "DOCS";
   pub(crate) const DUMP: &str = "DUMP";
   pub(crate) const EDIT: &str = "EDIT";
   pub(crate) const EVALUATE: &str = "EVALUATE";
+  pub(crate) const FORMAT: &str = "FMT";
+  pub(crate) const GRAPH: &str = "GRAPH";
+  pub(crate) const GROUPS: &str = "GROUPS";
   pub(crate) const INIT: &str = "INIT";
+  pub(crate) const LINT: &str = "LINT";
   pub(crate) const LIST: &str = "LIST";
+  pub(crate) const LSP: &str = "LSP";
+  pub(crate) const PLAN: &str = "PLAN";
   pub(crate) const SHOW: &str = "SHOW";
   pub(crate) const SUMMARY: &str = "SUMMARY";
+  pub(crate) const USAGE: &str = "USAGE";
   pub(crate) const VARIABLES: &str = "VARIABLES";
 
   pub(crate) const ALL: &[&str] = &[
+    CHOOSE,
+    COMPLETE,
     COMPLETIONS,
+    DOCS,
     DUMP,
     EDIT,
     INIT,
     EVALUATE,
+    FORMAT,
+    GRAPH,
+    GROUPS,
+    LINT,
     LIST,
+    LSP,
+    PLAN,
     SHOW,
     SUMMARY,
+    USAGE,
     VARIABLES,
   ];
 
   pub(crate) const ARGLESS: &[&str] = &[
     COMPLETIONS,
+    DOCS,
     DUMP,
     EDIT,
+    FORMAT,
+    GRAPH,
+    GROUPS,
     INIT,
+    LINT,
     LIST,
+    LSP,
     SHOW,
     SUMMARY,
+    USAGE,
     VARIABLES,
   ];
 }
 
 mod arg {
   pub(crate) const ARGUMENTS: &str = "ARGUMENTS";
+  pub(crate) const CHECK: &str = "CHECK";
   pub(crate) const CLEAR_SHELL_ARGS: &str = "CLEAR-SHELL-ARGS";
   pub(crate) const COLOR: &str = "COLOR";
+  pub(crate) const DENY: &str = "DENY";
   pub(crate) const DRY_RUN: &str = "DRY-RUN";
+  pub(crate) const FORMAT: &str = "FORMAT";
+  pub(crate) const GLOBAL_FALLBACK: &str = "GLOBAL-FALLBACK";
+  pub(crate) const GLOBAL_JUSTFILE: &str = "GLOBAL-JUSTFILE";
+  pub(crate) const GRAPH_FORMAT: &str = "GRAPH-FORMAT";
   pub(crate) const HIGHLIGHT: &str = "HIGHLIGHT";
   pub(crate) const JUSTFILE: &str = "JUSTFILE";
+  pub(crate) const LIST_HEADING: &str = "LIST-HEADING";
+  pub(crate) const LIST_PREFIX: &str = "LIST-PREFIX";
+  pub(crate) const MESSAGE_FORMAT: &str = "MESSAGE-FORMAT";
   pub(crate) const NO_HIGHLIGHT: &str = "NO-HIGHLIGHT";
   pub(crate) const QUIET: &str = "QUIET";
   pub(crate) const SET: &str = "SET";
   pub(crate) const SHELL: &str = "SHELL";
   pub(crate) const SHELL_ARG: &str = "SHELL-ARG";
+  pub(crate) const TIMEOUT: &str = "TIMEOUT";
+  pub(crate) const UNSORTED: &str = "UNSORTED";
   pub(crate) const VERBOSE: &str = "VERBOSE";
+  pub(crate) const WATCH: &str = "WATCH";
   pub(crate) const WORKING_DIRECTORY: &str = "WORKING-DIRECTORY";
 
   pub(crate) const COLOR_ALWAYS: &str = "always";
   pub(crate) const COLOR_AUTO: &str = "auto";
   pub(crate) const COLOR_NEVER: &str = "never";
   pub(crate) const COLOR_VALUES: &[&str] = &[COLOR_AUTO, COLOR_ALWAYS, COLOR_NEVER];
+
+  pub(crate) const COMPLETE_BASH: &str = "bash";
+  pub(crate) const COMPLETE_FISH: &str = "fish";
+  pub(crate) const COMPLETE_ZSH: &str = "zsh";
+  pub(crate) const COMPLETE_VALUES: &[&str] = &[COMPLETE_BASH, COMPLETE_FISH, COMPLETE_ZSH];
+
+  pub(crate) const DENY_WARNINGS: &str = "warnings";
+  pub(crate) const DENY_VALUES: &[&str] = &[DENY_WARNINGS];
+
+  pub(crate) const FORMAT_JSON: &str = "json";
+  pub(crate) const FORMAT_JUST: &str = "just";
+  pub(crate) const FORMAT_VALUES: &[&str] = &[FORMAT_JUST, FORMAT_JSON];
+
+  pub(crate) const GRAPH_FORMAT_DOT: &str = "dot";
+  pub(crate) const GRAPH_FORMAT_MERMAID: &str = "mermaid";
+  pub(crate) const GRAPH_FORMAT_VALUES: &[&str] = &[GRAPH_FORMAT_DOT, GRAPH_FORMAT_MERMAID];
+
+  pub(crate) const MESSAGE_FORMAT_HUMAN: &str = "human";
+  pub(crate) const MESSAGE_FORMAT_JSON: &str = "json";
+  pub(crate) const MESSAGE_FORMAT_VALUES: &[&str] = &[MESSAGE_FORMAT_HUMAN, MESSAGE_FORMAT_JSON];
 }
 
 impl Config {
   pub(crate) fn app() -> App<'static, 'static> {
+    Self::app_with_complete(true)
+  }
+
+  /// The app that completion scripts are generated from, which lacks the
+  /// hidden `--complete` flag, since it is only used by completion scripts
+  pub(crate) fn completions_app() -> App<'static, 'static> {
+    Self::app_with_complete(false)
+  }
+
+  fn app_with_complete(complete: bool) -> App<'static, 'static> {
+    let subcommands = cmd::ALL
+      .iter()
+      .cloned()
+      .filter(|subcommand| complete || *subcommand != cmd::COMPLETE)
+      .collect::<Vec<&str>>();
+
     let app = App::new(env!("CARGO_PKG_NAME"))
       .help_message("Print help information")
       .version_message("Print version information")
@@ -93,12 +175,50 @@ impl Config {
           .default_value(arg::COLOR_AUTO)
           .help("Print colorful output"),
       )
+      .arg(
+        Arg::with_name(arg::DENY)
+          .long("deny")
+          .takes_value(true)
+          .value_name("KIND")
+          .possible_values(arg::DENY_VALUES)
+          .help("Treat <KIND> as errors, e.g. `--deny warnings`"),
+      )
       .arg(
         Arg::with_name(arg::DRY_RUN)
           .long("dry-run")
           .help("Print what just would do without doing it")
           .conflicts_with(arg::QUIET),
       )
+      .arg(
+        Arg::with_name(arg::FORMAT)
+          .long("format")
+          .takes_value(true)
+          .possible_values(arg::FORMAT_VALUES)
+          .requires(cmd::DUMP)
+          .help("Print justfile with `--dump` in <FORMAT>"),
+      )
+      .arg(
+        Arg::with_name(arg::GLOBAL_FALLBACK)
+          .long("global-fallback")
+          .conflicts_with_all(&[arg::GLOBAL_JUSTFILE, arg::JUSTFILE])
+          .help("Use global justfile if no justfile is found"),
+      )
+      .arg(
+        Arg::with_name(arg::GLOBAL_JUSTFILE)
+          .short("g")
+          .long("global-justfile")
+          .conflicts_with_all(&[arg::JUSTFILE, arg::WORKING_DIRECTORY])
+          .help("Use global justfile, `$XDG_CONFIG_HOME/just/justfile` or `~/.justfile`"),
+      )
+      .arg(
+        Arg::with_name(arg::GRAPH_FORMAT)
+          .long("graph-format")
+          .takes_value(true)
+          .value_name("FORMAT")
+          .possible_values(arg::GRAPH_FORMAT_VALUES)
+          .requires(cmd::GRAPH)
+          .help("Print dependency graph with `--graph` in <FORMAT>"),
+      )
       .arg(
         Arg::with_name(arg::HIGHLIGHT)
           .long("highlight")
@@ -116,7 +236,34 @@ impl Config {
           .short("f")
           .long("justfile")
           .takes_value(true)
-          .help("Use <JUSTFILE> as justfile."),
+          .help("Use <JUSTFILE> as justfile, or read it from standard input if <JUSTFILE> is `-`"),
+      )
+      .arg(
+        Arg::with_name(arg::LIST_HEADING)
+          .long("list-heading")
+          .takes_value(true)
+          .value_name("TEXT")
+          .allow_hyphen_values(true)
+          .requires(cmd::LIST)
+          .help("Print <TEXT> before recipes with `--list`"),
+      )
+      .arg(
+        Arg::with_name(arg::LIST_PREFIX)
+          .long("list-prefix")
+          .takes_value(true)
+          .value_name("TEXT")
+          .allow_hyphen_values(true)
+          .requires(cmd::LIST)
+          .help("Print <TEXT> before each recipe with `--list`"),
+      )
+      .arg(
+        Arg::with_name(arg::MESSAGE_FORMAT)
+          .long("message-format")
+          .takes_value(true)
+          .value_name("FORMAT")
+          .possible_values(arg::MESSAGE_FORMAT_VALUES)
+          .default_value(arg::MESSAGE_FORMAT_HUMAN)
+          .help("Print errors and warnings in <FORMAT>"),
       )
       .arg(
         Arg::with_name(arg::QUIET)
@@ -152,12 +299,37 @@ impl Config {
           .overrides_with(arg::CLEAR_SHELL_ARGS)
           .help("Invoke shell with <SHELL-ARG> as an argument"),
       )
+      .arg(
+        Arg::with_name(arg::CHECK)
+          .long("check")
+          .requires(cmd::FORMAT)
+          .help("Don't write the formatted justfile with `--fmt`, and fail if it isn't formatted"),
+      )
       .arg(
         Arg::with_name(arg::CLEAR_SHELL_ARGS)
           .long("clear-shell-args")
           .overrides_with(arg::SHELL_ARG)
           .help("Clear shell arguments"),
       )
+      .arg(
+        Arg::with_name(arg::TIMEOUT)
+          .long("timeout")
+          .takes_value(true)
+          .value_name("DURATION")
+          .validator(|value| {
+            value
+              .parse::<HumanDuration>()
+              .map(|_| ())
+              .map_err(|()| format!("`{}` is not a valid duration, e.g. `30s` or `10m`", value))
+          })
+          .help("Terminate recipes that run for longer than <DURATION>"),
+      )
+      .arg(
+        Arg::with_name(arg::UNSORTED)
+          .short("u")
+          .long("unsorted")
+          .help("List recipes and groups in the order they appear in the justfile"),
+      )
       .arg(
         Arg::with_name(arg::VERBOSE)
           .short("v")
@@ -165,6 +337,17 @@ impl Config {
           .multiple(true)
           .help("Use verbose output"),
       )
+      .arg(
+        Arg::with_name(arg::WATCH)
+          .long("watch")
+          .takes_value(true)
+          .value_name("PATTERN")
+          .min_values(0)
+          .require_equals(true)
+          .multiple(true)
+          .conflicts_with_all(&subcommands)
+          .help("Rerun recipes when files matching <PATTERN> or recipe inputs change"),
+      )
       .arg(
         Arg::with_name(arg::WORKING_DIRECTORY)
           .short("d")
@@ -178,6 +361,11 @@ impl Config {
           .multiple(true)
           .help("Overrides and recipe(s) to run, defaulting to the first recipe in the justfile"),
       )
+      .arg(
+        Arg::with_name(cmd::CHOOSE)
+          .long("choose")
+          .help("Select one or more recipes to run using `$JUST_CHOOSER`, `fzf`, or a prompt"),
+      )
       .arg(
         Arg::with_name(cmd::COMPLETIONS)
           .long("completions")
@@ -187,6 +375,11 @@ impl Config {
           .set(ArgSettings::CaseInsensitive)
           .help("Print shell completion script for <SHELL>"),
       )
+      .arg(
+        Arg::with_name(cmd::DOCS)
+          .long("docs")
+          .help("Print Markdown documentation for recipes and variables"),
+      )
       .arg(
         Arg::with_name(cmd::DUMP)
           .long("dump")
@@ -203,17 +396,51 @@ impl Config {
           .long("evaluate")
           .help("Print evaluated variables"),
       )
+      .arg(
+        Arg::with_name(cmd::FORMAT)
+          .long("fmt")
+          .help("Format justfile in place"),
+      )
+      .arg(
+        Arg::with_name(cmd::GRAPH)
+          .long("graph")
+          .takes_value(true)
+          .value_name("RECIPE")
+          .min_values(0)
+          .max_values(1)
+          .help("Print dependency graph of <RECIPE>, or of all recipes"),
+      )
+      .arg(
+        Arg::with_name(cmd::GROUPS)
+          .long("groups")
+          .help("List recipe groups"),
+      )
       .arg(
         Arg::with_name(cmd::INIT)
           .long("init")
           .help("Initialize new justfile in project root"),
       )
+      .arg(
+        Arg::with_name(cmd::LINT)
+          .long("lint")
+          .help("Check justfile for likely mistakes, like unused variables and parameters"),
+      )
       .arg(
         Arg::with_name(cmd::LIST)
           .short("l")
           .long("list")
           .help("List available recipes and their arguments"),
       )
+      .arg(
+        Arg::with_name(cmd::LSP)
+          .long("lsp")
+          .help("Run a language server over stdin and stdout"),
+      )
+      .arg(
+        Arg::with_name(cmd::PLAN)
+          .long("plan")
+          .help("Print the recipes that would run, in order, with their arguments, and exit"),
+      )
       .arg(
         Arg::with_name(cmd::SHOW)
           .short("s")
@@ -227,12 +454,33 @@ impl Config {
           .long("summary")
           .help("List names of available recipes"),
       )
+      .arg(
+        Arg::with_name(cmd::USAGE)
+          .long("usage")
+          .takes_value(true)
+          .value_name("RECIPE")
+          .help("Print how to invoke <RECIPE>"),
+      )
       .arg(
         Arg::with_name(cmd::VARIABLES)
           .long("variables")
           .help("List names of variables"),
       )
-      .group(ArgGroup::with_name("SUBCOMMAND").args(cmd::ALL));
+      .group(ArgGroup::with_name("SUBCOMMAND").args(&subcommands));
+
+    let app = if complete {
+      app.arg(
+        Arg::with_name(cmd::COMPLETE)
+          .long("complete")
+          .takes_value(true)
+          .value_name("SHELL")
+          .possible_values(arg::COMPLETE_VALUES)
+          .hidden(true)
+          .help("Print completions for the last of <ARGUMENTS>, for completion scripts"),
+      )
+    } else {
+      app
+    };
 
     if cfg!(feature = "help4help2man") {
       app.version(env!("CARGO_PKG_VERSION")).about(concat!(
@@ -263,6 +511,36 @@ impl Config {
     }
   }
 
+  fn dump_format_from_value(value: &str) -> ConfigResult<DumpFormat> {
+    match value {
+      arg::FORMAT_JSON => Ok(DumpFormat::Json),
+      arg::FORMAT_JUST => Ok(DumpFormat::Just),
+      _ => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{}` to --format.", value),
+      }),
+    }
+  }
+
+  fn graph_format_from_value(value: &str) -> ConfigResult<GraphFormat> {
+    match value {
+      arg::GRAPH_FORMAT_DOT => Ok(GraphFormat::Dot),
+      arg::GRAPH_FORMAT_MERMAID => Ok(GraphFormat::Mermaid),
+      _ => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{}` to --graph-format.", value),
+      }),
+    }
+  }
+
+  fn message_format_from_value(value: &str) -> ConfigResult<MessageFormat> {
+    match value {
+      arg::MESSAGE_FORMAT_HUMAN => Ok(MessageFormat::Human),
+      arg::MESSAGE_FORMAT_JSON => Ok(MessageFormat::Json),
+      _ => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{}` to --message-format.", value),
+      }),
+    }
+  }
+
   pub(crate) fn from_matches(matches: &ArgMatches) -> ConfigResult<Self> {
     let invocation_directory = env::current_dir().context(config_error::CurrentDir)?;
 
@@ -274,6 +552,12 @@ impl Config {
         .expect("`--color` had no value"),
     )?;
 
+    let message_format = Self::message_format_from_value(
+      matches
+        .value_of(arg::MESSAGE_FORMAT)
+        .expect("`--message-format` had no value"),
+    )?;
+
     let set_count = matches.occurrences_of(arg::SET);
     let mut overrides = BTreeMap::new();
     if set_count > 0 {
@@ -297,10 +581,17 @@ impl Config {
       let working_directory = matches.value_of(arg::WORKING_DIRECTORY).map(PathBuf::from);
 
       if let Some(search_directory) = positional.search_directory.map(PathBuf::from) {
-        if justfile.is_some() || working_directory.is_some() {
+        if justfile.is_some()
+          || working_directory.is_some()
+          || matches.is_present(arg::GLOBAL_JUSTFILE)
+        {
           return Err(ConfigError::SearchDirConflict);
         }
         SearchConfig::FromSearchDirectory { search_directory }
+      } else if matches.is_present(arg::GLOBAL_JUSTFILE) {
+        SearchConfig::GlobalJustfile
+      } else if justfile.as_deref() == Some(Path::new(crate::search::STDIN)) {
+        SearchConfig::Stdin { working_directory }
       } else {
         match (justfile, working_directory) {
           (None, None) => SearchConfig::FromInvocationDirectory,
@@ -345,20 +636,80 @@ impl Config {
       }
     }
 
-    let subcommand = if let Some(shell) = matches.value_of(cmd::COMPLETIONS) {
+    let subcommand = if matches.is_present(cmd::CHOOSE) {
+      if !positional.arguments.is_empty() {
+        return Err(ConfigError::SubcommandArguments {
+          subcommand: format!("--{}", cmd::CHOOSE.to_lowercase()),
+          arguments:  positional.arguments,
+        });
+      }
+      Subcommand::Choose { overrides }
+    } else if let Some(shell) = matches.value_of(cmd::COMPLETE) {
+      Subcommand::Complete {
+        shell: shell.to_owned(),
+        words: matches
+          .values_of(arg::ARGUMENTS)
+          .map(|values| values.map(str::to_owned).collect())
+          .unwrap_or_default(),
+      }
+    } else if let Some(shell) = matches.value_of(cmd::COMPLETIONS) {
       Subcommand::Completions {
         shell: shell.to_owned(),
       }
+    } else if matches.is_present(cmd::DOCS) {
+      Subcommand::Docs
     } else if matches.is_present(cmd::EDIT) {
       Subcommand::Edit
     } else if matches.is_present(cmd::SUMMARY) {
       Subcommand::Summary
     } else if matches.is_present(cmd::DUMP) {
-      Subcommand::Dump
+      Subcommand::Dump {
+        format: matches
+          .value_of(arg::FORMAT)
+          .map(Self::dump_format_from_value)
+          .transpose()?
+          .unwrap_or(DumpFormat::Just),
+      }
+    } else if matches.is_present(cmd::FORMAT) {
+      Subcommand::Format {
+        check: matches.is_present(arg::CHECK),
+      }
+    } else if matches.is_present(cmd::GRAPH) {
+      Subcommand::Graph {
+        recipe: matches.value_of(cmd::GRAPH).map(str::to_owned),
+        format: matches
+          .value_of(arg::GRAPH_FORMAT)
+          .map(Self::graph_format_from_value)
+          .transpose()?
+          .unwrap_or(GraphFormat::Dot),
+      }
+    } else if matches.is_present(cmd::GROUPS) {
+      Subcommand::Groups {
+        unsorted: matches.is_present(arg::UNSORTED),
+      }
     } else if matches.is_present(cmd::INIT) {
       Subcommand::Init
+    } else if matches.is_present(cmd::LINT) {
+      Subcommand::Lint
     } else if matches.is_present(cmd::LIST) {
-      Subcommand::List
+      Subcommand::List {
+        heading:  matches
+          .value_of(arg::LIST_HEADING)
+          .unwrap_or("Available recipes:\n")
+          .to_owned(),
+        prefix:   matches
+          .value_of(arg::LIST_PREFIX)
+          .unwrap_or("    ")
+          .to_owned(),
+        unsorted: matches.is_present(arg::UNSORTED),
+      }
+    } else if matches.is_present(cmd::LSP) {
+      Subcommand::Lsp
+    } else if matches.is_present(cmd::PLAN) {
+      Subcommand::Plan {
+        arguments: positional.arguments,
+        overrides,
+      }
     } else if let Some(name) = matches.value_of(cmd::SHOW) {
       Subcommand::Show {
         name: name.to_owned(),
@@ -371,8 +722,16 @@ impl Config {
         });
       }
       Subcommand::Evaluate { overrides }
+    } else if let Some(name) = matches.value_of(cmd::USAGE) {
+      Subcommand::Usage {
+        name: name.to_owned(),
+      }
     } else if matches.is_present(cmd::VARIABLES) {
       Subcommand::Variables
+    } else if positional.arguments.len() == 2 && positional.arguments[1] == "--help" {
+      Subcommand::Usage {
+        name: positional.arguments[0].clone(),
+      }
     } else {
       Subcommand::Run {
         arguments: positional.arguments,
@@ -390,25 +749,81 @@ impl Config {
         .collect()
     };
 
+    let timeout = match matches.value_of(arg::TIMEOUT) {
+      Some(value) => Some(
+        value
+          .parse::<HumanDuration>()
+          .map_err(|()| ConfigError::internal(format!("Invalid duration `{}`", value)))?
+          .0,
+      ),
+      None => None,
+    };
+
+    let watch = if matches.is_present(arg::WATCH) {
+      Some(
+        matches
+          .values_of(arg::WATCH)
+          .map(|values| values.map(str::to_owned).collect())
+          .unwrap_or_default(),
+      )
+    } else {
+      None
+    };
+
+    // these subcommands need a justfile on disk, or read standard input, and
+    // `--watch` reruns `just`, which can't read standard input again
+    if let SearchConfig::Stdin { .. } = search_config {
+      let conflict = match subcommand {
+        Subcommand::Choose { .. } => Some(cmd::CHOOSE),
+        Subcommand::Edit => Some(cmd::EDIT),
+        Subcommand::Format { check: false } => Some(cmd::FORMAT),
+        Subcommand::Init => Some(cmd::INIT),
+        _ if watch.is_some() => Some(arg::WATCH),
+        _ => None,
+      };
+
+      if let Some(subcommand) = conflict {
+        return Err(ConfigError::StdinJustfile {
+          subcommand: format!("--{}", subcommand.to_lowercase()),
+        });
+      }
+    }
+
     let shell_present = matches.occurrences_of(arg::CLEAR_SHELL_ARGS) > 0
       || matches.occurrences_of(arg::SHELL) > 0
       || matches.occurrences_of(arg::SHELL_ARG) > 0;
 
     Ok(Self {
+      deny_warnings: matches.value_of(arg::DENY) == Some(arg::DENY_WARNINGS),
       dry_run: matches.is_present(arg::DRY_RUN),
+      global_fallback: matches.is_present(arg::GLOBAL_FALLBACK),
       highlight: !matches.is_present(arg::NO_HIGHLIGHT),
       quiet: matches.is_present(arg::QUIET),
       shell: matches.value_of(arg::SHELL).unwrap().to_owned(),
       color,
       invocation_directory,
+      message_format,
       search_config,
       shell_args,
       shell_present,
+      sinks: Sinks::default(),
       subcommand,
+      timeout,
       verbosity,
+      watch,
     })
   }
 
+  /// Find the justfile, falling back to the global justfile if none is found
+  /// and `--global-fallback` was passed
+  fn search(&self) -> SearchResult<Search> {
+    match Search::find(&self.search_config, &self.invocation_directory) {
+      Err(SearchError::NotFound) if self.global_fallback =>
+        Search::global(&self.invocation_directory),
+      result => result,
+    }
+  }
+
   pub(crate) fn run_subcommand(self) -> Result<(), i32> {
     use Subcommand::*;
 
@@ -420,50 +835,275 @@ impl Config {
       return Subcommand::completions(&shell);
     }
 
-    let search =
-      Search::find(&self.search_config, &self.invocation_directory).eprint(self.color)?;
+    if let Complete { shell, words } = &self.subcommand {
+      self.complete(shell, words);
+      return Ok(());
+    }
+
+    if self.subcommand == Lsp {
+      return Self::lsp();
+    }
+
+    let search = match self.message_format {
+      MessageFormat::Human => self.search().eprint(self.color)?,
+      MessageFormat::Json => self.search().map_err(|error| {
+        Diagnostic::search_error(&error).print();
+        EXIT_FAILURE
+      })?,
+    };
 
     if self.subcommand == Edit {
       return Self::edit(&search);
     }
 
-    let src = fs::read_to_string(&search.justfile)
-      .map_err(|io_error| LoadError {
-        io_error,
-        path: &search.justfile,
-      })
-      .eprint(self.color)?;
+    let src = self.load(&search)?;
 
-    let justfile = Compiler::compile(&src).eprint(self.color)?;
+    let justfile = self.compile(&search, &src)?;
 
-    for warning in &justfile.warnings {
-      if self.color.stderr().active() {
-        eprintln!("{:#}", warning);
-      } else {
-        eprintln!("{}", warning);
+    let lints = if self.subcommand == Lint {
+      Linter::lint(&justfile)
+    } else {
+      Vec::new()
+    };
+
+    let warnings = justfile.warnings.iter().chain(&lints).count();
+
+    for warning in justfile.warnings.iter().chain(&lints) {
+      match self.message_format {
+        MessageFormat::Human =>
+          if self.color.stderr().active() {
+            eprintln!("{:#}", warning);
+          } else {
+            eprintln!("{}", warning);
+          },
+        MessageFormat::Json => Diagnostic::warning(&search.justfile, warning).print(),
+      }
+    }
+
+    if self.deny_warnings && warnings > 0 {
+      if self.message_format == MessageFormat::Human {
+        eprintln!(
+          "Denying {} {} because of `--deny warnings`",
+          warnings,
+          Count("warning", warnings)
+        );
       }
+      return Err(EXIT_FAILURE);
     }
 
     match &self.subcommand {
-      Dump => Self::dump(justfile),
+      Choose { overrides } => self.choose(&justfile, &search, overrides),
+      Docs => Self::docs(&justfile),
+      Dump { format } => Self::dump(*format, justfile),
+      Format { check } => Self::format(*check, &search, &src, self.color),
       Evaluate { overrides } => self.run(justfile, &search, overrides, &Vec::new()),
-      List => self.list(justfile),
+      Graph { recipe, format } => Self::graph(recipe.as_deref(), *format, &justfile),
+      Groups { unsorted } => Self::groups(*unsorted, &justfile),
+      Lint => Ok(()),
+      List {
+        heading,
+        prefix,
+        unsorted,
+      } => self.list(heading, prefix, *unsorted, &justfile),
       Run {
         arguments,
         overrides,
+      } =>
+        if let Some(patterns) = &self.watch {
+          Self::watch(&justfile, &search, patterns, arguments, self.color)
+        } else {
+          self.run(justfile, &search, overrides, arguments)
+        },
+      Plan {
+        arguments,
+        overrides,
       } => self.run(justfile, &search, overrides, arguments),
       Show { ref name } => Self::show(&name, justfile),
       Summary => Self::summary(justfile),
+      Usage { ref name } => Self::usage(&name, justfile),
       Variables => Self::variables(justfile),
-      Completions { .. } | Edit | Init => unreachable!(),
+      Complete { .. } | Completions { .. } | Edit | Init | Lsp => unreachable!(),
+    }
+  }
+
+  /// Read the justfile found by `search`, or standard input with
+  /// `--justfile -`
+  fn load(&self, search: &Search) -> Result<String, i32> {
+    let result = if search.is_stdin() {
+      let mut src = String::new();
+      io::stdin().read_to_string(&mut src).map(|_| src)
+    } else {
+      fs::read_to_string(&search.justfile)
+    };
+
+    let result = result.map_err(|io_error| LoadError {
+      io_error,
+      path: &search.justfile,
+    });
+
+    match self.message_format {
+      MessageFormat::Human => result.eprint(self.color),
+      MessageFormat::Json => result.map_err(|error| {
+        Diagnostic::load_error(&error).print();
+        EXIT_FAILURE
+      }),
+    }
+  }
+
+  /// Compile `src`, printing errors in the requested message format
+  fn compile<'src>(&self, search: &Search, src: &'src str) -> Result<Justfile<'src>, i32> {
+    match self.message_format {
+      MessageFormat::Human => Compiler::compile(src).eprint(self.color),
+      MessageFormat::Json => Compiler::compile(src).map_err(|errors| {
+        for error in &errors {
+          Diagnostic::compilation_error(&search.justfile, error).print();
+        }
+        EXIT_FAILURE
+      }),
+    }
+  }
+
+  /// Print completions for the last of `words`, which are the words on the
+  /// command line being completed, after `just`. Nothing is printed if the
+  /// command line can't be parsed or the justfile can't be found or compiled.
+  fn complete(&self, shell: &str, words: &[String]) {
+    let (current, preceding) = match words.split_last() {
+      Some((current, preceding)) => (current.as_str(), preceding),
+      None => ("", words),
+    };
+
+    // the value of `--show`, `--graph`, or `--usage` is a recipe name
+    let recipe = match preceding.last().map(String::as_str) {
+      Some("-s") | Some("--show") | Some("--graph") | Some("--usage") => true,
+      _ => false,
+    };
+
+    let preceding = if recipe {
+      &preceding[..preceding.len() - 1]
+    } else {
+      preceding
+    };
+
+    // parse the preceding words like any other command line, so that options
+    // like `--justfile` are taken into account
+    let config = Self::app()
+      .get_matches_from_safe(iter::once("just").chain(preceding.iter().map(String::as_str)))
+      .ok()
+      .and_then(|matches| Self::from_matches(&matches).ok());
+
+    let config = match config {
+      Some(config) => config,
+      None => return,
+    };
+
+    // standard input is the shell's, not the justfile's
+    let search = match config.search() {
+      Ok(search) if !search.is_stdin() => search,
+      _ => return,
+    };
+
+    let src = match fs::read_to_string(&search.justfile) {
+      Ok(src) => src,
+      Err(_) => return,
+    };
+
+    let justfile = match Compiler::compile(&src) {
+      Ok(justfile) => justfile,
+      Err(_) => return,
+    };
+
+    let completer = Completer::new(&justfile, &self.invocation_directory);
+
+    let completions = match &config.subcommand {
+      _ if recipe => completer.recipes(current),
+      Subcommand::Run { arguments, .. } => completer.complete(arguments, current),
+      _ => return,
+    };
+
+    print!("{}", Completer::render(&completions, shell));
+  }
+
+  fn docs(justfile: &Justfile) -> Result<(), i32> {
+    print!("{}", Docs::new(justfile));
+    Ok(())
+  }
+
+  fn dump(format: DumpFormat, justfile: Justfile) -> Result<(), i32> {
+    match format {
+      DumpFormat::Json => {
+        let json = serde_json::to_string(&Dump::new(&justfile)).map_err(|serde_json_error| {
+          eprintln!("Failed to serialize justfile: {}", serde_json_error);
+          EXIT_FAILURE
+        })?;
+        println!("{}", json);
+      },
+      DumpFormat::Just => println!("{}", justfile),
     }
+    Ok(())
   }
 
-  fn dump(justfile: Justfile) -> Result<(), i32> {
-    println!("{}", justfile);
+  fn format(check: bool, search: &Search, src: &str, color: Color) -> Result<(), i32> {
+    let formatted = Reformatter::reformat(src).eprint(color)?;
+
+    if formatted == src {
+      return Ok(());
+    }
+
+    if check {
+      let added = color.stdout().diff_added();
+      let deleted = color.stdout().diff_deleted();
+
+      for change in TextDiff::from_lines(src, &formatted).iter_all_changes() {
+        let (sign, color) = match change.tag() {
+          ChangeTag::Delete => ("-", deleted),
+          ChangeTag::Insert => ("+", added),
+          ChangeTag::Equal => (" ", color.stdout()),
+        };
+
+        print!(
+          "{}{}{}{}",
+          color.prefix(),
+          sign,
+          change.value(),
+          color.suffix()
+        );
+
+        if change.missing_newline() {
+          println!();
+        }
+      }
+
+      eprintln!("Justfile `{}` is not formatted", search.justfile.display());
+      return Err(EXIT_FAILURE);
+    }
+
+    if let Err(io_error) = fs::write(&search.justfile, formatted) {
+      eprintln!(
+        "Failed to write justfile to `{}`: {}",
+        search.justfile.display(),
+        io_error
+      );
+      return Err(EXIT_FAILURE);
+    }
+
+    eprintln!("Wrote justfile to `{}`", search.justfile.display());
+
     Ok(())
   }
 
+  fn lsp() -> Result<(), i32> {
+    let stdin = io::stdin();
+
+    match LanguageServer::run(stdin.lock(), io::stdout()) {
+      Ok(0) => Ok(()),
+      Ok(code) => Err(code),
+      Err(io_error) => {
+        eprintln!("Language server I/O error: {}", io_error);
+        Err(EXIT_FAILURE)
+      },
+    }
+  }
+
   pub(crate) fn edit(search: &Search) -> Result<(), i32> {
     let editor = env::var_os("VISUAL")
       .or_else(|| env::var_os("EDITOR"))
@@ -513,7 +1153,23 @@ impl Config {
     }
   }
 
-  fn list(&self, justfile: Justfile) -> Result<(), i32> {
+  fn groups(unsorted: bool, justfile: &Justfile) -> Result<(), i32> {
+    println!("Recipe groups:");
+
+    for group in justfile.public_groups(unsorted) {
+      println!("    {}", group);
+    }
+
+    Ok(())
+  }
+
+  fn list(
+    &self,
+    heading: &str,
+    prefix: &str,
+    unsorted: bool,
+    justfile: &Justfile,
+  ) -> Result<(), i32> {
     // Construct a target to alias map.
     let mut recipe_aliases: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
     for alias in justfile.aliases.values() {
@@ -529,14 +1185,14 @@ impl Config {
       }
     }
 
+    let recipes = justfile.public_recipes(unsorted);
+
     let mut line_widths: BTreeMap<&str, usize> = BTreeMap::new();
 
-    for (name, recipe) in &justfile.recipes {
-      if recipe.private {
-        continue;
-      }
+    for recipe in &recipes {
+      let name = recipe.name();
 
-      for name in iter::once(name).chain(recipe_aliases.get(name).unwrap_or(&Vec::new())) {
+      for name in iter::once(&name).chain(recipe_aliases.get(name).unwrap_or(&Vec::new())) {
         let mut line_width = UnicodeWidthStr::width(*name);
 
         for parameter in &recipe.parameters {
@@ -551,77 +1207,221 @@ impl Config {
 
     let max_line_width = cmp::min(line_widths.values().cloned().max().unwrap_or(0), 30);
 
+    // Recipes without a group are listed first, followed by a section for
+    // each group. Recipes in more than one group are listed in each.
+    let mut sections: Vec<(Option<&str>, Vec<&Recipe>)> = Vec::new();
+
+    let ungrouped = recipes
+      .iter()
+      .filter(|recipe| recipe.groups().next().is_none())
+      .copied()
+      .collect::<Vec<&Recipe>>();
+
+    if !ungrouped.is_empty() {
+      sections.push((None, ungrouped));
+    }
+
+    for group in justfile.public_groups(unsorted) {
+      sections.push((
+        Some(group),
+        recipes
+          .iter()
+          .filter(|recipe| recipe.groups().any(|name| name == group))
+          .copied()
+          .collect(),
+      ));
+    }
+
     let doc_color = self.color.stdout().doc();
-    println!("Available recipes:");
+    print!("{}", heading);
 
-    for (name, recipe) in &justfile.recipes {
-      if recipe.private {
-        continue;
+    for (i, (group, recipes)) in sections.iter().enumerate() {
+      if i > 0 {
+        println!();
       }
 
-      let alias_doc = format!("alias for `{}`", recipe.name);
+      if let Some(group) = group {
+        println!("{}[{}]", prefix, group);
+      }
 
-      for (i, name) in iter::once(name)
-        .chain(recipe_aliases.get(name).unwrap_or(&Vec::new()))
-        .enumerate()
-      {
-        print!("    {}", name);
-        for parameter in &recipe.parameters {
-          if self.color.stdout().active() {
-            print!(" {:#}", parameter);
-          } else {
-            print!(" {}", parameter);
+      for recipe in recipes {
+        let name = recipe.name();
+
+        let alias_doc = format!("alias for `{}`", recipe.name);
+
+        for (i, name) in iter::once(&name)
+          .chain(recipe_aliases.get(name).unwrap_or(&Vec::new()))
+          .enumerate()
+        {
+          print!("{}{}", prefix, name);
+          for parameter in &recipe.parameters {
+            if self.color.stdout().active() {
+              print!(" {:#}", parameter);
+            } else {
+              print!(" {}", parameter);
+            }
           }
-        }
-
-        // Declaring this outside of the nested loops will probably be more efficient,
-        // but it creates all sorts of lifetime issues with variables inside the loops.
-        // If this is inlined like the docs say, it shouldn't make any difference.
-        let print_doc = |doc| {
-          print!(
-            " {:padding$}{} {}",
-            "",
-            doc_color.paint("#"),
-            doc_color.paint(doc),
-            padding = max_line_width
-              .saturating_sub(line_widths.get(name).cloned().unwrap_or(max_line_width))
-          );
-        };
 
-        match (i, recipe.doc) {
-          (0, Some(doc)) => print_doc(doc),
-          (0, None) => (),
-          _ => print_doc(&alias_doc),
+          // Declaring this outside of the nested loops will probably be more efficient,
+          // but it creates all sorts of lifetime issues with variables inside the loops.
+          // If this is inlined like the docs say, it shouldn't make any difference.
+          let print_doc = |doc| {
+            print!(
+              " {:padding$}{} {}",
+              "",
+              doc_color.paint("#"),
+              doc_color.paint(doc),
+              padding = max_line_width
+                .saturating_sub(line_widths.get(name).cloned().unwrap_or(max_line_width))
+            );
+          };
+
+          match (i, recipe.doc_summary()) {
+            (0, Some(doc)) => print_doc(doc),
+            (0, None) => (),
+            _ => print_doc(&alias_doc),
+          }
+          println!();
         }
-        println!();
       }
     }
 
     Ok(())
   }
 
-  fn run(
+  fn choose(
     &self,
-    justfile: Justfile,
+    justfile: &Justfile,
     search: &Search,
     overrides: &BTreeMap<String, String>,
-    arguments: &[String],
   ) -> Result<(), i32> {
+    let chosen = Chooser::new(self, justfile).choose()?;
+
     if let Err(error) = InterruptHandler::install() {
       warn!("Failed to set CTRL-C handler: {}", error)
     }
 
-    let result = justfile.run(&self, search, overrides, arguments);
+    let result = justfile.run_chosen(&self, search, overrides, &chosen);
 
-    if !self.quiet {
-      result.eprint(self.color)
-    } else {
-      result.map_err(|err| err.code())
-    }
+    self.report(search, result)
   }
 
-  fn show(name: &str, justfile: Justfile) -> Result<(), i32> {
-    if let Some(alias) = justfile.get_alias(name) {
+  fn run(
+    &self,
+    justfile: Justfile,
+    search: &Search,
+    overrides: &BTreeMap<String, String>,
+    arguments: &[String],
+  ) -> Result<(), i32> {
+    if let Err(error) = InterruptHandler::install() {
+      warn!("Failed to set CTRL-C handler: {}", error)
+    }
+
+    self.run_with_fallback(justfile, search, overrides, arguments)
+  }
+
+  /// Run `arguments` with `justfile`. If any recipes are unknown and the
+  /// justfile has `set fallback`, run them with the next justfile found in a
+  /// parent directory instead.
+  fn run_with_fallback(
+    &self,
+    justfile: Justfile,
+    search: &Search,
+    overrides: &BTreeMap<String, String>,
+    arguments: &[String],
+  ) -> Result<(), i32> {
+    let result = justfile.run(&self, search, overrides, arguments);
+
+    if let Err(RuntimeError::UnknownRecipes { recipes, .. }) = &result {
+      let searched = match self.search_config {
+        SearchConfig::FromInvocationDirectory | SearchConfig::FromSearchDirectory { .. } => true,
+        SearchConfig::GlobalJustfile
+        | SearchConfig::Stdin { .. }
+        | SearchConfig::WithJustfile { .. }
+        | SearchConfig::WithJustfileAndWorkingDirectory { .. } => false,
+      };
+
+      if justfile.settings.fallback && searched {
+        if let Ok(parent) = search.parent() {
+          if self.dry_run || self.verbosity.loquacious() {
+            let color = self.color.stderr().banner();
+            self.sinks.eprintln(format_args!(
+              "{}===> {} {} not found in `{}`, trying `{}`...{}",
+              color.prefix(),
+              Count("Recipe", recipes.len()),
+              List::and_ticked(recipes),
+              search.justfile.display(),
+              parent.justfile.display(),
+              color.suffix()
+            ));
+          }
+
+          let src = self.load(&parent)?;
+
+          let justfile = self.compile(&parent, &src)?;
+
+          return self.run_with_fallback(justfile, &parent, overrides, arguments);
+        }
+      }
+    }
+
+    self.report(search, result)
+  }
+
+  /// Print the error from running recipes, if any, returning its exit code
+  fn report(&self, search: &Search, result: RunResult<()>) -> Result<(), i32> {
+    if self.quiet {
+      result.map_err(|err| err.code())
+    } else {
+      match self.message_format {
+        MessageFormat::Human => result.eprint(self.color),
+        MessageFormat::Json => result.map_err(|error| {
+          Diagnostic::runtime_error(&search.justfile, &error).print();
+          error.code()
+        }),
+      }
+    }
+  }
+
+  fn watch(
+    justfile: &Justfile,
+    search: &Search,
+    patterns: &[String],
+    arguments: &[String],
+    color: Color,
+  ) -> Result<(), i32> {
+    let patterns = patterns
+      .iter()
+      .map(String::as_str)
+      .chain(justfile.inputs(arguments))
+      .map(FilePattern::new)
+      .collect();
+
+    Watcher::new(color, search, patterns).watch()
+  }
+
+  fn graph(recipe: Option<&str>, format: GraphFormat, justfile: &Justfile) -> Result<(), i32> {
+    let root = match recipe {
+      Some(name) =>
+        if let Some(recipe) = justfile.get_recipe(name) {
+          Some(recipe)
+        } else {
+          eprintln!("Justfile does not contain recipe `{}`.", name);
+          if let Some(suggestion) = justfile.suggest(name) {
+            eprintln!("{}", suggestion);
+          }
+          return Err(EXIT_FAILURE);
+        },
+      None => None,
+    };
+
+    print!("{}", Graph::new(justfile, root).render(format));
+
+    Ok(())
+  }
+
+  fn show(name: &str, justfile: Justfile) -> Result<(), i32> {
+    if let Some(alias) = justfile.get_alias(name) {
       let recipe = justfile.get_recipe(alias.target.name.lexeme()).unwrap();
       println!("{}", alias);
       println!("{}", recipe);
@@ -638,6 +1438,19 @@ impl Config {
     }
   }
 
+  fn usage(name: &str, justfile: Justfile) -> Result<(), i32> {
+    if let Some(recipe) = justfile.get_recipe(name) {
+      print!("{}", Usage::new(&justfile, recipe));
+      Ok(())
+    } else {
+      eprintln!("Justfile does not contain recipe `{}`.", name);
+      if let Some(suggestion) = justfile.suggest(name) {
+        eprintln!("{}", suggestion);
+      }
+      Err(EXIT_FAILURE)
+    }
+  }
+
   fn summary(justfile: Justfile) -> Result<(), i32> {
     if justfile.count() == 0 {
       eprintln!("Justfile contains no recipes.");
@@ -686,18 +1499,34 @@ USAGE:
     just [FLAGS] [OPTIONS] [--] [ARGUMENTS]...
 
 FLAGS:
+        --check               Don't write the formatted justfile with `--fmt`, and fail if it \
+                                 isn't formatted
+        --choose              Select one or more recipes to run using `$JUST_CHOOSER`, `fzf`, or a \
+                                 prompt
         --clear-shell-args    Clear shell arguments
+        --docs                Print Markdown documentation for recipes and variables
         --dry-run             Print what just would do without doing it
         --dump                Print entire justfile
     -e, --edit                Edit justfile with editor given by $VISUAL or $EDITOR, falling back \
                                  to `vim`
         --evaluate            Print evaluated variables
+        --fmt                 Format justfile in place
+        --global-fallback     Use global justfile if no justfile is found
+    -g, --global-justfile     Use global justfile, `$XDG_CONFIG_HOME/just/justfile` or \
+                                 `~/.justfile`
+        --groups              List recipe groups
         --highlight           Highlight echoed recipe lines in bold
         --init                Initialize new justfile in project root
+        --lint                Check justfile for likely mistakes, like unused variables and \
+                                 parameters
     -l, --list                List available recipes and their arguments
+        --lsp                 Run a language server over stdin and stdout
         --no-highlight        Don't highlight echoed recipe lines in bold
+        --plan                Print the recipes that would run, in order, with their arguments, \
+                                 and exit
     -q, --quiet               Suppress all output
         --summary             List names of available recipes
+    -u, --unsorted            List recipes and groups in the order they appear in the justfile
         --variables           List names of variables
     -v, --verbose             Use verbose output
 
@@ -709,12 +1538,35 @@ OPTIONS:
             Print shell completion script for <SHELL> [possible values: zsh, bash, fish, \
                                  powershell, elvish]
 
-    -f, --justfile <JUSTFILE>                      Use <JUSTFILE> as justfile.
+        --deny <KIND>
+            Treat <KIND> as errors, e.g. `--deny warnings` [possible values: warnings]
+
+        --format <FORMAT>
+            Print justfile with `--dump` in <FORMAT> [possible values: just, json]
+
+        --graph <RECIPE>                           Print dependency graph of <RECIPE>, or of all \
+                                 recipes
+        --graph-format <FORMAT>
+            Print dependency graph with `--graph` in <FORMAT> [possible values: dot, mermaid]
+
+    -f, --justfile <JUSTFILE>
+            Use <JUSTFILE> as justfile, or read it from standard input if <JUSTFILE> is `-`
+
+        --list-heading <TEXT>                      Print <TEXT> before recipes with `--list`
+        --list-prefix <TEXT>                       Print <TEXT> before each recipe with `--list`
+        --message-format <FORMAT>
+            Print errors and warnings in <FORMAT> [default: human]  [possible values: human, json]
+
         --set <VARIABLE> <VALUE>                   Override <VARIABLE> with <VALUE>
         --shell <SHELL>                            Invoke <SHELL> to run recipes [default: sh]
         --shell-arg <SHELL-ARG>...                 Invoke shell with <SHELL-ARG> as an argument \
                                  [default: -cu]
     -s, --show <RECIPE>                            Show information about <RECIPE>
+        --timeout <DURATION>                       Terminate recipes that run for longer than \
+                                 <DURATION>
+        --usage <RECIPE>                           Print how to invoke <RECIPE>
+        --watch=<PATTERN>...                       Rerun recipes when files matching <PATTERN> \
+                                 or recipe inputs change
     -d, --working-directory <WORKING-DIRECTORY>
             Use <WORKING-DIRECTORY> as working directory. --justfile must also be set
 
@@ -736,15 +1588,20 @@ ARGS:
       name: $name:ident,
       args: [$($arg:expr),*],
       $(color: $color:expr,)?
+      $(deny_warnings: $deny_warnings:expr,)?
       $(dry_run: $dry_run:expr,)?
+      $(global_fallback: $global_fallback:expr,)?
       $(highlight: $highlight:expr,)?
+      $(message_format: $message_format:expr,)?
       $(quiet: $quiet:expr,)?
       $(search_config: $search_config:expr,)?
       $(shell: $shell:expr,)?
       $(shell_args: $shell_args:expr,)?
       $(shell_present: $shell_present:expr,)?
       $(subcommand: $subcommand:expr,)?
+      $(timeout: $timeout:expr,)?
       $(verbosity: $verbosity:expr,)?
+      $(watch: $watch:expr,)?
     } => {
       #[test]
       fn $name() {
@@ -755,15 +1612,20 @@ ARGS:
 
         let want = Config {
           $(color: $color,)?
+          $(deny_warnings: $deny_warnings,)?
           $(dry_run: $dry_run,)?
+          $(global_fallback: $global_fallback,)?
           $(highlight: $highlight,)?
+          $(message_format: $message_format,)?
           $(quiet: $quiet,)?
           $(search_config: $search_config,)?
           $(shell: $shell.to_string(),)?
           $(shell_args: $shell_args,)?
           $(shell_present: $shell_present,)?
           $(subcommand: $subcommand,)?
+          $(timeout: $timeout,)?
           $(verbosity: $verbosity,)?
+          $(watch: $watch,)?
           ..testing::config(&[])
         };
 
@@ -874,6 +1736,23 @@ ARGS:
     args: ["--color", "foo"],
   }
 
+  test! {
+    name: deny_default,
+    args: [],
+    deny_warnings: false,
+  }
+
+  test! {
+    name: deny_warnings,
+    args: ["--deny", "warnings"],
+    deny_warnings: true,
+  }
+
+  error! {
+    name: deny_bad_value,
+    args: ["--deny", "errors"],
+  }
+
   test! {
     name: dry_run_default,
     args: [],
@@ -927,6 +1806,29 @@ ARGS:
     highlight: false,
   }
 
+  test! {
+    name: message_format_default,
+    args: [],
+    message_format: MessageFormat::Human,
+  }
+
+  test! {
+    name: message_format_human,
+    args: ["--message-format", "human"],
+    message_format: MessageFormat::Human,
+  }
+
+  test! {
+    name: message_format_json,
+    args: ["--message-format", "json"],
+    message_format: MessageFormat::Json,
+  }
+
+  error! {
+    name: message_format_bad_value,
+    args: ["--message-format", "xml"],
+  }
+
   test! {
     name: quiet_default,
     args: [],
@@ -1010,6 +1912,66 @@ ARGS:
     shell_present: true,
   }
 
+  test! {
+    name: timeout_default,
+    args: [],
+    timeout: None,
+  }
+
+  test! {
+    name: timeout_seconds,
+    args: ["--timeout", "30s"],
+    timeout: Some(Duration::from_secs(30)),
+  }
+
+  test! {
+    name: timeout_minutes,
+    args: ["--timeout", "10m"],
+    timeout: Some(Duration::from_secs(600)),
+  }
+
+  error! {
+    name: timeout_invalid,
+    args: ["--timeout", "10"],
+  }
+
+  test! {
+    name: watch_default,
+    args: [],
+    watch: None,
+  }
+
+  test! {
+    name: watch_all,
+    args: ["--watch"],
+    watch: Some(Vec::new()),
+  }
+
+  test! {
+    name: watch_patterns,
+    args: ["--watch=src", "--watch=Cargo.toml", "build"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("build")],
+      overrides: map!(),
+    },
+    watch: Some(vec!["src".to_owned(), "Cargo.toml".to_owned()]),
+  }
+
+  test! {
+    name: watch_arguments,
+    args: ["--watch", "build"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("build")],
+      overrides: map!(),
+    },
+    watch: Some(Vec::new()),
+  }
+
+  error! {
+    name: watch_list,
+    args: ["--watch", "--list"],
+  }
+
   test! {
     name: verbosity_default,
     args: [],
@@ -1049,6 +2011,46 @@ ARGS:
     },
   }
 
+  test! {
+    name: subcommand_choose,
+    args: ["--choose"],
+    subcommand: Subcommand::Choose {
+      overrides: map!{},
+    },
+  }
+
+  test! {
+    name: subcommand_choose_overrides,
+    args: ["--choose", "x=y"],
+    subcommand: Subcommand::Choose {
+      overrides: map!{"x": "y"},
+    },
+  }
+
+  error! {
+    name: subcommand_choose_arguments,
+    args: ["--choose", "build"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--choose");
+      assert_eq!(arguments, &["build"]);
+    },
+  }
+
+  test! {
+    name: subcommand_complete,
+    args: ["--complete", "zsh", "--", "--dry-run", "build", ""],
+    subcommand: Subcommand::Complete {
+      shell: "zsh".to_owned(),
+      words: vec!["--dry-run".to_owned(), "build".to_owned(), "".to_owned()],
+    },
+  }
+
+  error! {
+    name: subcommand_complete_invalid,
+    args: ["--complete", "elvish"],
+  }
+
   test! {
     name: subcommand_completions,
     args: ["--completions", "bash"],
@@ -1066,10 +2068,121 @@ ARGS:
     args: ["--completions", "monstersh"],
   }
 
+  test! {
+    name: subcommand_docs,
+    args: ["--docs"],
+    subcommand: Subcommand::Docs,
+  }
+
+  error! {
+    name: docs_arguments,
+    args: ["--docs", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--docs");
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   test! {
     name: subcommand_dump,
     args: ["--dump"],
-    subcommand: Subcommand::Dump,
+    subcommand: Subcommand::Dump {
+      format: DumpFormat::Just,
+    },
+  }
+
+  test! {
+    name: subcommand_dump_format_json,
+    args: ["--dump", "--format", "json"],
+    subcommand: Subcommand::Dump {
+      format: DumpFormat::Json,
+    },
+  }
+
+  test! {
+    name: subcommand_dump_format_just,
+    args: ["--dump", "--format", "just"],
+    subcommand: Subcommand::Dump {
+      format: DumpFormat::Just,
+    },
+  }
+
+  error! {
+    name: subcommand_dump_format_invalid,
+    args: ["--dump", "--format", "yaml"],
+  }
+
+  error! {
+    name: format_without_dump,
+    args: ["--format", "json"],
+  }
+
+  test! {
+    name: subcommand_format,
+    args: ["--fmt"],
+    subcommand: Subcommand::Format { check: false },
+  }
+
+  test! {
+    name: subcommand_format_check,
+    args: ["--fmt", "--check"],
+    subcommand: Subcommand::Format { check: true },
+  }
+
+  error! {
+    name: check_without_format,
+    args: ["--check"],
+  }
+
+  test! {
+    name: subcommand_lsp,
+    args: ["--lsp"],
+    subcommand: Subcommand::Lsp,
+  }
+
+  error! {
+    name: lsp_arguments,
+    args: ["--lsp", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--lsp");
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
+  test! {
+    name: subcommand_plan,
+    args: ["--plan"],
+    subcommand: Subcommand::Plan {
+      arguments: Vec::new(),
+      overrides: map!(),
+    },
+  }
+
+  test! {
+    name: subcommand_plan_arguments_and_overrides,
+    args: ["--plan", "foo=bar", "build", "release"],
+    subcommand: Subcommand::Plan {
+      arguments: vec![String::from("build"), String::from("release")],
+      overrides: map!{"foo": "bar"},
+    },
+  }
+
+  test! {
+    name: subcommand_lint,
+    args: ["--lint"],
+    subcommand: Subcommand::Lint,
+  }
+
+  error! {
+    name: lint_arguments,
+    args: ["--lint", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--lint");
+      assert_eq!(arguments, &["bar"]);
+    },
   }
 
   test! {
@@ -1078,6 +2191,48 @@ ARGS:
     subcommand: Subcommand::Edit,
   }
 
+  test! {
+    name: subcommand_graph,
+    args: ["--graph"],
+    subcommand: Subcommand::Graph {
+      format: GraphFormat::Dot,
+      recipe: None,
+    },
+  }
+
+  test! {
+    name: subcommand_graph_recipe,
+    args: ["--graph", "build"],
+    subcommand: Subcommand::Graph {
+      format: GraphFormat::Dot,
+      recipe: Some(String::from("build")),
+    },
+  }
+
+  test! {
+    name: subcommand_graph_format_mermaid,
+    args: ["--graph-format", "mermaid", "--graph"],
+    subcommand: Subcommand::Graph {
+      format: GraphFormat::Mermaid,
+      recipe: None,
+    },
+  }
+
+  error! {
+    name: subcommand_graph_format_invalid,
+    args: ["--graph", "--graph-format", "png"],
+  }
+
+  error! {
+    name: graph_format_without_graph,
+    args: ["--graph-format", "dot"],
+  }
+
+  error! {
+    name: graph_arguments,
+    args: ["--graph", "build", "test"],
+  }
+
   test! {
     name: subcommand_evaluate,
     args: ["--evaluate"],
@@ -1094,16 +2249,71 @@ ARGS:
     },
   }
 
+  test! {
+    name: subcommand_groups,
+    args: ["--groups"],
+    subcommand: Subcommand::Groups { unsorted: false },
+  }
+
+  test! {
+    name: subcommand_groups_unsorted,
+    args: ["--groups", "--unsorted"],
+    subcommand: Subcommand::Groups { unsorted: true },
+  }
+
+  error! {
+    name: subcommand_groups_arguments,
+    args: ["--groups", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--groups");
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   test! {
     name: subcommand_list_long,
     args: ["--list"],
-    subcommand: Subcommand::List,
+    subcommand: Subcommand::List {
+      heading:  "Available recipes:\n".to_owned(),
+      prefix:   "    ".to_owned(),
+      unsorted: false,
+    },
   }
 
   test! {
     name: subcommand_list_short,
     args: ["-l"],
-    subcommand: Subcommand::List,
+    subcommand: Subcommand::List {
+      heading:  "Available recipes:\n".to_owned(),
+      prefix:   "    ".to_owned(),
+      unsorted: false,
+    },
+  }
+
+  test! {
+    name: subcommand_list_unsorted,
+    args: ["--list", "-u"],
+    subcommand: Subcommand::List {
+      heading:  "Available recipes:\n".to_owned(),
+      prefix:   "    ".to_owned(),
+      unsorted: true,
+    },
+  }
+
+  test! {
+    name: subcommand_list_heading_and_prefix,
+    args: ["--list", "--list-heading", "Recipes:", "--list-prefix", "- "],
+    subcommand: Subcommand::List {
+      heading:  "Recipes:".to_owned(),
+      prefix:   "- ".to_owned(),
+      unsorted: false,
+    },
+  }
+
+  error! {
+    name: list_heading_requires_list,
+    args: ["--list-heading", "Recipes:"],
   }
 
   test! {
@@ -1129,6 +2339,32 @@ ARGS:
     subcommand: Subcommand::Summary,
   }
 
+  test! {
+    name: subcommand_usage,
+    args: ["--usage", "build"],
+    subcommand: Subcommand::Usage { name: String::from("build") },
+  }
+
+  test! {
+    name: subcommand_usage_recipe_help,
+    args: ["build", "--help"],
+    subcommand: Subcommand::Usage { name: String::from("build") },
+  }
+
+  test! {
+    name: subcommand_usage_recipe_help_after_arguments,
+    args: ["build", "foo", "--help"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("build"), String::from("foo"), String::from("--help")],
+      overrides: map!{},
+    },
+  }
+
+  error! {
+    name: subcommand_usage_no_arg,
+    args: ["--usage"],
+  }
+
   test! {
     name: arguments,
     args: ["foo", "bar"],
@@ -1314,6 +2550,102 @@ ARGS:
     error: ConfigError::SearchDirConflict,
   }
 
+  test! {
+    name: search_config_stdin,
+    args: ["--justfile", "-"],
+    search_config: SearchConfig::Stdin {
+      working_directory: None,
+    },
+  }
+
+  test! {
+    name: search_config_stdin_with_working_directory,
+    args: ["--justfile", "-", "--working-directory", "foo"],
+    search_config: SearchConfig::Stdin {
+      working_directory: Some(PathBuf::from("foo")),
+    },
+  }
+
+  test! {
+    name: search_config_stdin_format_check,
+    args: ["--justfile", "-", "--fmt", "--check"],
+    search_config: SearchConfig::Stdin {
+      working_directory: None,
+    },
+    subcommand: Subcommand::Format { check: true },
+  }
+
+  error! {
+    name: search_config_stdin_edit,
+    args: ["--justfile", "-", "--edit"],
+    error: ConfigError::StdinJustfile { subcommand },
+    check: {
+      assert_eq!(subcommand, "--edit");
+    },
+  }
+
+  error! {
+    name: search_config_stdin_format,
+    args: ["--justfile", "-", "--fmt"],
+    error: ConfigError::StdinJustfile { subcommand },
+    check: {
+      assert_eq!(subcommand, "--fmt");
+    },
+  }
+
+  error! {
+    name: search_config_stdin_init,
+    args: ["-f", "-", "--init"],
+    error: ConfigError::StdinJustfile { subcommand },
+    check: {
+      assert_eq!(subcommand, "--init");
+    },
+  }
+
+  error! {
+    name: search_config_stdin_watch,
+    args: ["-f", "-", "--watch"],
+    error: ConfigError::StdinJustfile { subcommand },
+    check: {
+      assert_eq!(subcommand, "--watch");
+    },
+  }
+
+  error! {
+    name: search_directory_conflict_global_justfile,
+    args: ["--global-justfile", "foo/build"],
+    error: ConfigError::SearchDirConflict,
+  }
+
+  test! {
+    name: search_config_global_justfile_long,
+    args: ["--global-justfile"],
+    search_config: SearchConfig::GlobalJustfile,
+  }
+
+  test! {
+    name: search_config_global_justfile_short,
+    args: ["-g"],
+    search_config: SearchConfig::GlobalJustfile,
+  }
+
+  error! {
+    name: global_justfile_conflicts_with_justfile,
+    args: ["--global-justfile", "--justfile", "foo"],
+  }
+
+  test! {
+    name: global_fallback_default,
+    args: [],
+    global_fallback: false,
+  }
+
+  test! {
+    name: global_fallback,
+    args: ["--global-fallback"],
+    global_fallback: true,
+  }
+
   error! {
     name: completions_arguments,
     args: ["--completions", "zsh", "foo"],
@@ -1354,6 +2686,16 @@ ARGS:
     },
   }
 
+  error! {
+    name: format_arguments,
+    args: ["--fmt", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--fmt");
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   error! {
     name: edit_arguments,
     args: ["--edit", "bar"],
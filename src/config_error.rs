@@ -12,9 +12,15 @@ pub(crate) enum ConfigError {
   #[snafu(display("Failed to get current directory: {}", source))]
   CurrentDir { source: io::Error },
   #[snafu(display(
-    "Path-prefixed recipes may not be used with `--working-directory` or `--justfile`."
+    "Path-prefixed recipes may not be used with `--working-directory`, `--justfile`, or \
+     `--global-justfile`."
   ))]
   SearchDirConflict,
+  #[snafu(display(
+    "`{}` may not be used when the justfile is read from standard input with `--justfile -`",
+    subcommand
+  ))]
+  StdinJustfile { subcommand: String },
   #[snafu(display(
     "`{}` used with unexpected {}: {}",
     subcommand,
@@ -0,0 +1,242 @@
+//! The JSON representation of an error or warning printed with
+//! `--message-format json`, one object per line on stderr.
+//!
+//! Lines and columns are one-based, and columns and lengths are counted in
+//! characters. `line`, `column`, and `length` are `null` for diagnostics
+//! that don't refer to a location in the justfile, except that `line` is
+//! given for errors and warnings about a recipe line that failed. `file` is
+//! `null` if no justfile was found.
+
+use crate::common::*;
+
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Diagnostic {
+  pub(crate) severity:   Severity,
+  pub(crate) code:       &'static str,
+  pub(crate) message:    String,
+  pub(crate) file:       Option<String>,
+  pub(crate) line:       Option<usize>,
+  pub(crate) column:     Option<usize>,
+  pub(crate) length:     Option<usize>,
+  pub(crate) suggestion: Option<String>,
+}
+
+impl Diagnostic {
+  pub(crate) fn compilation_error(file: &Path, error: &CompilationError) -> Self {
+    Self::new(
+      Severity::Error,
+      error.kind.diagnostic_code(),
+      error.message(),
+      Some(file),
+      Some(error.token),
+      None,
+    )
+  }
+
+  pub(crate) fn runtime_error(file: &Path, error: &RuntimeError) -> Self {
+    let mut diagnostic = Self::new(
+      Severity::Error,
+      error.diagnostic_code(),
+      error.message(),
+      Some(file),
+      error.context(),
+      error.suggestion().map(|suggestion| suggestion.to_string()),
+    );
+
+    diagnostic.line = diagnostic.line.or_else(|| error.line_number());
+
+    diagnostic
+  }
+
+  pub(crate) fn warning(file: &Path, warning: &Warning) -> Self {
+    let mut diagnostic = Self::new(
+      Severity::Warning,
+      warning.diagnostic_code(),
+      warning.message(),
+      Some(file),
+      warning.context(),
+      None,
+    );
+
+    diagnostic.line = diagnostic.line.or_else(|| warning.line_number());
+
+    diagnostic
+  }
+
+  pub(crate) fn search_error(error: &SearchError) -> Self {
+    Self::new(
+      Severity::Error,
+      error.diagnostic_code(),
+      error.to_string(),
+      None,
+      None,
+      None,
+    )
+  }
+
+  pub(crate) fn load_error(error: &LoadError) -> Self {
+    Self::new(
+      Severity::Error,
+      LoadError::DIAGNOSTIC_CODE,
+      error.to_string(),
+      Some(error.path),
+      None,
+      None,
+    )
+  }
+
+  fn new(
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    file: Option<&Path>,
+    token: Option<Token>,
+    suggestion: Option<String>,
+  ) -> Self {
+    let column = token.map(|token| {
+      let line = token.src.lines().nth(token.line).unwrap_or_default();
+      line
+        .get(..token.column)
+        .unwrap_or(line)
+        .chars()
+        .count()
+        .ordinal()
+    });
+
+    Self {
+      line: token.map(|token| token.line.ordinal()),
+      length: token.map(|token| token.lexeme().chars().count()),
+      file: file.map(|file| file.display().to_string()),
+      code,
+      column,
+      message,
+      severity,
+      suggestion,
+    }
+  }
+
+  /// Print as a single line of JSON to stderr
+  pub(crate) fn print(&self) {
+    eprintln!("{}", self);
+  }
+}
+
+impl Display for Diagnostic {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      serde_json::to_string(self).expect("diagnostic serialization cannot fail")
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn compilation_error() {
+    let src = "foo:\n  echo 'ü' {{bar}}";
+    let error = Compiler::compile(src).unwrap_err().remove(0);
+
+    assert_eq!(
+      serde_json::to_value(Diagnostic::compilation_error(
+        Path::new("justfile"),
+        &error
+      ))
+      .unwrap(),
+      serde_json::json!({
+        "severity": "error",
+        "code": "E0028",
+        "message": "Variable `bar` not defined",
+        "file": "justfile",
+        "line": 2,
+        "column": 14,
+        "length": 3,
+        "suggestion": null,
+      })
+    );
+  }
+
+  #[test]
+  fn warning() {
+    let src = "foo = 'bar'";
+    let justfile = Compiler::compile(src).unwrap();
+
+    assert_eq!(
+      Diagnostic::warning(Path::new("justfile"), &justfile.warnings[0]),
+      Diagnostic {
+        severity:   Severity::Warning,
+        code:       "W0001",
+        message:    justfile.warnings[0].message(),
+        file:       Some("justfile".to_owned()),
+        line:       Some(1),
+        column:     Some(5),
+        length:     Some(1),
+        suggestion: None,
+      }
+    );
+  }
+
+  #[test]
+  fn runtime_error() {
+    let error = RuntimeError::UnknownRecipes {
+      recipes:    vec!["buld"],
+      suggestion: Some(Suggestion {
+        name:   "build",
+        target: None,
+      }),
+    };
+
+    assert_eq!(
+      Diagnostic::runtime_error(Path::new("justfile"), &error),
+      Diagnostic {
+        severity:   Severity::Error,
+        code:       "E1015",
+        message:    "Justfile does not contain recipe `buld`.".to_owned(),
+        file:       Some("justfile".to_owned()),
+        line:       None,
+        column:     None,
+        length:     None,
+        suggestion: Some("Did you mean `build`?".to_owned()),
+      }
+    );
+  }
+
+  #[test]
+  fn runtime_error_line() {
+    let error = RuntimeError::Code {
+      recipe:      "build",
+      line_number: Some(3),
+      code:        1,
+      attempts:    1,
+    };
+
+    assert_eq!(
+      Diagnostic::runtime_error(Path::new("justfile"), &error).line,
+      Some(3)
+    );
+  }
+
+  #[test]
+  fn search_error() {
+    assert_eq!(
+      serde_json::to_value(Diagnostic::search_error(&SearchError::NotFound)).unwrap(),
+      serde_json::json!({
+        "severity": "error",
+        "code": "E2003",
+        "message": "No justfile found",
+        "file": null,
+        "line": null,
+        "column": null,
+        "length": null,
+        "suggestion": null,
+      })
+    );
+  }
+}
@@ -0,0 +1,297 @@
+use crate::common::*;
+
+/// Markdown reference documentation for the public recipes and the variables
+/// of a justfile, printed by `--docs`. Recipes and variables are listed in
+/// alphabetical order, so that the output only changes when the justfile
+/// does.
+pub(crate) struct Docs<'src: 'run, 'run> {
+  justfile: &'run Justfile<'src>,
+}
+
+impl<'src: 'run, 'run> Docs<'src, 'run> {
+  pub(crate) fn new(justfile: &'run Justfile<'src>) -> Self {
+    Self { justfile }
+  }
+
+  /// The length of the longest run of backticks in `text`
+  fn backticks(text: &str) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+
+    for c in text.chars() {
+      if c == '`' {
+        run += 1;
+        longest = cmp::max(longest, run);
+      } else {
+        run = 0;
+      }
+    }
+
+    longest
+  }
+
+  /// `text` as inline code, delimited by enough backticks that backticks in
+  /// `text` don't end it early
+  fn code(text: &str) -> String {
+    let delimiter = "`".repeat(Self::backticks(text) + 1);
+
+    if text.starts_with('`') || text.ends_with('`') {
+      format!("{} {} {}", delimiter, text, delimiter)
+    } else {
+      format!("{}{}{}", delimiter, text, delimiter)
+    }
+  }
+
+  /// `items` as a comma separated list of inline code
+  fn code_list(items: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    items
+      .into_iter()
+      .map(|item| Self::code(item.as_ref()))
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+
+  fn recipe(&self, f: &mut Formatter, recipe: &Recipe) -> fmt::Result {
+    writeln!(f, "### {}", Self::code(recipe.name()))?;
+    writeln!(f)?;
+
+    if let Some(doc) = &recipe.doc {
+      writeln!(f, "{}", doc)?;
+      writeln!(f)?;
+    }
+
+    let mut signature = recipe.name().to_owned();
+    for parameter in &recipe.parameters {
+      signature += &format!(" {}", parameter);
+    }
+
+    writeln!(f, "- Signature: {}", Self::code(&signature))?;
+
+    let aliases = self
+      .justfile
+      .aliases
+      .values()
+      .filter(|alias| !alias.is_private() && alias.target.name() == recipe.name())
+      .map(|alias| alias.name.lexeme())
+      .collect::<Vec<&str>>();
+
+    if !aliases.is_empty() {
+      writeln!(f, "- Aliases: {}", Self::code_list(aliases))?;
+    }
+
+    let groups = recipe.groups().collect::<Vec<&str>>();
+
+    if !groups.is_empty() {
+      writeln!(f, "- Groups: {}", Self::code_list(groups))?;
+    }
+
+    if !recipe.dependencies.is_empty() {
+      writeln!(
+        f,
+        "- Dependencies: {}",
+        Self::code_list(recipe.dependencies.iter().map(ToString::to_string))
+      )?;
+    }
+
+    // docs and groups are shown above
+    let attributes = recipe
+      .attributes
+      .iter()
+      .filter(|attribute| match attribute {
+        Attribute::Doc { .. } | Attribute::Group { .. } => false,
+        _ => true,
+      })
+      .map(ToString::to_string)
+      .collect::<Vec<String>>();
+
+    if !attributes.is_empty() {
+      writeln!(f, "- Attributes: {}", Self::code_list(attributes))?;
+    }
+
+    let exports = self
+      .justfile
+      .assignments
+      .values()
+      .filter(|assignment| assignment.export)
+      .map(|assignment| assignment.name.lexeme())
+      .collect::<Vec<&str>>();
+
+    if !exports.is_empty() {
+      writeln!(f, "- Environment variables: {}", Self::code_list(exports))?;
+    }
+
+    if !recipe.parameters.is_empty() {
+      writeln!(f, "- Parameters:")?;
+
+      for parameter in &recipe.parameters {
+        write!(f, "  - {}", Self::code(parameter.name.lexeme()))?;
+
+        let mut notes = Vec::new();
+
+        if parameter.variadic {
+          notes.push("variadic".to_owned());
+        }
+
+        if let Some(default) = &parameter.default {
+          notes.push(format!("default: {}", Self::code(&default.to_string())));
+        }
+
+        if !notes.is_empty() {
+          write!(f, " ({})", notes.join(", "))?;
+        }
+
+        if let Some(doc) = recipe.parameter_doc(parameter.name.lexeme()) {
+          write!(f, ": {}", doc)?;
+        }
+
+        writeln!(f)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl<'src: 'run, 'run> Display for Docs<'src, 'run> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let recipes = self.justfile.public_recipes(false);
+
+    if !recipes.is_empty() {
+      writeln!(f, "## Recipes")?;
+
+      for recipe in &recipes {
+        writeln!(f)?;
+        self.recipe(f, recipe)?;
+      }
+    }
+
+    let assignments = self
+      .justfile
+      .assignments
+      .values()
+      .map(|assignment| {
+        format!(
+          "{}{} := {}",
+          if assignment.export { "export " } else { "" },
+          assignment.name.lexeme(),
+          assignment.value
+        )
+      })
+      .collect::<Vec<String>>();
+
+    if !assignments.is_empty() {
+      if !recipes.is_empty() {
+        writeln!(f)?;
+      }
+
+      // the fence must be longer than any run of backticks in the block
+      let fence = "`".repeat(cmp::max(
+        3,
+        assignments
+          .iter()
+          .map(|assignment| Self::backticks(assignment) + 1)
+          .max()
+          .unwrap_or(0),
+      ));
+
+      writeln!(f, "## Variables")?;
+      writeln!(f)?;
+      writeln!(f, "{}just", fence)?;
+
+      for assignment in assignments {
+        writeln!(f, "{}", assignment)?;
+      }
+
+      writeln!(f, "{}", fence)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  macro_rules! test {
+    {
+      name: $name:ident,
+      justfile: $justfile:expr,
+      docs: $docs:expr,
+    } => {
+      #[test]
+      fn $name() {
+        let src = testing::unindent($justfile);
+        let justfile = testing::compile(&src);
+
+        let have = Docs::new(&justfile).to_string();
+
+        assert_eq!(have, testing::unindent($docs));
+      }
+    }
+  }
+
+  test! {
+    name: recipes_and_variables,
+    justfile: "
+      target := 'debug'
+      export TOKEN := `cat token`
+
+      # build the project
+      #
+      # in debug mode by default
+      [group('dev')]
+      [doc('mode', 'debug or release')]
+      [retry('2')]
+      build mode=target +flags='': (test mode)
+
+      test mode:
+
+      _helper:
+
+      alias b := build
+    ",
+    docs: "
+      ## Recipes
+
+      ### `build`
+
+      build the project
+
+      in debug mode by default
+
+      - Signature: `build mode=target +flags=''`
+      - Aliases: `b`
+      - Groups: `dev`
+      - Dependencies: `(test mode)`
+      - Attributes: `[retry(2)]`
+      - Environment variables: `TOKEN`
+      - Parameters:
+        - `mode` (default: `target`): debug or release
+        - `flags` (variadic, default: `''`)
+
+      ### `test`
+
+      - Signature: `test mode`
+      - Environment variables: `TOKEN`
+      - Parameters:
+        - `mode`
+
+      ## Variables
+
+      ```just
+      export TOKEN := `cat token`
+      target := 'debug'
+      ```
+    ",
+  }
+
+  #[test]
+  fn code() {
+    assert_eq!(Docs::code("foo"), "`foo`");
+    assert_eq!(Docs::code("a `b` c"), "``a `b` c``");
+    assert_eq!(Docs::code("`b`"), "`` `b` ``");
+  }
+}
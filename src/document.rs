@@ -0,0 +1,556 @@
+use crate::common::*;
+
+use serde_json::{json, Value};
+use std::convert::TryFrom;
+
+/// The definition that a name in a document refers to
+#[derive(Clone, Copy)]
+enum Definition<'document, 'src> {
+  Alias(&'document Alias<'src, Name<'src>>),
+  Assignment(&'document Assignment<'src>),
+  Parameter(&'document Parameter<'src>),
+  Recipe(&'document UnresolvedRecipe<'src>),
+}
+
+impl<'src> Definition<'_, 'src> {
+  fn name(self) -> Name<'src> {
+    match self {
+      Self::Alias(alias) => alias.name,
+      Self::Assignment(assignment) => assignment.name,
+      Self::Parameter(parameter) => parameter.name,
+      Self::Recipe(recipe) => recipe.name,
+    }
+  }
+}
+
+/// A parsed justfile open in the language server. Positions are LSP
+/// positions, with lines and UTF-16 characters counted from zero.
+pub(crate) struct Document<'src> {
+  src:    &'src str,
+  tokens: Vec<Token<'src>>,
+  items:  Vec<Item<'src>>,
+}
+
+impl<'src> Document<'src> {
+  const SETTINGS: &'static [&'static str] = &[
+    keyword::AFTER,
+    keyword::BEFORE,
+    keyword::FALLBACK,
+    keyword::SHELL,
+  ];
+
+  /// Parse `src`, returning `None` if it contains syntax errors
+  pub(crate) fn parse(src: &'src str) -> Option<Self> {
+    let tokens = Lexer::lex(src).ok()?;
+
+    let module = Parser::parse(&tokens).ok()?;
+
+    Some(Self {
+      items: module.items,
+      src,
+      tokens,
+    })
+  }
+
+  /// Errors and warnings produced when compiling `src`
+  pub(crate) fn diagnostics(src: &str) -> Vec<Value> {
+    let tokens = match Lexer::lex(src) {
+      Ok(tokens) => tokens,
+      Err(error) => return vec![Self::error(src, &error)],
+    };
+
+    let (module, parse_errors) = Parser::parse_partial(&tokens);
+
+    let mut diagnostics = module
+      .warnings
+      .iter()
+      .filter_map(|warning| {
+        warning.context().map(|token| {
+          json!({
+            "range": Self::range(src, token.offset, token.length),
+            "severity": 2,
+            "source": "just",
+            "message": warning.message(),
+          })
+        })
+      })
+      .collect::<Vec<Value>>();
+
+    if let Err(errors) = Compiler::analyze(module, parse_errors) {
+      diagnostics.extend(errors.iter().map(|error| Self::error(src, error)));
+    }
+
+    diagnostics
+  }
+
+  /// Completions that don't depend on the contents of a document
+  pub(crate) fn builtin_completions() -> Vec<Value> {
+    let functions = crate::function::TABLE.keys().map(|name| {
+      json!({
+        "label": name,
+        "kind": 3,
+        "detail": "function",
+      })
+    });
+
+    let settings = Self::SETTINGS.iter().map(|name| {
+      json!({
+        "label": name,
+        "kind": 10,
+        "detail": "setting",
+      })
+    });
+
+    functions.chain(settings).collect()
+  }
+
+  /// Recipes and variables in scope at `position`
+  pub(crate) fn completions(&self, position: &Value) -> Vec<Value> {
+    let offset = self.offset(position);
+
+    let mut completions = Vec::new();
+
+    for item in &self.items {
+      match item {
+        Item::Alias(alias) => completions.push(json!({
+          "label": alias.name.lexeme(),
+          "kind": 3,
+          "detail": "alias",
+        })),
+        Item::Assignment(assignment) => completions.push(json!({
+          "label": assignment.name.lexeme(),
+          "kind": 6,
+          "detail": "variable",
+        })),
+        Item::Recipe(recipe) => {
+          completions.push(json!({
+            "label": recipe.name(),
+            "kind": 3,
+            "detail": "recipe",
+          }));
+
+          if offset.map_or(false, |offset| self.span(recipe.name).contains(&offset)) {
+            for parameter in &recipe.parameters {
+              completions.push(json!({
+                "label": parameter.name.lexeme(),
+                "kind": 6,
+                "detail": "parameter",
+              }));
+            }
+          }
+        },
+        Item::Set(_) => {},
+      }
+    }
+
+    completions
+  }
+
+  /// The range of the definition of the name at `position`
+  pub(crate) fn definition(&self, position: &Value) -> Option<Value> {
+    let definition = self.resolve(position)?.1.name();
+    Some(self.name_range(definition))
+  }
+
+  /// Markdown describing the definition of the name at `position`
+  pub(crate) fn hover(&self, position: &Value) -> Option<Value> {
+    let (name, definition) = self.resolve(position)?;
+
+    let (signature, doc) = match definition {
+      Definition::Alias(alias) => (alias.to_string(), None),
+      Definition::Assignment(assignment) => {
+        let export = if assignment.export { "export " } else { "" };
+        (
+          format!(
+            "{}{} := {}",
+            export,
+            assignment.name.lexeme(),
+            assignment.value
+          ),
+          None,
+        )
+      },
+      Definition::Parameter(parameter) => (parameter.to_string(), None),
+      Definition::Recipe(recipe) => {
+        let mut signature = recipe.name().to_owned();
+
+        for parameter in &recipe.parameters {
+          signature.push(' ');
+          signature.push_str(&parameter.to_string());
+        }
+
+        signature.push(':');
+
+        for dependency in &recipe.dependencies {
+          signature.push(' ');
+          signature.push_str(&dependency.to_string());
+        }
+
+        (signature, recipe.doc.as_deref())
+      },
+    };
+
+    let mut value = format!("```just\n{}\n```", signature);
+
+    if let Some(doc) = doc {
+      value.push_str("\n\n");
+      value.push_str(doc);
+    }
+
+    Some(json!({
+      "contents": {
+        "kind": "markdown",
+        "value": value,
+      },
+      "range": self.name_range(name),
+    }))
+  }
+
+  /// The aliases, assignments, recipes, and settings in the document
+  pub(crate) fn symbols(&self) -> Vec<Value> {
+    self
+      .items
+      .iter()
+      .map(|item| {
+        let (name, kind) = match item {
+          Item::Alias(alias) => (alias.name, 12),
+          Item::Assignment(assignment) => (assignment.name, 13),
+          Item::Recipe(recipe) => (recipe.name, 12),
+          Item::Set(set) => (set.name, 7),
+        };
+
+        let span = self.span(name);
+
+        json!({
+          "name": name.lexeme(),
+          "kind": kind,
+          "range": Self::range(self.src, span.start, span.end - span.start),
+          "selectionRange": self.name_range(name),
+        })
+      })
+      .collect()
+  }
+
+  /// Find the name at `position` and the definition that it refers to
+  fn resolve(&self, position: &Value) -> Option<(Name<'src>, Definition<'_, 'src>)> {
+    let offset = self.offset(position)?;
+
+    self
+      .names()
+      .into_iter()
+      .find(|(name, _)| name.offset <= offset && offset <= name.offset + name.length)
+  }
+
+  /// Every name in the document whose definition can be found, paired with
+  /// that definition
+  fn names(&self) -> Vec<(Name<'src>, Definition<'_, 'src>)> {
+    let mut names = Vec::new();
+
+    for item in &self.items {
+      match item {
+        Item::Alias(alias) => {
+          names.push((alias.name, Definition::Alias(alias)));
+          if let Some(target) = self.recipe(alias.target.lexeme()) {
+            names.push((alias.target, target));
+          }
+        },
+        Item::Assignment(assignment) => {
+          names.push((assignment.name, Definition::Assignment(assignment)));
+          self.variables(&assignment.value, None, &mut names);
+        },
+        Item::Recipe(recipe) => {
+          names.push((recipe.name, Definition::Recipe(recipe)));
+
+          for parameter in &recipe.parameters {
+            names.push((parameter.name, Definition::Parameter(parameter)));
+            if let Some(default) = &parameter.default {
+              self.variables(default, Some(recipe), &mut names);
+            }
+          }
+
+          for dependency in &recipe.dependencies {
+            if let Some(target) = self.recipe(dependency.recipe.lexeme()) {
+              names.push((dependency.recipe, target));
+            }
+            for argument in &dependency.arguments {
+              self.variables(argument, Some(recipe), &mut names);
+            }
+          }
+
+          for line in &recipe.body {
+            for fragment in &line.fragments {
+              if let Fragment::Interpolation { expression } = fragment {
+                self.variables(expression, Some(recipe), &mut names);
+              }
+            }
+          }
+        },
+        Item::Set(_) => {},
+      }
+    }
+
+    names
+  }
+
+  /// Add the variables in `expression`, which is inside of `recipe`, to
+  /// `names`
+  fn variables<'document>(
+    &'document self,
+    expression: &'document Expression<'src>,
+    recipe: Option<&'document UnresolvedRecipe<'src>>,
+    names: &mut Vec<(Name<'src>, Definition<'document, 'src>)>,
+  ) {
+    match expression {
+      Expression::Backtick { .. } | Expression::StringLiteral { .. } => {},
+      Expression::Call { thunk } => match thunk {
+        Thunk::Nullary { .. } => {},
+        Thunk::Unary { arg, .. } => self.variables(arg, recipe, names),
+        Thunk::Binary { args, .. } =>
+          for arg in args {
+            self.variables(arg, recipe, names);
+          },
+      },
+      Expression::Concatination { lhs, rhs } => {
+        self.variables(lhs, recipe, names);
+        self.variables(rhs, recipe, names);
+      },
+      Expression::Group { contents } => self.variables(contents, recipe, names),
+      Expression::Variable { name } => {
+        let parameter = recipe.and_then(|recipe| {
+          recipe
+            .parameters
+            .iter()
+            .find(|parameter| parameter.name.lexeme() == name.lexeme())
+        });
+
+        let definition = match parameter {
+          Some(parameter) => Some(Definition::Parameter(parameter)),
+          None => self.items.iter().find_map(|item| match item {
+            Item::Assignment(assignment) if assignment.name.lexeme() == name.lexeme() =>
+              Some(Definition::Assignment(assignment)),
+            _ => None,
+          }),
+        };
+
+        if let Some(definition) = definition {
+          names.push((*name, definition));
+        }
+      },
+    }
+  }
+
+  /// The recipe or alias called `name`
+  fn recipe(&self, name: &str) -> Option<Definition<'_, 'src>> {
+    self.items.iter().find_map(|item| match item {
+      Item::Recipe(recipe) if recipe.name() == name => Some(Definition::Recipe(recipe)),
+      Item::Alias(alias) if alias.name.lexeme() == name => Some(Definition::Alias(alias)),
+      _ => None,
+    })
+  }
+
+  /// The byte range from the start of `name` to the end of the item that it
+  /// names, including a recipe's body
+  fn span(&self, name: Name<'src>) -> Range<usize> {
+    let start = name.offset;
+    let mut end = name.offset + name.length;
+    let mut depth = 0;
+
+    let mut tokens = self
+      .tokens
+      .iter()
+      .skip_while(|token| token.offset <= start)
+      .peekable();
+
+    while let Some(token) = tokens.next() {
+      match token.kind {
+        TokenKind::Indent => depth += 1,
+        TokenKind::Dedent => {
+          depth -= 1;
+          if depth == 0 {
+            break;
+          }
+        },
+        TokenKind::Eol =>
+          if depth == 0 && tokens.peek().map(|token| token.kind) != Some(TokenKind::Indent) {
+            break;
+          },
+        TokenKind::Eof => break,
+        TokenKind::Whitespace => {},
+        _ => end = token.offset + token.length,
+      }
+    }
+
+    start..end
+  }
+
+  fn name_range(&self, name: Name<'src>) -> Value {
+    Self::range(self.src, name.offset, name.length)
+  }
+
+  fn error(src: &str, error: &CompilationError) -> Value {
+    json!({
+      "range": Self::range(src, error.token.offset, error.token.length),
+      "severity": 1,
+      "source": "just",
+      "message": error.message(),
+    })
+  }
+
+  fn range(src: &str, offset: usize, length: usize) -> Value {
+    json!({
+      "start": Self::position(src, offset),
+      "end": Self::position(src, offset + length),
+    })
+  }
+
+  /// Convert a byte offset into an LSP position
+  fn position(src: &str, offset: usize) -> Value {
+    let before = &src[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    json!({
+      "line": before.matches('\n').count(),
+      "character": before[line_start..].encode_utf16().count(),
+    })
+  }
+
+  /// Convert an LSP position into a byte offset
+  fn offset(&self, position: &Value) -> Option<usize> {
+    let line = usize::try_from(position["line"].as_u64()?).ok()?;
+    let character = usize::try_from(position["character"].as_u64()?).ok()?;
+
+    let line_start = if line == 0 {
+      0
+    } else {
+      self.src.match_indices('\n').nth(line - 1)?.0 + 1
+    };
+
+    let mut units = 0;
+
+    for (i, c) in self.src[line_start..].char_indices() {
+      if units >= character || c == '\n' {
+        return Some(line_start + i);
+      }
+      units += c.len_utf16();
+    }
+
+    Some(self.src.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  fn position(line: usize, character: usize) -> Value {
+    json!({
+      "line": line,
+      "character": character,
+    })
+  }
+
+  #[test]
+  fn offset_round_trip() {
+    let src = "a := 'ö'\nb:\n  echo {{a}}\n";
+    let document = Document::parse(src).unwrap();
+
+    for offset in src.char_indices().map(|(i, _)| i) {
+      assert_eq!(
+        document.offset(&Document::position(src, offset)),
+        Some(offset)
+      );
+    }
+  }
+
+  #[test]
+  fn position_utf16() {
+    assert_eq!(Document::position("x := '𝄞' + y", 14), position(0, 12));
+  }
+
+  #[test]
+  fn definition_of_variable() {
+    let src = "foo := 'bar'\nbaz:\n  echo {{foo}}\n";
+    let document = Document::parse(src).unwrap();
+    assert_eq!(
+      document.definition(&position(2, 10)),
+      Some(json!({"start": position(0, 0), "end": position(0, 3)}))
+    );
+  }
+
+  #[test]
+  fn definition_of_parameter() {
+    let src = "foo := 'bar'\nbaz foo:\n  echo {{foo}}\n";
+    let document = Document::parse(src).unwrap();
+    assert_eq!(
+      document.definition(&position(2, 10)),
+      Some(json!({"start": position(1, 4), "end": position(1, 7)}))
+    );
+  }
+
+  #[test]
+  fn definition_of_dependency() {
+    let src = "a: (b 'x')\nalias c := b\nb arg:\n";
+    let document = Document::parse(src).unwrap();
+    let expected = Some(json!({"start": position(2, 0), "end": position(2, 1)}));
+    assert_eq!(document.definition(&position(0, 4)), expected);
+    assert_eq!(document.definition(&position(1, 11)), expected);
+  }
+
+  #[test]
+  fn definition_in_function_call() {
+    let src = "a := 'x'\nb := env_var_or_default('y', a)\n";
+    let document = Document::parse(src).unwrap();
+    assert_eq!(
+      document.definition(&position(1, 29)),
+      Some(json!({"start": position(0, 0), "end": position(0, 1)}))
+    );
+  }
+
+  #[test]
+  fn span() {
+    let src = "a:\n  echo a\n\n  echo b\n\nb := 'c' # comment\nd:\n";
+    let document = Document::parse(src).unwrap();
+
+    let spans = document
+      .items
+      .iter()
+      .map(|item| match item {
+        Item::Recipe(recipe) => document.span(recipe.name),
+        Item::Assignment(assignment) => document.span(assignment.name),
+        _ => unreachable!(),
+      })
+      .map(|span| &src[span])
+      .collect::<Vec<&str>>();
+
+    assert_eq!(
+      spans,
+      &["a:\n  echo a\n\n  echo b", "b := 'c' # comment", "d:"]
+    );
+  }
+
+  #[test]
+  fn diagnostics() {
+    assert_eq!(
+      Document::diagnostics("a:\n  echo {{b}}\n"),
+      vec![json!({
+        "range": {"start": position(1, 9), "end": position(1, 10)},
+        "severity": 1,
+        "source": "just",
+        "message": "Variable `b` not defined",
+      })]
+    );
+  }
+
+  #[test]
+  fn diagnostics_warning() {
+    let diagnostics = Document::diagnostics("a = 'b'\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 2);
+    assert_eq!(
+      diagnostics[0]["range"],
+      json!({"start": position(0, 2), "end": position(0, 3)})
+    );
+  }
+}
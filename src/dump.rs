@@ -0,0 +1,281 @@
+//! The JSON representation of a justfile printed by `--dump --format json`.
+//!
+//! This format is stable. Fields may be added, but existing fields will not
+//! be removed or change meaning without incrementing `Dump::VERSION`.
+//! Expressions, including recipe lines, are given as justfile source text.
+
+use crate::common::*;
+
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Dump<'src> {
+  pub(crate) version:     u32,
+  pub(crate) aliases:     BTreeMap<&'src str, Alias<'src>>,
+  pub(crate) assignments: BTreeMap<&'src str, Assignment<'src>>,
+  pub(crate) first:       Option<&'src str>,
+  pub(crate) recipes:     BTreeMap<&'src str, Recipe<'src>>,
+  pub(crate) settings:    Settings<'src>,
+}
+
+impl<'src> Dump<'src> {
+  pub(crate) const VERSION: u32 = 1;
+
+  pub(crate) fn new(justfile: &'src Justfile<'src>) -> Self {
+    Self {
+      version:     Self::VERSION,
+      aliases:     justfile
+        .aliases
+        .iter()
+        .map(|(name, alias)| (*name, Alias::new(alias)))
+        .collect(),
+      assignments: justfile
+        .assignments
+        .iter()
+        .map(|(name, assignment)| (*name, Assignment::new(assignment)))
+        .collect(),
+      first:       justfile.first().map(crate::recipe::Recipe::name),
+      recipes:     justfile
+        .recipes
+        .iter()
+        .map(|(name, recipe)| (*name, Recipe::new(recipe)))
+        .collect(),
+      settings:    Settings::new(&justfile.settings),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Alias<'src> {
+  pub(crate) name:    &'src str,
+  pub(crate) target:  &'src str,
+  pub(crate) private: bool,
+}
+
+impl<'src> Alias<'src> {
+  fn new(alias: &'src crate::alias::Alias<'src>) -> Self {
+    Self {
+      name:    alias.name.lexeme(),
+      target:  alias.target.name(),
+      private: alias.is_private(),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Assignment<'src> {
+  pub(crate) name:   &'src str,
+  pub(crate) export: bool,
+  pub(crate) value:  String,
+}
+
+impl<'src> Assignment<'src> {
+  fn new(assignment: &'src crate::assignment::Assignment<'src>) -> Self {
+    Self {
+      name:   assignment.name.lexeme(),
+      export: assignment.export,
+      value:  assignment.value.to_string(),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Recipe<'src> {
+  pub(crate) name:         &'src str,
+  pub(crate) doc:          Option<&'src str>,
+  pub(crate) attributes:   Vec<String>,
+  pub(crate) parameters:   Vec<Parameter<'src>>,
+  pub(crate) dependencies: Vec<Dependency<'src>>,
+  pub(crate) body:         Vec<String>,
+  pub(crate) private:      bool,
+  pub(crate) quiet:        bool,
+  pub(crate) shebang:      bool,
+}
+
+impl<'src> Recipe<'src> {
+  fn new(recipe: &'src crate::recipe::Recipe<'src>) -> Self {
+    Self {
+      name:         recipe.name(),
+      doc:          recipe.doc.as_deref(),
+      attributes:   recipe
+        .attributes
+        .iter()
+        .map(ToString::to_string)
+        .collect(),
+      parameters:   recipe
+        .parameters
+        .iter()
+        .map(|parameter| Parameter::new(recipe, parameter))
+        .collect(),
+      dependencies: recipe.dependencies.iter().map(Dependency::new).collect(),
+      body:         recipe.body.iter().map(ToString::to_string).collect(),
+      private:      recipe.private,
+      quiet:        recipe.quiet,
+      shebang:      recipe.shebang,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Parameter<'src> {
+  pub(crate) name:     &'src str,
+  pub(crate) doc:      Option<&'src str>,
+  pub(crate) default:  Option<String>,
+  pub(crate) variadic: bool,
+}
+
+impl<'src> Parameter<'src> {
+  fn new(
+    recipe: &'src crate::recipe::Recipe<'src>,
+    parameter: &'src crate::parameter::Parameter<'src>,
+  ) -> Self {
+    Self {
+      name:     parameter.name.lexeme(),
+      doc:      recipe.parameter_doc(parameter.name.lexeme()),
+      default:  parameter.default.as_ref().map(ToString::to_string),
+      variadic: parameter.variadic,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Dependency<'src> {
+  pub(crate) recipe:    &'src str,
+  pub(crate) arguments: Vec<String>,
+}
+
+impl<'src> Dependency<'src> {
+  fn new(dependency: &'src crate::dependency::Dependency<'src>) -> Self {
+    Self {
+      recipe:    dependency.recipe.name(),
+      arguments: dependency
+        .arguments
+        .iter()
+        .map(ToString::to_string)
+        .collect(),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Settings<'src> {
+  pub(crate) after:    Option<&'src str>,
+  pub(crate) before:   Option<&'src str>,
+  pub(crate) fallback: bool,
+  pub(crate) shell:    Option<Vec<&'src str>>,
+}
+
+impl<'src> Settings<'src> {
+  fn new(settings: &'src crate::settings::Settings<'src>) -> Self {
+    Self {
+      after:    settings.after,
+      before:   settings.before,
+      fallback: settings.fallback,
+      shell:    settings.shell.as_ref().map(|shell| {
+        iter::once(&shell.command)
+          .chain(&shell.arguments)
+          .map(|literal| literal.cooked.as_ref())
+          .collect()
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn recipe() {
+    let justfile = testing::compile(
+      "
+alias b := build
+
+export FLAGS := '-v'
+
+# build the project
+[retry(\"2\")]
+build target='debug' +rest='': (setup target)
+  @echo {{target}}
+
+setup mode:
+",
+    );
+
+    let dump = Dump::new(&justfile);
+
+    assert_eq!(dump.version, 1);
+    assert_eq!(dump.first, Some("build"));
+
+    assert_eq!(
+      dump.aliases["b"],
+      Alias {
+        name:    "b",
+        target:  "build",
+        private: false,
+      }
+    );
+
+    assert_eq!(
+      dump.assignments["FLAGS"],
+      Assignment {
+        name:   "FLAGS",
+        export: true,
+        value:  "'-v'".to_owned(),
+      }
+    );
+
+    assert_eq!(
+      dump.recipes["build"],
+      Recipe {
+        name:         "build",
+        doc:          Some("build the project"),
+        attributes:   vec!["[retry(2)]".to_owned()],
+        parameters:   vec![
+          Parameter {
+            name:     "target",
+            doc:      None,
+            default:  Some("'debug'".to_owned()),
+            variadic: false,
+          },
+          Parameter {
+            name:     "rest",
+            doc:      None,
+            default:  Some("''".to_owned()),
+            variadic: true,
+          },
+        ],
+        dependencies: vec![Dependency {
+          recipe:    "setup",
+          arguments: vec!["target".to_owned()],
+        }],
+        body:         vec!["@echo {{target}}".to_owned()],
+        private:      false,
+        quiet:        false,
+        shebang:      false,
+      }
+    );
+  }
+
+  #[test]
+  fn settings() {
+    let justfile = testing::compile(
+      "
+set shell := ['bash', '-c']
+set before := 'banner'
+
+banner:
+",
+    );
+
+    assert_eq!(
+      Dump::new(&justfile).settings,
+      Settings {
+        after:    None,
+        before:   Some("banner"),
+        fallback: false,
+        shell:    Some(vec!["bash", "-c"]),
+      }
+    );
+  }
+}
@@ -0,0 +1,5 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum DumpFormat {
+  Json,
+  Just,
+}
@@ -20,3 +20,31 @@ impl<T, E: Error> ErrorResultExt<T> for Result<T, E> {
     }
   }
 }
+
+impl<T, E: Error> ErrorResultExt<T> for Result<T, Vec<E>> {
+  fn eprint(self, color: Color) -> Result<T, i32> {
+    match self {
+      Ok(ok) => Ok(ok),
+      Err(errors) => {
+        let mut code = EXIT_FAILURE;
+
+        let omitted = errors.len().saturating_sub(Compiler::MAX_ERRORS);
+
+        for (i, error) in errors.into_iter().take(Compiler::MAX_ERRORS).enumerate() {
+          if i > 0 {
+            eprintln!();
+          }
+
+          code = Err::<T, E>(error).eprint(color).err().unwrap_or(code);
+        }
+
+        if omitted > 0 {
+          eprintln!();
+          eprintln!("{} more {} omitted", omitted, Count("error", omitted));
+        }
+
+        Err(code)
+      },
+    }
+  }
+}
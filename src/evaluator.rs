@@ -109,7 +109,7 @@ impl<'src, 'run> Evaluator<'src, 'run> {
       },
       Expression::StringLiteral { string_literal } => Ok(string_literal.cooked.to_string()),
       Expression::Backtick { contents, token } =>
-        if self.config.dry_run {
+        if self.config.dry_run || self.config.subcommand.is_plan() {
           Ok(format!("`{}`", contents))
         } else {
           Ok(self.run_backtick(contents, token)?)
@@ -0,0 +1,103 @@
+/// A pattern matching paths relative to the working directory, used by
+/// `--watch` and the `[inputs]` attribute.
+///
+/// Paths are matched component by component, with `/` as the separator. `*`
+/// matches any number of characters within a component, `?` matches a single
+/// character, and a `**` component matches any number of components. A pattern
+/// also matches every path inside of a directory that it matches.
+#[derive(Debug, PartialEq)]
+pub(crate) struct FilePattern {
+  components: Vec<String>,
+}
+
+impl FilePattern {
+  pub(crate) fn new(pattern: &str) -> Self {
+    Self {
+      components: pattern
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .map(str::to_owned)
+        .collect(),
+    }
+  }
+
+  /// Check if the path with components `path` matches
+  pub(crate) fn matches(&self, path: &[&str]) -> bool {
+    Self::match_components(&self.components, path)
+  }
+
+  fn match_components(pattern: &[String], path: &[&str]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+      (None, _) => true,
+      (Some((first, rest)), _) if first == "**" =>
+        (0..=path.len()).any(|skip| Self::match_components(rest, &path[skip..])),
+      (Some(_), None) => false,
+      (Some((first, rest)), Some((component, path))) =>
+        Self::match_component(
+          &first.chars().collect::<Vec<char>>(),
+          &component.chars().collect::<Vec<char>>(),
+        ) && Self::match_components(rest, path),
+    }
+  }
+
+  fn match_component(pattern: &[char], text: &[char]) -> bool {
+    match (pattern.split_first(), text.split_first()) {
+      (None, None) => true,
+      (Some(('*', rest)), _) =>
+        (0..=text.len()).any(|skip| Self::match_component(rest, &text[skip..])),
+      (Some(('?', rest)), Some((_, text))) => Self::match_component(rest, text),
+      (Some((expected, rest)), Some((found, text))) =>
+        expected == found && Self::match_component(rest, text),
+      (Some(_), None) | (None, Some(_)) => false,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn matches(pattern: &str, path: &str) -> bool {
+    FilePattern::new(pattern).matches(&path.split('/').collect::<Vec<&str>>())
+  }
+
+  #[test]
+  fn literal() {
+    assert!(matches("Cargo.toml", "Cargo.toml"));
+    assert!(matches("./Cargo.toml", "Cargo.toml"));
+    assert!(!matches("Cargo.toml", "Cargo.lock"));
+    assert!(!matches("Cargo.toml", "foo/Cargo.toml"));
+  }
+
+  #[test]
+  fn directory() {
+    assert!(matches("src", "src/lib.rs"));
+    assert!(matches("src/", "src/foo/bar.rs"));
+    assert!(!matches("src", "tests/lib.rs"));
+  }
+
+  #[test]
+  fn star() {
+    assert!(matches("*.rs", "lib.rs"));
+    assert!(matches("src/*.rs", "src/lib.rs"));
+    assert!(matches("src/l*b.rs", "src/lb.rs"));
+    assert!(!matches("*.rs", "src/lib.rs"));
+    assert!(!matches("src/*.rs", "src/lib.toml"));
+  }
+
+  #[test]
+  fn question_mark() {
+    assert!(matches("src/?.rs", "src/a.rs"));
+    assert!(!matches("src/?.rs", "src/ab.rs"));
+  }
+
+  #[test]
+  fn double_star() {
+    assert!(matches("**/*.rs", "lib.rs"));
+    assert!(matches("**/*.rs", "src/foo/lib.rs"));
+    assert!(matches("src/**/*.rs", "src/lib.rs"));
+    assert!(matches("src/**/*.rs", "src/a/b/lib.rs"));
+    assert!(!matches("src/**/*.rs", "tests/lib.rs"));
+    assert!(!matches("src/**/*.rs", "src/a/b/lib.toml"));
+  }
+}
@@ -54,6 +54,10 @@ fn invocation_directory(context: &FunctionContext) -> Result<String, String> {
 }
 
 fn justfile(context: &FunctionContext) -> Result<String, String> {
+  if context.search.is_stdin() {
+    return Err("Justfile was read from standard input and has no path".to_owned());
+  }
+
   context
     .search
     .justfile
@@ -68,6 +72,10 @@ fn justfile(context: &FunctionContext) -> Result<String, String> {
 }
 
 fn justfile_directory(context: &FunctionContext) -> Result<String, String> {
+  if context.search.is_stdin() {
+    return Err("Justfile was read from standard input and has no directory".to_owned());
+  }
+
   let justfile_directory = context.search.justfile.parent().ok_or_else(|| {
     format!(
       "Could not resolve justfile directory. Justfile `{}` had no parent.",
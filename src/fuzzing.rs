@@ -1,9 +1,11 @@
 use crate::common::*;
 
 pub(crate) fn compile(text: &str) {
-  if let Err(error) = Parser::parse(text) {
-    if let CompilationErrorKind::Internal { .. } = error.kind {
-      panic!("{}", error)
+  if let Err(errors) = Parser::parse(text) {
+    for error in errors {
+      if let CompilationErrorKind::Internal { .. } = error.kind {
+        panic!("{}", error)
+      }
     }
   }
 }
@@ -0,0 +1,268 @@
+use crate::common::*;
+
+/// The dependency graph of a justfile, or of a single recipe and its
+/// transitive dependencies, printed by `--graph`
+pub(crate) struct Graph<'src: 'run, 'run> {
+  /// Recipes in the graph, by name
+  recipes: BTreeMap<&'src str, &'run Recipe<'src>>,
+}
+
+impl<'src: 'run, 'run> Graph<'src, 'run> {
+  /// The graph of `root` and its transitive dependencies, or of all recipes
+  /// in `justfile` if `root` is `None`
+  pub(crate) fn new(justfile: &'run Justfile<'src>, root: Option<&'run Recipe<'src>>) -> Self {
+    let mut recipes = BTreeMap::new();
+
+    let mut stack = match root {
+      Some(root) => vec![root],
+      None => justfile.recipes.values().map(Rc::as_ref).collect(),
+    };
+
+    while let Some(recipe) = stack.pop() {
+      if recipes.insert(recipe.name(), recipe).is_none() {
+        stack.extend(recipe.dependencies.iter().map(|dependency| dependency.recipe.as_ref()));
+      }
+    }
+
+    Self { recipes }
+  }
+
+  pub(crate) fn render(&self, format: GraphFormat) -> String {
+    match format {
+      GraphFormat::Dot => self.dot(),
+      GraphFormat::Mermaid => self.mermaid(),
+    }
+  }
+
+  /// Graphviz DOT, with private recipes drawn with a dashed outline
+  fn dot(&self) -> String {
+    fn quote(text: &str) -> String {
+      format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    let mut dot = String::from("digraph justfile {\n");
+
+    for recipe in self.recipes.values() {
+      dot += &format!(
+        "  {} [label={}{}];\n",
+        quote(recipe.name()),
+        quote(&Self::label(recipe)),
+        if recipe.private { ", style=dashed" } else { "" }
+      );
+    }
+
+    for (recipe, dependency, arguments) in self.edges() {
+      dot += &format!("  {} -> {}", quote(recipe), quote(dependency));
+
+      if let Some(arguments) = arguments {
+        dot += &format!(" [label={}]", quote(&arguments));
+      }
+
+      dot += ";\n";
+    }
+
+    dot += "}\n";
+
+    dot
+  }
+
+  /// A Mermaid flowchart, with private recipes drawn with a dashed outline.
+  /// Since Mermaid node IDs may not contain all characters that recipe names
+  /// may, nodes are identified by their position in the graph.
+  fn mermaid(&self) -> String {
+    fn quote(text: &str) -> String {
+      format!("\"{}\"", text.replace('"', "#quot;"))
+    }
+
+    let ids = self
+      .recipes
+      .keys()
+      .enumerate()
+      .map(|(i, name)| (*name, format!("recipe{}", i)))
+      .collect::<BTreeMap<&str, String>>();
+
+    let mut mermaid = String::from("flowchart TD\n");
+
+    for recipe in self.recipes.values() {
+      mermaid += &format!(
+        "  {}[{}]\n",
+        ids[recipe.name()],
+        quote(&Self::label(recipe))
+      );
+    }
+
+    for (recipe, dependency, arguments) in self.edges() {
+      mermaid += &format!("  {} -->", ids[recipe]);
+
+      if let Some(arguments) = arguments {
+        mermaid += &format!("|{}|", quote(&arguments));
+      }
+
+      mermaid += &format!(" {}\n", ids[dependency]);
+    }
+
+    let private = self
+      .recipes
+      .values()
+      .filter(|recipe| recipe.private)
+      .map(|recipe| ids[recipe.name()].as_str())
+      .collect::<Vec<&str>>();
+
+    if !private.is_empty() {
+      mermaid += "  classDef private stroke-dasharray: 5 5\n";
+      mermaid += &format!("  class {} private\n", private.join(","));
+    }
+
+    mermaid
+  }
+
+  /// A node label, consisting of the recipe name and parameters
+  fn label(recipe: &Recipe) -> String {
+    let mut label = recipe.name().to_owned();
+
+    for parameter in &recipe.parameters {
+      label += &format!(" {}", parameter);
+    }
+
+    label
+  }
+
+  /// Edges from recipes to their dependencies, in order, with the dependency
+  /// arguments, if any
+  fn edges(&self) -> Vec<(&'src str, &'src str, Option<String>)> {
+    let mut edges = Vec::new();
+
+    for recipe in self.recipes.values() {
+      for dependency in &recipe.dependencies {
+        let arguments = if dependency.arguments.is_empty() {
+          None
+        } else {
+          Some(
+            dependency
+              .arguments
+              .iter()
+              .map(ToString::to_string)
+              .collect::<Vec<String>>()
+              .join(" "),
+          )
+        };
+
+        edges.push((recipe.name(), dependency.recipe.name(), arguments));
+      }
+    }
+
+    edges
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  macro_rules! test {
+    {
+      name: $name:ident,
+      justfile: $justfile:expr,
+      $(recipe: $recipe:expr,)?
+      format: $format:expr,
+      graph: $graph:expr,
+    } => {
+      #[test]
+      fn $name() {
+        let src = testing::unindent($justfile);
+        let justfile = testing::compile(&src);
+
+        #[allow(unused_mut, unused_assignments)]
+        let mut root = None;
+        $(root = justfile.get_recipe($recipe);)?
+
+        let have = Graph::new(&justfile, root).render($format);
+
+        assert_eq!(have, testing::unindent($graph));
+      }
+    }
+  }
+
+  const JUSTFILE: &str = r#"
+    build mode="debug": (test mode) _setup
+      cargo build --{{mode}}
+
+    test mode +flags='':
+      cargo test --{{mode}} {{flags}}
+
+    _setup:
+
+    clean:
+  "#;
+
+  test! {
+    name: dot,
+    justfile: JUSTFILE,
+    format: GraphFormat::Dot,
+    graph: r#"
+      digraph justfile {
+        "_setup" [label="_setup", style=dashed];
+        "build" [label="build mode=\"debug\""];
+        "clean" [label="clean"];
+        "test" [label="test mode +flags=''"];
+        "build" -> "test" [label="mode"];
+        "build" -> "_setup";
+      }
+    "#,
+  }
+
+  test! {
+    name: mermaid,
+    justfile: JUSTFILE,
+    format: GraphFormat::Mermaid,
+    graph: r#"
+      flowchart TD
+        recipe0["_setup"]
+        recipe1["build mode=#quot;debug#quot;"]
+        recipe2["clean"]
+        recipe3["test mode +flags=''"]
+        recipe1 -->|"mode"| recipe3
+        recipe1 --> recipe0
+        classDef private stroke-dasharray: 5 5
+        class recipe0 private
+    "#,
+  }
+
+  test! {
+    name: transitive_dependencies_of_recipe,
+    justfile: "
+      a: b
+      b: c
+      c:
+      d: a
+    ",
+    recipe: "a",
+    format: GraphFormat::Dot,
+    graph: r#"
+      digraph justfile {
+        "a" [label="a"];
+        "b" [label="b"];
+        "c" [label="c"];
+        "a" -> "b";
+        "b" -> "c";
+      }
+    "#,
+  }
+
+  test! {
+    name: no_private_recipes,
+    justfile: "
+      a: b
+      b:
+    ",
+    format: GraphFormat::Mermaid,
+    graph: "
+      flowchart TD
+        recipe0[\"a\"]
+        recipe1[\"b\"]
+        recipe0 --> recipe1
+    ",
+  }
+}
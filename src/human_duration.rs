@@ -0,0 +1,88 @@
+use crate::common::*;
+
+use std::str::FromStr;
+
+/// A `Duration` written as an integer followed by a unit, e.g. `10m`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct HumanDuration(pub(crate) Duration);
+
+const UNITS: &[(&str, u64)] = &[("h", 60 * 60 * 1000), ("m", 60 * 1000), ("s", 1000), ("ms", 1)];
+
+impl FromStr for HumanDuration {
+  type Err = ();
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+
+    if digits == 0 {
+      return Err(());
+    }
+
+    let (number, unit) = text.split_at(digits);
+
+    let number = number.parse::<u64>().or(Err(()))?;
+
+    let (_, milliseconds) = UNITS
+      .iter()
+      .find(|(name, _)| *name == unit)
+      .ok_or(())?;
+
+    let milliseconds = number.checked_mul(*milliseconds).ok_or(())?;
+
+    Ok(HumanDuration(Duration::from_millis(milliseconds)))
+  }
+}
+
+impl Display for HumanDuration {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let milliseconds = self.0.as_millis();
+
+    for (name, size) in UNITS {
+      let size = u128::from(*size);
+      if milliseconds.checked_rem(size) == Some(0) && (milliseconds > 0 || size == 1) {
+        if let Some(count) = milliseconds.checked_div(size) {
+          return write!(f, "{}{}", count, name);
+        }
+      }
+    }
+
+    write!(f, "{}ms", milliseconds)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(text: &str) -> Option<Duration> {
+    text.parse::<HumanDuration>().ok().map(|duration| duration.0)
+  }
+
+  #[test]
+  fn units() {
+    assert_eq!(parse("250ms"), Some(Duration::from_millis(250)));
+    assert_eq!(parse("30s"), Some(Duration::from_secs(30)));
+    assert_eq!(parse("10m"), Some(Duration::from_secs(600)));
+    assert_eq!(parse("2h"), Some(Duration::from_secs(7200)));
+  }
+
+  #[test]
+  fn invalid() {
+    assert_eq!(parse(""), None);
+    assert_eq!(parse("10"), None);
+    assert_eq!(parse("m"), None);
+    assert_eq!(parse("-1s"), None);
+    assert_eq!(parse("1.5s"), None);
+    assert_eq!(parse("10 s"), None);
+    assert_eq!(parse("10d"), None);
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(HumanDuration(Duration::from_secs(600)).to_string(), "10m");
+    assert_eq!(HumanDuration(Duration::from_secs(90)).to_string(), "90s");
+    assert_eq!(HumanDuration(Duration::from_millis(1500)).to_string(), "1500ms");
+    assert_eq!(HumanDuration(Duration::from_secs(3600)).to_string(), "1h");
+    assert_eq!(HumanDuration(Duration::from_secs(0)).to_string(), "0ms");
+  }
+}
@@ -1,7 +1,10 @@
 use crate::common::*;
 
+use std::mem;
+
 pub(crate) struct InterruptHandler {
   blocks:      u32,
+  deferrals:   u32,
   interrupted: bool,
 }
 
@@ -29,14 +32,19 @@ impl InterruptHandler {
   fn new() -> Self {
     Self {
       blocks:      0,
+      deferrals:   0,
       interrupted: false,
     }
   }
 
-  fn interrupt(&mut self) {
+  /// Record an interrupt, and exit unless blocked or deferred. Called when
+  /// `^C` is received, or when a child that was in the foreground of the
+  /// terminal in place of `just`, and so received `^C` instead of it, is
+  /// terminated by `SIGINT`.
+  pub(crate) fn interrupt(&mut self) {
     self.interrupted = true;
 
-    if self.blocks > 0 {
+    if self.blocks > 0 || self.deferrals > 0 {
       return;
     }
 
@@ -61,11 +69,56 @@ impl InterruptHandler {
 
     self.blocks -= 1;
 
-    if self.interrupted {
+    if self.interrupted && self.deferrals == 0 {
+      Self::exit();
+    }
+  }
+
+  /// Defer exiting on interrupt until `undefer` is called, so that finally
+  /// recipes can run
+  pub(crate) fn defer(&mut self) {
+    self.deferrals += 1;
+  }
+
+  /// Stop deferring exit on interrupt, and exit if an interrupt was received
+  /// while deferred, either before or after `take_interrupted`
+  pub(crate) fn undefer(&mut self, interrupted: bool) {
+    if self.deferrals == 0 {
+      eprintln!("{}", RuntimeError::Internal {
+        message: "attempted to undefer interrupt handler, but handler was not deferred"
+          .to_string(),
+      });
+      std::process::exit(EXIT_FAILURE);
+    }
+
+    self.deferrals -= 1;
+    self.interrupted |= interrupted;
+
+    if self.interrupted && self.deferrals == 0 && self.blocks == 0 {
       Self::exit();
     }
   }
 
+  /// Clear and return whether an interrupt was received, so that a finally
+  /// recipe can run without being interrupted itself
+  pub(crate) fn take_interrupted(&mut self) -> bool {
+    mem::replace(&mut self.interrupted, false)
+  }
+
+  /// Whether an interrupt was received, without clearing it
+  pub(crate) fn interrupted(&self) -> bool {
+    self.interrupted
+  }
+
+  /// Return an error if an interrupt was received while exit was deferred
+  pub(crate) fn check(recipe: &str) -> RunResult<()> {
+    if Self::instance().interrupted {
+      Err(RuntimeError::Interrupted { recipe })
+    } else {
+      Ok(())
+    }
+  }
+
   pub(crate) fn guard<T, F: FnOnce() -> T>(function: F) -> T {
     let _guard = InterruptGuard::new();
     function()
@@ -1,5 +1,9 @@
 use crate::common::*;
 
+/// Environment variable set to the error message of a failed run, or the
+/// empty string, for the `after` hook
+const AFTER_HOOK_ERROR: &str = "JUST_ERROR";
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct Justfile<'src> {
   pub(crate) recipes:     Table<'src, Rc<Recipe<'src>>>,
@@ -13,6 +17,10 @@ impl<'src> Justfile<'src> {
   pub(crate) fn first(&self) -> Option<&Recipe> {
     let mut first: Option<&Recipe<Dependency>> = None;
     for recipe in self.recipes.values() {
+      if self.is_hook(recipe.name()) {
+        continue;
+      }
+
       if let Some(first_recipe) = first {
         if recipe.line_number() < first_recipe.line_number() {
           first = Some(recipe)
@@ -24,6 +32,43 @@ impl<'src> Justfile<'src> {
     first
   }
 
+  /// Public recipes that aren't hooks, in alphabetical order, or in the
+  /// order they appear in the justfile if `unsorted`
+  pub(crate) fn public_recipes(&self, unsorted: bool) -> Vec<&Recipe<'src>> {
+    let mut recipes = self
+      .recipes
+      .values()
+      .map(Rc::as_ref)
+      .filter(|recipe| !recipe.private && !self.is_hook(recipe.name()))
+      .collect::<Vec<&Recipe>>();
+
+    if unsorted {
+      recipes.sort_by_key(|recipe| recipe.line_number());
+    }
+
+    recipes
+  }
+
+  /// Groups of public recipes, in alphabetical order, or in the order they
+  /// first appear in the justfile if `unsorted`
+  pub(crate) fn public_groups(&self, unsorted: bool) -> Vec<&str> {
+    let mut groups = Vec::new();
+
+    for recipe in self.public_recipes(unsorted) {
+      for group in recipe.groups() {
+        if !groups.contains(&group) {
+          groups.push(group);
+        }
+      }
+    }
+
+    if !unsorted {
+      groups.sort_unstable();
+    }
+
+    groups
+  }
+
   pub(crate) fn count(&self) -> usize {
     self.recipes.len()
   }
@@ -78,47 +123,16 @@ impl<'src> Justfile<'src> {
 
     let arguments = argvec.as_slice();
 
-    let unknown_overrides = overrides
-      .keys()
-      .filter(|name| !self.assignments.contains_key(name.as_str()))
-      .map(String::as_str)
-      .collect::<Vec<&str>>();
+    // recipes are resolved before overrides are checked or assignments are
+    // evaluated, so that unknown recipes are reported, and can be retried
+    // with `set fallback`, without running any backticks
+    let grouped = self.group(arguments)?;
 
-    if !unknown_overrides.is_empty() {
-      return Err(RuntimeError::UnknownOverrides {
-        overrides: unknown_overrides,
-      });
-    }
+    self.check_overrides(overrides)?;
 
     let dotenv = load_dotenv()?;
 
-    let scope = {
-      let mut scope = Scope::new();
-      let mut unknown_overrides = Vec::new();
-
-      for (name, value) in overrides {
-        if let Some(assignment) = self.assignments.get(name) {
-          scope.bind(assignment.export, assignment.name, value.clone());
-        } else {
-          unknown_overrides.push(name.as_ref());
-        }
-      }
-
-      if !unknown_overrides.is_empty() {
-        return Err(RuntimeError::UnknownOverrides {
-          overrides: unknown_overrides,
-        });
-      }
-
-      Evaluator::evaluate_assignments(
-        &self.assignments,
-        config,
-        &dotenv,
-        scope,
-        &self.settings,
-        search,
-      )?
-    };
+    let scope = self.scope(config, &dotenv, search, overrides)?;
 
     if let Subcommand::Evaluate { .. } = config.subcommand {
       let mut width = 0;
@@ -138,6 +152,171 @@ impl<'src> Justfile<'src> {
       return Ok(());
     }
 
+    self.run_grouped(config, search, &dotenv, scope, grouped)
+  }
+
+  /// Run recipes chosen with `--choose`. Since each recipe's arguments are
+  /// given separately, they aren't grouped like command line arguments, so
+  /// optional and variadic parameters don't swallow the following recipes.
+  pub(crate) fn run_chosen<'run>(
+    &'run self,
+    config: &'run Config,
+    search: &'run Search,
+    overrides: &'run BTreeMap<String, String>,
+    chosen: &'run [Vec<String>],
+  ) -> RunResult<'run, ()> {
+    let argvecs = chosen
+      .iter()
+      .map(|invocation| invocation.iter().map(String::as_str).collect())
+      .collect::<Vec<Vec<&str>>>();
+
+    let mut grouped = Vec::new();
+
+    for argvec in &argvecs {
+      grouped.extend(self.group(argvec)?);
+    }
+
+    self.check_overrides(overrides)?;
+
+    let dotenv = load_dotenv()?;
+
+    let scope = self.scope(config, &dotenv, search, overrides)?;
+
+    self.run_grouped(config, search, &dotenv, scope, grouped)
+  }
+
+  /// Run `grouped` recipes with their arguments, along with hooks, or print
+  /// the plan for running them with `--plan`
+  fn run_grouped<'run>(
+    &'run self,
+    config: &'run Config,
+    search: &'run Search,
+    dotenv: &'run BTreeMap<String, String>,
+    scope: Scope<'src, 'run>,
+    grouped: Vec<(&'run Recipe<'src>, &[&'run str])>,
+  ) -> RunResult<'src, ()> {
+    let context = RecipeContext {
+      settings: &self.settings,
+      config,
+      scope,
+      search,
+    };
+
+    let before = self.settings.before.map(|name| self.hook(name)).transpose()?;
+    let after = self.settings.after.map(|name| self.hook(name)).transpose()?;
+
+    if let Subcommand::Plan { .. } = config.subcommand {
+      print!(
+        "{}",
+        Plan::new(self, &context, before, grouped, after, dotenv, search)?
+      );
+      return Ok(());
+    }
+
+    let mut ran = BTreeSet::new();
+
+    let after = if let Some(after) = after {
+      after
+    } else {
+      return self.run_recipes(&context, before, grouped, dotenv, search, &mut ran);
+    };
+
+    InterruptHandler::instance().defer();
+
+    let result = self.run_recipes(&context, before, grouped, dotenv, search, &mut ran);
+
+    let interrupted = InterruptHandler::instance().take_interrupted();
+
+    let error = match &result {
+      Ok(()) => String::new(),
+      Err(error) => error.to_string(),
+    };
+
+    // the message is exported rather than passed as an argument, so that
+    // backticks in it aren't run by the shell if it is interpolated
+    let mut dotenv = dotenv.clone();
+    dotenv.insert(AFTER_HOOK_ERROR.to_owned(), error);
+
+    let hook = self.run_recipe(&context, after, &[], &dotenv, search, &mut ran);
+
+    InterruptHandler::instance().undefer(interrupted);
+
+    result.and(hook)
+  }
+
+  /// Evaluate assignments, with `overrides` taking precedence, returning the
+  /// value of each variable by name
+  pub(crate) fn evaluate<'run>(
+    &'run self,
+    config: &'run Config,
+    search: &'run Search,
+    overrides: &'run BTreeMap<String, String>,
+  ) -> RunResult<'run, BTreeMap<String, String>> {
+    self.check_overrides(overrides)?;
+
+    let dotenv = load_dotenv()?;
+
+    let scope = self.scope(config, &dotenv, search, overrides)?;
+
+    Ok(
+      scope
+        .bindings()
+        .map(|binding| (binding.name.lexeme().to_owned(), binding.value.clone()))
+        .collect(),
+    )
+  }
+
+  /// Check that all `overrides` name assignments
+  fn check_overrides<'run>(
+    &self,
+    overrides: &'run BTreeMap<String, String>,
+  ) -> RunResult<'run, ()> {
+    let unknown_overrides = overrides
+      .keys()
+      .filter(|name| !self.assignments.contains_key(name.as_str()))
+      .map(String::as_str)
+      .collect::<Vec<&str>>();
+
+    if !unknown_overrides.is_empty() {
+      return Err(RuntimeError::UnknownOverrides {
+        overrides: unknown_overrides,
+      });
+    }
+
+    Ok(())
+  }
+
+  /// Evaluate assignments with `overrides` bound
+  fn scope<'run>(
+    &'run self,
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    search: &'run Search,
+    overrides: &BTreeMap<String, String>,
+  ) -> RunResult<'src, Scope<'src, 'run>> {
+    let mut scope = Scope::new();
+
+    for (name, value) in overrides {
+      if let Some(assignment) = self.assignments.get(name) {
+        scope.bind(assignment.export, assignment.name, value.clone());
+      }
+    }
+
+    Evaluator::evaluate_assignments(
+      &self.assignments,
+      config,
+      dotenv,
+      scope,
+      &self.settings,
+      search,
+    )
+  }
+
+  /// Group `arguments` into recipes and the arguments to each recipe
+  fn group<'run, 'arguments>(
+    &'run self,
+    arguments: &'arguments [&'run str],
+  ) -> RunResult<'run, Vec<(&'run Recipe<'src>, &'arguments [&'run str])>> {
     let mut missing = vec![];
     let mut grouped = vec![];
     let mut rest = arguments;
@@ -179,21 +358,76 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    let context = RecipeContext {
-      settings: &self.settings,
-      config,
-      scope,
-      search,
-    };
+    Ok(grouped)
+  }
+
+  /// Run the `before` hook, if any, and then the recipes requested on the
+  /// command line
+  fn run_recipes<'run>(
+    &self,
+    context: &'run RecipeContext<'src, 'run>,
+    before: Option<&Recipe<'src>>,
+    grouped: Vec<(&Recipe<'src>, &[&'run str])>,
+    dotenv: &BTreeMap<String, String>,
+    search: &'run Search,
+    ran: &mut BTreeSet<Vec<String>>,
+  ) -> RunResult<'src, ()> {
+    if let Some(before) = before {
+      self.run_recipe(context, before, &[], dotenv, search, ran)?;
+    }
 
-    let mut ran = BTreeSet::new();
     for (recipe, arguments) in grouped {
-      self.run_recipe(&context, recipe, arguments, &dotenv, &search, &mut ran)?
+      self.run_recipe(context, recipe, arguments, dotenv, search, ran)?
     }
 
     Ok(())
   }
 
+  /// Get the recipe named by a `before` or `after` hook setting
+  fn hook(&self, name: &str) -> RunResult<'src, &Recipe<'src>> {
+    self
+      .recipes
+      .get(name)
+      .map(Rc::as_ref)
+      .ok_or_else(|| RuntimeError::Internal {
+        message: format!("unknown hook recipe `{}`", name),
+      })
+  }
+
+  /// Patterns from the `[inputs]` attributes of the recipes in `arguments`,
+  /// or the default recipe if `arguments` is empty, and their dependencies
+  pub(crate) fn inputs(&self, arguments: &[String]) -> Vec<&str> {
+    let mut stack = if arguments.is_empty() {
+      self.first().into_iter().collect::<Vec<&Recipe>>()
+    } else {
+      arguments
+        .iter()
+        .filter_map(|argument| self.get_recipe(argument))
+        .collect()
+    };
+
+    let mut seen = BTreeSet::new();
+    let mut inputs = Vec::new();
+
+    while let Some(recipe) = stack.pop() {
+      if !seen.insert(recipe.name()) {
+        continue;
+      }
+
+      inputs.extend(recipe.inputs());
+
+      for dependency in &recipe.dependencies {
+        stack.push(&dependency.recipe);
+      }
+    }
+
+    inputs
+  }
+
+  pub(crate) fn is_hook(&self, name: &str) -> bool {
+    self.settings.before == Some(name) || self.settings.after == Some(name)
+  }
+
   pub(crate) fn get_alias(&self, name: &str) -> Option<&Alias> {
     self.aliases.get(name)
   }
@@ -216,6 +450,39 @@ impl<'src> Justfile<'src> {
     dotenv: &BTreeMap<String, String>,
     search: &'run Search,
     ran: &mut BTreeSet<Vec<String>>,
+  ) -> RunResult<'src, ()> {
+    let finally = if let Some((name, _token)) = recipe.finally() {
+      self
+        .recipes
+        .get(name)
+        .ok_or_else(|| RuntimeError::Internal {
+          message: format!("unknown finally recipe `{}`", name),
+        })?
+    } else {
+      return self.run_recipe_and_dependencies(context, recipe, arguments, dotenv, search, ran);
+    };
+
+    InterruptHandler::instance().defer();
+
+    let result = self.run_recipe_and_dependencies(context, recipe, arguments, dotenv, search, ran);
+
+    let interrupted = InterruptHandler::instance().take_interrupted();
+
+    let cleanup = self.run_recipe(context, finally, &[], dotenv, search, ran);
+
+    InterruptHandler::instance().undefer(interrupted);
+
+    result.and(cleanup)
+  }
+
+  fn run_recipe_and_dependencies<'run>(
+    &self,
+    context: &'run RecipeContext<'src, 'run>,
+    recipe: &Recipe<'src>,
+    arguments: &[&'run str],
+    dotenv: &BTreeMap<String, String>,
+    search: &'run Search,
+    ran: &mut BTreeSet<Vec<String>>,
   ) -> RunResult<'src, ()> {
     let scope = Evaluator::evaluate_parameters(
       context.config,
@@ -264,6 +531,9 @@ impl<'src> Display for Justfile<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     let mut items = self.recipes.len() + self.assignments.len() + self.aliases.len();
     for (name, assignment) in &self.assignments {
+      for attribute in &assignment.attributes {
+        writeln!(f, "{}", attribute)?;
+      }
       if assignment.export {
         write!(f, "export ")?;
       }
@@ -355,11 +625,13 @@ mod tests {
       recipe,
       line_number,
       code,
+      attempts,
     },
     check: {
       assert_eq!(recipe, "a");
       assert_eq!(code, 200);
       assert_eq!(line_number, None);
+      assert_eq!(attempts, 1);
     }
   }
 
@@ -374,11 +646,13 @@ mod tests {
       recipe,
       line_number,
       code,
+      attempts,
     },
     check: {
       assert_eq!(recipe, "fail");
       assert_eq!(code, 100);
       assert_eq!(line_number, Some(2));
+      assert_eq!(attempts, 1);
     }
   }
 
@@ -393,11 +667,13 @@ mod tests {
       recipe,
       line_number,
       code,
+      attempts,
     },
     check: {
       assert_eq!(recipe, "a");
       assert_eq!(code, 150);
       assert_eq!(line_number, Some(2));
+      assert_eq!(attempts, 1);
     }
   }
 
@@ -548,12 +824,14 @@ mod tests {
     args: ["--quiet", "wut"],
     error: Code {
       code: _,
+      attempts,
       line_number,
       recipe,
     },
     check: {
       assert_eq!(recipe, "wut");
       assert_eq!(line_number, Some(7));
+      assert_eq!(attempts, 1);
     }
   }
 
@@ -1,5 +1,22 @@
+pub(crate) const AFTER: &str = "after";
 pub(crate) const ALIAS: &str = "alias";
+pub(crate) const ALLOW: &str = "allow";
+pub(crate) const BACKOFF: &str = "backoff";
+pub(crate) const BEFORE: &str = "before";
+pub(crate) const CHOICES: &str = "choices";
+pub(crate) const DELAY: &str = "delay";
+pub(crate) const DOC: &str = "doc";
 pub(crate) const EXPORT: &str = "export";
+pub(crate) const FALLBACK: &str = "fallback";
+pub(crate) const FALSE: &str = "false";
+pub(crate) const FINALLY: &str = "finally";
+pub(crate) const GROUP: &str = "group";
+pub(crate) const INPUTS: &str = "inputs";
+pub(crate) const PATH: &str = "path";
+pub(crate) const RETRY: &str = "retry";
 pub(crate) const SET: &str = "set";
 
 pub(crate) const SHELL: &str = "shell";
+
+pub(crate) const TIMEOUT: &str = "timeout";
+pub(crate) const TRUE: &str = "true";
@@ -0,0 +1,209 @@
+use crate::common::*;
+
+use serde_json::{json, Value};
+use std::io::BufRead;
+
+/// A language server, speaking JSON-RPC over stdin and stdout, which
+/// publishes compilation errors as diagnostics and supports go-to-definition,
+/// hover, completion, and document symbols
+pub(crate) struct LanguageServer {
+  /// Text of open documents, by URI
+  documents: BTreeMap<String, String>,
+  /// Whether a `shutdown` request has been received
+  shutdown:  bool,
+}
+
+impl LanguageServer {
+  const METHOD_NOT_FOUND: i64 = -32601;
+
+  /// Serve requests from `input` until the client sends `exit`, returning
+  /// the exit code
+  pub(crate) fn run(mut input: impl BufRead, mut output: impl Write) -> io::Result<i32> {
+    let mut server = Self {
+      documents: BTreeMap::new(),
+      shutdown:  false,
+    };
+
+    while let Some(message) = Self::receive(&mut input)? {
+      let method = match message["method"].as_str() {
+        Some(method) => method,
+        // responses to requests, which this server never sends
+        None => continue,
+      };
+
+      if method == "exit" {
+        return Ok(if server.shutdown { 0 } else { EXIT_FAILURE });
+      }
+
+      let params = &message["params"];
+
+      if let Some(id) = message.get("id") {
+        let response = match server.request(method, params) {
+          Ok(result) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+          }),
+          Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": error,
+          }),
+        };
+
+        Self::send(&mut output, &response)?;
+      } else {
+        for notification in server.notification(method, params) {
+          Self::send(&mut output, &notification)?;
+        }
+      }
+    }
+
+    Ok(EXIT_FAILURE)
+  }
+
+  fn request(&mut self, method: &str, params: &Value) -> Result<Value, Value> {
+    let text = params["textDocument"]["uri"]
+      .as_str()
+      .and_then(|uri| self.documents.get(uri));
+
+    let document = text.and_then(|text| Document::parse(text));
+
+    let position = &params["position"];
+
+    match method {
+      "initialize" => Ok(json!({
+        "capabilities": {
+          "textDocumentSync": 1,
+          "definitionProvider": true,
+          "hoverProvider": true,
+          "completionProvider": {},
+          "documentSymbolProvider": true,
+        },
+        "serverInfo": {
+          "name": env!("CARGO_PKG_NAME"),
+          "version": env!("CARGO_PKG_VERSION"),
+        },
+      })),
+      "shutdown" => {
+        self.shutdown = true;
+        Ok(Value::Null)
+      },
+      "textDocument/completion" => {
+        let mut completions = document
+          .map(|document| document.completions(position))
+          .unwrap_or_default();
+        completions.extend(Document::builtin_completions());
+        Ok(Value::Array(completions))
+      },
+      "textDocument/definition" => Ok(
+        document
+          .and_then(|document| document.definition(position))
+          .map_or(Value::Null, |range| {
+            json!({
+              "uri": params["textDocument"]["uri"],
+              "range": range,
+            })
+          }),
+      ),
+      "textDocument/documentSymbol" => Ok(
+        document
+          .map(|document| Value::Array(document.symbols()))
+          .unwrap_or(Value::Null),
+      ),
+      "textDocument/hover" => Ok(
+        document
+          .and_then(|document| document.hover(position))
+          .unwrap_or(Value::Null),
+      ),
+      _ => Err(json!({
+        "code": Self::METHOD_NOT_FOUND,
+        "message": format!("Method not found: {}", method),
+      })),
+    }
+  }
+
+  /// Handle a notification, returning the notifications to send in response
+  fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+    let uri = match params["textDocument"]["uri"].as_str() {
+      Some(uri) => uri.to_owned(),
+      None => return Vec::new(),
+    };
+
+    let text = match method {
+      "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+      "textDocument/didChange" => params["contentChanges"]
+        .as_array()
+        .and_then(|changes| changes.last())
+        .and_then(|change| change["text"].as_str()),
+      "textDocument/didClose" => {
+        self.documents.remove(&uri);
+        return vec![Self::diagnostics(&uri, Vec::new())];
+      },
+      _ => None,
+    };
+
+    match text {
+      Some(text) => {
+        let diagnostics = Document::diagnostics(text);
+        self.documents.insert(uri.clone(), text.to_owned());
+        vec![Self::diagnostics(&uri, diagnostics)]
+      },
+      None => Vec::new(),
+    }
+  }
+
+  fn diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": {
+        "uri": uri,
+        "diagnostics": diagnostics,
+      },
+    })
+  }
+
+  /// Read a message, returning `None` if `input` has been closed
+  fn receive(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+
+    loop {
+      let mut header = String::new();
+
+      if input.read_line(&mut header)? == 0 {
+        return Ok(None);
+      }
+
+      let header = header.trim_end();
+
+      if header.is_empty() {
+        break;
+      }
+
+      if let Some(value) = header.strip_prefix("Content-Length:") {
+        length = Some(value.trim().parse::<usize>().map_err(|parse_int_error| {
+          io::Error::new(io::ErrorKind::InvalidData, parse_int_error)
+        })?);
+      }
+    }
+
+    let length = length.ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        "message without Content-Length header",
+      )
+    })?;
+
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+
+    Ok(Some(serde_json::from_slice(&content)?))
+  }
+
+  fn send(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+    output.flush()
+  }
+}
@@ -454,6 +454,7 @@ impl<'src> Lexer<'src> {
       ' ' | '\t' => self.lex_whitespace(),
       '\'' => self.lex_raw_string(),
       '"' => self.lex_cooked_string(),
+      '0'..='9' => self.lex_integer(),
       _ =>
         if Self::is_identifier_start(start) {
           self.lex_identifier()
@@ -624,6 +625,24 @@ impl<'src> Lexer<'src> {
     Ok(())
   }
 
+  /// Lex integer: [0-9]+
+  fn lex_integer(&mut self) -> CompilationResult<'src, ()> {
+    while let Some('0'..='9') = self.next {
+      self.advance()?;
+    }
+
+    // names may not start with a digit
+    if let Some(c) = self.next {
+      if Self::is_identifier_continue(c) {
+        return Err(self.error(UnknownStartOfToken));
+      }
+    }
+
+    self.token(Integer);
+
+    Ok(())
+  }
+
   /// Lex comment: #[^\r\n]
   fn lex_comment(&mut self) -> CompilationResult<'src, ()> {
     // advance over #
@@ -826,7 +845,8 @@ mod tests {
       Dedent | Eof => "",
 
       // Variable lexemes
-      Text | StringCooked | StringRaw | Identifier | Comment | Backtick | Unspecified =>
+      Text | StringCooked | StringRaw | Identifier | Integer | Comment | Backtick
+      | Unspecified =>
         panic!("Token {:?} has no default lexeme", kind),
     }
   }
@@ -905,6 +925,12 @@ mod tests {
     tokens: (StringCooked:"\"hello\""),
   }
 
+  test! {
+    name:   integer,
+    text:   "[retry(10)]",
+    tokens: (BracketL, Identifier:"retry", ParenL, Integer:"10", ParenR, BracketR),
+  }
+
   test! {
     name:   export_concatination,
     text:   "export foo = 'foo' + 'bar'",
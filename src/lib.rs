@@ -52,19 +52,27 @@ mod alias;
 mod analyzer;
 mod assignment;
 mod assignment_resolver;
+mod attribute;
 mod binding;
+mod chooser;
 mod color;
 mod command_ext;
 mod common;
 mod compilation_error;
 mod compilation_error_kind;
 mod compilation_result_ext;
+mod completer;
 mod compiler;
 mod config;
 mod config_error;
 mod count;
 mod default;
 mod dependency;
+mod diagnostic;
+mod document;
+mod docs;
+mod dump;
+mod dump_format;
 mod empty;
 mod enclosure;
 mod error;
@@ -73,18 +81,26 @@ mod evaluator;
 mod expression;
 mod fragment;
 mod function;
+mod file_pattern;
 mod function_context;
+mod graph;
+mod graph_format;
+mod human_duration;
 mod interrupt_guard;
 mod interrupt_handler;
 mod item;
 mod justfile;
 mod keyed;
 mod keyword;
+mod language_server;
 mod lexer;
 mod line;
+mod lint;
+mod linter;
 mod list;
 mod load_dotenv;
 mod load_error;
+mod message_format;
 mod module;
 mod name;
 mod ordinal;
@@ -92,6 +108,7 @@ mod output;
 mod output_error;
 mod parameter;
 mod parser;
+mod plan;
 mod platform;
 mod platform_interface;
 mod position;
@@ -100,6 +117,8 @@ mod range_ext;
 mod recipe;
 mod recipe_context;
 mod recipe_resolver;
+mod reformatter;
+mod retry;
 mod run;
 mod runtime_error;
 mod scope;
@@ -109,7 +128,9 @@ mod search_error;
 mod set;
 mod setting;
 mod settings;
+mod severity;
 mod shebang;
+mod sinks;
 mod show_whitespace;
 mod string_literal;
 mod subcommand;
@@ -120,12 +141,16 @@ mod token;
 mod token_kind;
 mod unresolved_dependency;
 mod unresolved_recipe;
+mod usage;
 mod use_color;
 mod variables;
 mod verbosity;
 mod warning;
+mod watcher;
 
 pub use crate::run::run;
 
+pub mod api;
+
 #[cfg(feature = "summary")]
 pub mod summary;
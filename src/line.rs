@@ -18,6 +18,16 @@ impl<'src> Line<'src> {
     }
   }
 
+  pub(crate) fn is_infallible(&self) -> bool {
+    match self.fragments.first() {
+      Some(Fragment::Text { token }) => {
+        let text = token.lexeme();
+        text.starts_with('-') || text.starts_with("@-")
+      },
+      _ => false,
+    }
+  }
+
   pub(crate) fn is_shebang(&self) -> bool {
     match self.fragments.first() {
       Some(Fragment::Text { token }) => token.lexeme().starts_with("#!"),
@@ -25,3 +35,16 @@ impl<'src> Line<'src> {
     }
   }
 }
+
+impl<'src> Display for Line<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for fragment in &self.fragments {
+      match fragment {
+        Fragment::Text { token } => write!(f, "{}", token.lexeme())?,
+        Fragment::Interpolation { expression, .. } => write!(f, "{{{{{}}}}}", expression)?,
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,55 @@
+use crate::common::*;
+
+use std::str::FromStr;
+
+/// A kind of warning reported by `--lint`, which can be suppressed with the
+/// `[allow("lint-name")]` attribute
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub(crate) enum Lint {
+  EnvVarWithoutDefault,
+  SimilarAliases,
+  UnusedBackticks,
+  UnusedParameters,
+  UnusedPrivateRecipes,
+  UnusedVariables,
+}
+
+impl Lint {
+  pub(crate) const ALL: &'static [Lint] = &[
+    Self::EnvVarWithoutDefault,
+    Self::SimilarAliases,
+    Self::UnusedBackticks,
+    Self::UnusedParameters,
+    Self::UnusedPrivateRecipes,
+    Self::UnusedVariables,
+  ];
+
+  pub(crate) fn name(self) -> &'static str {
+    match self {
+      Self::EnvVarWithoutDefault => "env-var-without-default",
+      Self::SimilarAliases => "similar-aliases",
+      Self::UnusedBackticks => "unused-backticks",
+      Self::UnusedParameters => "unused-parameters",
+      Self::UnusedPrivateRecipes => "unused-private-recipes",
+      Self::UnusedVariables => "unused-variables",
+    }
+  }
+}
+
+impl FromStr for Lint {
+  type Err = ();
+
+  fn from_str(name: &str) -> Result<Self, ()> {
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|lint| lint.name() == name)
+      .ok_or(())
+  }
+}
+
+impl Display for Lint {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
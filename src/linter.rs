@@ -0,0 +1,488 @@
+use crate::common::*;
+
+/// Checks an analyzed justfile for likely mistakes, producing the warnings
+/// printed by `--lint`
+pub(crate) struct Linter<'src: 'run, 'run> {
+  justfile: &'run Justfile<'src>,
+  warnings: Vec<Warning<'src>>,
+}
+
+impl<'src: 'run, 'run> Linter<'src, 'run> {
+  /// Minimum length of an alias name for it to be checked for similarity
+  /// to other names, since short aliases are often one edit from each other
+  const SIMILAR_ALIAS_MIN_LENGTH: usize = 3;
+
+  /// Lint `justfile`, returning warnings in source order
+  pub(crate) fn lint(justfile: &'run Justfile<'src>) -> Vec<Warning<'src>> {
+    let mut linter = Self {
+      warnings: Vec::new(),
+      justfile,
+    };
+
+    linter.assignments();
+    linter.recipes();
+    linter.aliases();
+
+    linter
+      .warnings
+      .sort_by_key(|warning| warning.context().map(|token| token.offset));
+
+    linter.warnings
+  }
+
+  fn assignments(&mut self) {
+    let mut referenced = BTreeSet::new();
+    let mut reachable = BTreeSet::new();
+    let mut stack = Vec::new();
+
+    for recipe in self.justfile.recipes.values() {
+      // parameters may not shadow variables, so any of these names which
+      // aren't parameters are variables
+      for expression in Self::recipe_expressions(recipe) {
+        stack.extend(Self::variables(expression));
+      }
+    }
+
+    for assignment in self.justfile.assignments.values() {
+      if assignment.export {
+        stack.push(assignment.name.lexeme());
+      }
+
+      for name in Self::variables(&assignment.value) {
+        referenced.insert(name);
+      }
+    }
+
+    referenced.extend(stack.iter().copied());
+
+    while let Some(name) = stack.pop() {
+      if !reachable.insert(name) {
+        continue;
+      }
+
+      if let Some(assignment) = self.justfile.assignments.get(name) {
+        stack.extend(Self::variables(&assignment.value));
+      }
+    }
+
+    for assignment in self.justfile.assignments.values() {
+      let name = assignment.name;
+
+      if reachable.contains(name.lexeme()) {
+        continue;
+      }
+
+      let has_backtick = Self::expressions(&assignment.value)
+        .iter()
+        .any(|expression| match expression {
+          Expression::Backtick { .. } => true,
+          _ => false,
+        });
+
+      if has_backtick {
+        if !Self::allowed(&assignment.attributes, Lint::UnusedBackticks) {
+          self
+            .warnings
+            .push(Warning::UnusedBacktick { variable: name });
+        }
+      } else if !referenced.contains(name.lexeme())
+        && !Self::allowed(&assignment.attributes, Lint::UnusedVariables)
+      {
+        self
+          .warnings
+          .push(Warning::UnusedVariable { variable: name });
+      }
+    }
+  }
+
+  fn recipes(&mut self) {
+    let mut used = BTreeSet::new();
+
+    if let Some(first) = self.justfile.first() {
+      used.insert(first.name());
+    }
+
+    for recipe in self.justfile.recipes.values() {
+      for dependency in &recipe.dependencies {
+        used.insert(dependency.recipe.name());
+      }
+
+      if let Some((finally, _token)) = recipe.finally() {
+        used.insert(finally);
+      }
+    }
+
+    for alias in self.justfile.aliases.values() {
+      used.insert(alias.target.name());
+    }
+
+    for recipe in self.justfile.recipes.values() {
+      let attributes = &recipe.attributes;
+
+      if recipe.private
+        && !used.contains(recipe.name())
+        && !self.justfile.is_hook(recipe.name())
+        && !Self::allowed(attributes, Lint::UnusedPrivateRecipes)
+      {
+        self
+          .warnings
+          .push(Warning::UnusedPrivateRecipe { recipe: recipe.name });
+      }
+
+      let expressions = Self::recipe_expressions(recipe)
+        .into_iter()
+        .flat_map(Self::expressions)
+        .collect::<Vec<&Expression>>();
+
+      if !Self::allowed(attributes, Lint::UnusedParameters) {
+        for parameter in &recipe.parameters {
+          let referenced = expressions.iter().any(|expression| match expression {
+            Expression::Variable { name } => name.lexeme() == parameter.name.lexeme(),
+            _ => false,
+          });
+
+          if !referenced {
+            self.warnings.push(Warning::UnusedParameter {
+              recipe:    recipe.name(),
+              parameter: parameter.name,
+            });
+          }
+        }
+      }
+
+      if !Self::allowed(attributes, Lint::EnvVarWithoutDefault) {
+        for expression in &expressions {
+          if let Expression::Call {
+            thunk: Thunk::Unary { name, .. },
+          } = expression
+          {
+            if name.lexeme() == "env_var" {
+              self.warnings.push(Warning::EnvVarWithoutDefault {
+                recipe:   recipe.name(),
+                function: *name,
+              });
+            }
+          }
+        }
+      }
+    }
+  }
+
+  fn aliases(&mut self) {
+    for alias in self.justfile.aliases.values() {
+      let name = alias.name.lexeme();
+
+      if name.chars().count() < Self::SIMILAR_ALIAS_MIN_LENGTH
+        || Self::allowed(&alias.attributes, Lint::SimilarAliases)
+      {
+        continue;
+      }
+
+      let similar = self
+        .justfile
+        .recipes
+        .keys()
+        .chain(self.justfile.aliases.keys())
+        .copied()
+        .filter(|other| *other != name && *other != alias.target.name())
+        .find(|other| edit_distance(name, other) == 1);
+
+      if let Some(similar) = similar {
+        self.warnings.push(Warning::SimilarAlias {
+          alias: alias.name,
+          similar,
+        });
+      }
+    }
+  }
+
+  /// Whether `attributes` contain an `[allow]` attribute for `lint`
+  fn allowed(attributes: &[Attribute], lint: Lint) -> bool {
+    attributes.iter().any(|attribute| match attribute {
+      Attribute::Allow { lints } => lints.contains(&lint),
+      _ => false,
+    })
+  }
+
+  /// Expressions in the body, parameter defaults, and dependency arguments
+  /// of `recipe`
+  fn recipe_expressions(recipe: &'run Recipe<'src>) -> Vec<&'run Expression<'src>> {
+    let body = recipe
+      .body
+      .iter()
+      .flat_map(|line| &line.fragments)
+      .filter_map(|fragment| match fragment {
+        Fragment::Interpolation { expression } => Some(expression),
+        Fragment::Text { .. } => None,
+      });
+
+    let defaults = recipe
+      .parameters
+      .iter()
+      .filter_map(|parameter| parameter.default.as_ref());
+
+    let arguments = recipe
+      .dependencies
+      .iter()
+      .flat_map(|dependency| &dependency.arguments);
+
+    body.chain(defaults).chain(arguments).collect()
+  }
+
+  /// Names of the variables used in `expression`, including in function
+  /// arguments
+  fn variables(expression: &'run Expression<'src>) -> Vec<&'src str> {
+    Self::expressions(expression)
+      .into_iter()
+      .filter_map(|expression| match expression {
+        Expression::Variable { name } => Some(name.lexeme()),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// `expression` and all of its subexpressions
+  fn expressions(expression: &'run Expression<'src>) -> Vec<&'run Expression<'src>> {
+    let mut expressions = Vec::new();
+    let mut stack = vec![expression];
+
+    while let Some(expression) = stack.pop() {
+      expressions.push(expression);
+
+      match expression {
+        Expression::Call { thunk } => match thunk {
+          Thunk::Nullary { .. } => {},
+          Thunk::Unary { arg, .. } => stack.push(arg),
+          Thunk::Binary { args, .. } => stack.extend(args.iter().map(Box::as_ref)),
+        },
+        Expression::Concatination { lhs, rhs } => {
+          stack.push(lhs);
+          stack.push(rhs);
+        },
+        Expression::Group { contents } => stack.push(contents),
+        Expression::Backtick { .. }
+        | Expression::StringLiteral { .. }
+        | Expression::Variable { .. } => {},
+      }
+    }
+
+    expressions
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  macro_rules! test {
+    {
+      name: $name:ident,
+      justfile: $justfile:expr,
+      warnings: [$($warning:expr),* $(,)?],
+    } => {
+      #[test]
+      fn $name() {
+        let src = testing::unindent($justfile);
+        let justfile = testing::compile(&src);
+
+        let have = Linter::lint(&justfile)
+          .iter()
+          .map(Warning::message)
+          .collect::<Vec<String>>();
+
+        let want: &[&str] = &[$($warning),*];
+
+        assert_eq!(have, want);
+      }
+    }
+  }
+
+  test! {
+    name: clean,
+    justfile: "
+      target := 'debug'
+      export TOKEN := 'x'
+
+      build mode=target: (test mode)
+        cargo build --{{mode}}
+
+      alias b := build
+
+      test mode:
+        cargo test --{{mode}} {{env_var_or_default('ARGS', '')}}
+    ",
+    warnings: [],
+  }
+
+  test! {
+    name: unused_variable,
+    justfile: "
+      a := 'a'
+      b := a
+      c := 'c'
+
+      foo:
+        echo {{c}}
+    ",
+    warnings: ["Variable `b` is never used"],
+  }
+
+  test! {
+    name: variable_used_in_function_argument,
+    justfile: "
+      a := 'a'
+
+      foo:
+        echo {{env_var_or_default('A', a)}}
+    ",
+    warnings: [],
+  }
+
+  test! {
+    name: unused_backtick,
+    justfile: "
+      sha := `git rev-parse HEAD`
+      short := sha
+
+      foo:
+        echo foo
+    ",
+    warnings: [
+      "Variable `sha` contains a backtick that runs on every invocation, but no recipe uses it",
+      "Variable `short` is never used",
+    ],
+  }
+
+  test! {
+    name: backtick_used_by_recipe,
+    justfile: "
+      sha := `git rev-parse HEAD`
+      short := sha
+
+      foo:
+        echo {{short}}
+    ",
+    warnings: [],
+  }
+
+  test! {
+    name: unused_parameter,
+    justfile: "
+      a := 'a'
+
+      foo b c=a:
+        echo {{b}}
+    ",
+    warnings: ["Parameter `c` of recipe `foo` is never used"],
+  }
+
+  test! {
+    name: parameter_used_in_dependency_argument,
+    justfile: "
+      foo a: (bar a)
+
+      bar b:
+        echo {{b}}
+    ",
+    warnings: [],
+  }
+
+  test! {
+    name: unused_private_recipe,
+    justfile: "
+      foo: _bar
+
+      _bar:
+
+      _baz:
+
+      _qux:
+
+      [finally('_qux')]
+      quux:
+    ",
+    warnings: ["Private recipe `_baz` is never used"],
+  }
+
+  test! {
+    name: private_recipe_used_by_alias_or_hook,
+    justfile: "
+      set before := '_before'
+
+      foo:
+
+      _before:
+
+      _bar:
+
+      alias bar := _bar
+    ",
+    warnings: [],
+  }
+
+  test! {
+    name: env_var_without_default,
+    justfile: "
+      foo:
+        echo {{env_var('HOME')}}
+    ",
+    warnings: [
+      "Recipe `foo` calls `env_var`, which fails if the variable is not set. Consider using \
+       `env_var_or_default`",
+    ],
+  }
+
+  test! {
+    name: similar_alias,
+    justfile: "
+      build:
+
+      built:
+
+      alias buil := built
+      alias b := built
+    ",
+    warnings: [
+      "Alias `buil` is nearly identical to `build`, so a typo may run the wrong recipe",
+    ],
+  }
+
+  test! {
+    name: allow,
+    justfile: "
+      [allow('unused-variables')]
+      a := 'a'
+
+      [allow('unused-backticks')]
+      b := `echo b`
+
+      foo: (bar 'x')
+
+      [allow('unused-parameters', 'env-var-without-default')]
+      bar x:
+        echo {{env_var('HOME')}}
+
+      [allow('unused-private-recipes')]
+      _baz:
+
+      [allow('similar-aliases')]
+      alias fob := bar
+    ",
+    warnings: [],
+  }
+
+  test! {
+    name: source_order,
+    justfile: "
+      foo x:
+
+      a := 'a'
+    ",
+    warnings: [
+      "Parameter `x` of recipe `foo` is never used",
+      "Variable `a` is never used",
+    ],
+  }
+}
@@ -5,6 +5,12 @@ pub(crate) struct LoadError<'path> {
   pub(crate) io_error: io::Error,
 }
 
+impl LoadError<'_> {
+  /// A stable code identifying this error, used in diagnostics printed with
+  /// `--message-format json`
+  pub(crate) const DIAGNOSTIC_CODE: &'static str = "E2006";
+}
+
 impl Error for LoadError<'_> {}
 
 impl Display for LoadError<'_> {
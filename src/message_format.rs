@@ -0,0 +1,5 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum MessageFormat {
+  Human,
+  Json,
+}
@@ -28,21 +28,29 @@ impl<'src> Node<'src> for Item<'src> {
 
 impl<'src> Node<'src> for Alias<'src, Name<'src>> {
   fn tree(&self) -> Tree<'src> {
-    Tree::atom(keyword::ALIAS)
-      .push(self.name.lexeme())
-      .push(self.target.lexeme())
+    let mut tree = Tree::atom(keyword::ALIAS);
+
+    if !self.attributes.is_empty() {
+      tree.push_mut(Tree::atom("attributes").extend(self.attributes.iter().map(Node::tree)));
+    }
+
+    tree.push(self.name.lexeme()).push(self.target.lexeme())
   }
 }
 
 impl<'src> Node<'src> for Assignment<'src> {
   fn tree(&self) -> Tree<'src> {
-    if self.export {
+    let mut tree = if self.export {
       Tree::atom("assignment").push("#").push(keyword::EXPORT)
     } else {
       Tree::atom("assignment")
+    };
+
+    if !self.attributes.is_empty() {
+      tree.push_mut(Tree::atom("attributes").extend(self.attributes.iter().map(Node::tree)));
     }
-    .push(self.name.lexeme())
-    .push(self.value.tree())
+
+    tree.push(self.name.lexeme()).push(self.value.tree())
   }
 }
 
@@ -90,10 +98,14 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
       t.push_mut("quiet");
     }
 
-    if let Some(doc) = self.doc {
+    if let Some(doc) = &self.doc {
       t.push_mut(Tree::string(doc));
     }
 
+    if !self.attributes.is_empty() {
+      t.push_mut(Tree::atom("attributes").extend(self.attributes.iter().map(Node::tree)));
+    }
+
     t.push_mut(self.name.lexeme());
 
     if !self.parameters.is_empty() {
@@ -134,6 +146,69 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
   }
 }
 
+impl<'src> Node<'src> for Attribute<'src> {
+  fn tree(&self) -> Tree<'src> {
+    let tree = Tree::atom(self.name());
+
+    match self {
+      Attribute::Allow { lints } => {
+        let mut tree = tree;
+
+        for lint in lints {
+          tree.push_mut(Tree::string(lint.name()));
+        }
+
+        tree
+      },
+      Attribute::Choices {
+        parameter, choices, ..
+      } => {
+        let mut tree = tree.push(Tree::string(parameter));
+
+        for choice in choices {
+          tree.push_mut(Tree::string(&choice.cooked));
+        }
+
+        tree
+      },
+      Attribute::Doc { parameter, doc, .. } => match parameter {
+        Some(parameter) => tree
+          .push(Tree::string(parameter))
+          .push(Tree::string(&doc.cooked)),
+        None => tree.push(Tree::string(&doc.cooked)),
+      },
+      Attribute::Finally { recipe, .. } => tree.push(Tree::string(&recipe.cooked)),
+      Attribute::Group { group } => tree.push(Tree::string(&group.cooked)),
+      Attribute::Inputs { patterns } => {
+        let mut tree = tree;
+
+        for pattern in patterns {
+          tree.push_mut(Tree::string(&pattern.cooked));
+        }
+
+        tree
+      },
+      Attribute::Path { parameter, .. } => tree.push(Tree::string(parameter)),
+      Attribute::Retry(retry) => {
+        let mut tree = tree.push(Tree::string(retry.retries.to_string()));
+
+        if let Some(delay) = retry.delay {
+          tree.push_mut(Tree::atom(keyword::DELAY).push(Tree::string(delay.to_string())));
+        }
+
+        if retry.exponential {
+          tree.push_mut(
+            Tree::atom(keyword::BACKOFF).push(Tree::string(Retry::BACKOFF_EXPONENTIAL)),
+          );
+        }
+
+        tree
+      },
+      Attribute::Timeout { duration } => tree.push(Tree::string(duration.to_string())),
+    }
+  }
+}
+
 impl<'src> Node<'src> for Parameter<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut children = Vec::new();
@@ -170,6 +245,12 @@ impl<'src> Node<'src> for Set<'src> {
 
     use Setting::*;
     match &self.value {
+      After(setting::Hook { recipe, .. }) | Before(setting::Hook { recipe, .. }) => {
+        set.push_mut(Tree::string(recipe));
+      },
+      Fallback(value) => {
+        set.push_mut(value.to_string());
+      },
       Shell(setting::Shell { command, arguments }) => {
         set.push_mut(Tree::string(&command.cooked));
         for argument in arguments {
@@ -186,6 +267,13 @@ impl<'src> Node<'src> for Warning<'src> {
   fn tree(&self) -> Tree<'src> {
     match self {
       Warning::DeprecatedEquals { .. } => Tree::atom("warning").push("deprecated_equals"),
+      Warning::IgnoredError { .. } => Tree::atom("warning").push("ignored_error"),
+      Warning::EnvVarWithoutDefault { .. }
+      | Warning::SimilarAlias { .. }
+      | Warning::UnusedBacktick { .. }
+      | Warning::UnusedParameter { .. }
+      | Warning::UnusedPrivateRecipe { .. }
+      | Warning::UnusedVariable { .. } => unreachable!("lints are not produced by the parser"),
     }
   }
 }
@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use std::mem;
+
 use TokenKind::*;
 
 /// Just language parser
@@ -27,7 +29,23 @@ pub(crate) struct Parser<'tokens, 'src> {
 
 impl<'tokens, 'src> Parser<'tokens, 'src> {
   /// Parse `tokens` into an `Module`
-  pub(crate) fn parse(tokens: &'tokens [Token<'src>]) -> CompilationResult<'src, Module<'src>> {
+  pub(crate) fn parse(
+    tokens: &'tokens [Token<'src>],
+  ) -> Result<Module<'src>, Vec<CompilationError<'src>>> {
+    let (module, errors) = Self::parse_partial(tokens);
+
+    if errors.is_empty() {
+      Ok(module)
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Parse `tokens` into a `Module` containing the items that could be
+  /// parsed, along with errors for those that couldn't
+  pub(crate) fn parse_partial(
+    tokens: &'tokens [Token<'src>],
+  ) -> (Module<'src>, Vec<CompilationError<'src>>) {
     Self::new(tokens).parse_justfile()
   }
 
@@ -254,111 +272,296 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   }
 
   /// Parse a justfile, consumes self
-  fn parse_justfile(mut self) -> CompilationResult<'src, Module<'src>> {
+  ///
+  /// When an item fails to parse, the error is recorded and parsing resumes at
+  /// the start of the next top-level item, so that all errors can be reported
+  /// at once.
+  fn parse_justfile(mut self) -> (Module<'src>, Vec<CompilationError<'src>>) {
     let mut items = Vec::new();
     let mut warnings = Vec::new();
+    let mut errors = Vec::new();
 
     let mut doc = None;
 
+    let mut attributes: Vec<(Name<'src>, Attribute<'src>)> = Vec::new();
+
     loop {
-      let next = self.next()?;
+      let next = match self.next() {
+        Ok(next) => next,
+        Err(error) => {
+          errors.push(error);
+          return (Module { items, warnings }, errors);
+        },
+      };
+
+      if next.kind == Eof {
+        if let Err(error) = self.advance() {
+          errors.push(error);
+        }
+        break;
+      }
+
+      let start = self.next;
+
+      match self.parse_item(next, &mut doc, &mut attributes, &mut warnings) {
+        Ok(Some(item)) => items.push(item),
+        Ok(None) => {},
+        Err(error) => {
+          errors.push(error);
+          self.recover(start);
+          doc = None;
+          attributes.clear();
+          continue;
+        },
+      }
 
       match next.kind {
+        // consecutive comment lines form a single doc comment, skipping lines
+        // that start with `#!`, which are shebangs rather than documentation
+        Comment if next.lexeme().starts_with("#!") => {},
         Comment => {
-          doc = Some(next.lexeme()[1..].trim());
-          self.expect_eol()?;
-        },
-        Eol => {
-          self.advance()?;
-        },
-        Eof => {
-          self.advance()?;
-          break;
-        },
-        Identifier => match next.lexeme() {
-          keyword::ALIAS =>
-            if self.next_are(&[Identifier, Identifier, Equals]) {
-              warnings.push(Warning::DeprecatedEquals {
-                equals: self.get(2)?,
-              });
-              items.push(Item::Alias(self.parse_alias()?));
-            } else if self.next_are(&[Identifier, Identifier, ColonEquals]) {
-              items.push(Item::Alias(self.parse_alias()?));
-            } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
-            },
-          keyword::EXPORT =>
-            if self.next_are(&[Identifier, Identifier, Equals]) {
-              warnings.push(Warning::DeprecatedEquals {
-                equals: self.get(2)?,
-              });
-              self.presume_name(keyword::EXPORT)?;
-              items.push(Item::Assignment(self.parse_assignment(true)?));
-            } else if self.next_are(&[Identifier, Identifier, ColonEquals]) {
-              self.presume_name(keyword::EXPORT)?;
-              items.push(Item::Assignment(self.parse_assignment(true)?));
-            } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
-            },
-          keyword::SET =>
-            if self.next_are(&[Identifier, Identifier, ColonEquals]) {
-              items.push(Item::Set(self.parse_set()?));
-            } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
-            },
-          _ =>
-            if self.next_are(&[Identifier, Equals]) {
-              warnings.push(Warning::DeprecatedEquals {
-                equals: self.get(1)?,
-              });
-              items.push(Item::Assignment(self.parse_assignment(false)?));
-            } else if self.next_are(&[Identifier, ColonEquals]) {
-              items.push(Item::Assignment(self.parse_assignment(false)?));
-            } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
-            },
-        },
-        At => {
-          self.presume(At)?;
-          items.push(Item::Recipe(self.parse_recipe(doc, true)?));
-        },
-        _ => {
-          return Err(self.unexpected_token(&[Identifier, At])?);
+          let line = next.lexeme()[1..].trim();
+          doc = Some(match doc.take() {
+            Some(doc) => Cow::Owned(format!("{}\n{}", doc, line)),
+            None => Cow::Borrowed(line),
+          });
         },
+        BracketL => {},
+        _ => doc = None,
       }
 
-      if next.kind != Comment {
-        doc = None;
+      if next.kind != Comment && next.kind != BracketL && next.kind != Eol {
+        if let Err(error) = Self::check_attributes_consumed(&attributes) {
+          errors.push(error);
+          attributes.clear();
+        }
       }
     }
 
+    if let Err(error) = Self::check_attributes_consumed(&attributes) {
+      errors.push(error);
+    }
+
     if self.next != self.tokens.len() {
-      Err(self.internal_error(format!(
-        "Parse completed with {} unparsed tokens",
-        self.tokens.len() - self.next,
-      ))?)
-    } else {
-      Ok(Module { items, warnings })
+      errors.push(
+        self
+          .internal_error(format!(
+            "Parse completed with {} unparsed tokens",
+            self.tokens.len() - self.next,
+          ))
+          .unwrap_or_else(|error| error),
+      );
     }
+
+    (Module { items, warnings }, errors)
+  }
+
+  /// Parse the top-level item, comment, attribute, or blank line starting
+  /// with `next`
+  fn parse_item(
+    &mut self,
+    next: Token<'src>,
+    doc: &mut Option<Cow<'src, str>>,
+    attributes: &mut Vec<(Name<'src>, Attribute<'src>)>,
+    warnings: &mut Vec<Warning<'src>>,
+  ) -> CompilationResult<'src, Option<Item<'src>>> {
+    let item = match next.kind {
+      BracketL => {
+        let (name, attribute) = self.parse_attribute()?;
+
+        if let Some((first, _)) = attributes.iter().find(|(first, first_attribute)| {
+          first.lexeme() == name.lexeme()
+            && ((!attribute.repeatable() && !first_attribute.repeatable())
+              || *first_attribute == attribute)
+        }) {
+          return Err(name.error(CompilationErrorKind::DuplicateAttribute {
+            attribute: name.lexeme(),
+            first:     first.line,
+          }));
+        }
+
+        attributes.push((name, attribute));
+
+        None
+      },
+      Comment => {
+        self.expect_eol()?;
+        None
+      },
+      Eol => {
+        self.advance()?;
+        None
+      },
+      Identifier => match next.lexeme() {
+        keyword::ALIAS =>
+          if self.next_are(&[Identifier, Identifier, Equals]) {
+            warnings.push(Warning::DeprecatedEquals {
+              equals: self.get(2)?,
+            });
+            Some(Item::Alias(self.parse_alias(Self::item_attributes(attributes)?)?))
+          } else if self.next_are(&[Identifier, Identifier, ColonEquals]) {
+            Some(Item::Alias(self.parse_alias(Self::item_attributes(attributes)?)?))
+          } else {
+            Some(Item::Recipe(self.parse_recipe(doc.take(), false, mem::take(attributes))?))
+          },
+        keyword::EXPORT =>
+          if self.next_are(&[Identifier, Identifier, Equals]) {
+            warnings.push(Warning::DeprecatedEquals {
+              equals: self.get(2)?,
+            });
+            self.presume_name(keyword::EXPORT)?;
+            Some(Item::Assignment(self.parse_assignment(true, Self::item_attributes(attributes)?)?))
+          } else if self.next_are(&[Identifier, Identifier, ColonEquals]) {
+            self.presume_name(keyword::EXPORT)?;
+            Some(Item::Assignment(self.parse_assignment(true, Self::item_attributes(attributes)?)?))
+          } else {
+            Some(Item::Recipe(self.parse_recipe(doc.take(), false, mem::take(attributes))?))
+          },
+        keyword::SET =>
+          if self.next_are(&[Identifier, Identifier, ColonEquals])
+            || self.next_are(&[Identifier, Identifier, Eol])
+            || self.next_are(&[Identifier, Identifier, Eof])
+          {
+            Some(Item::Set(self.parse_set()?))
+          } else {
+            Some(Item::Recipe(self.parse_recipe(doc.take(), false, mem::take(attributes))?))
+          },
+        _ =>
+          if self.next_are(&[Identifier, Equals]) {
+            warnings.push(Warning::DeprecatedEquals {
+              equals: self.get(1)?,
+            });
+            Some(Item::Assignment(self.parse_assignment(false, Self::item_attributes(attributes)?)?))
+          } else if self.next_are(&[Identifier, ColonEquals]) {
+            Some(Item::Assignment(self.parse_assignment(false, Self::item_attributes(attributes)?)?))
+          } else {
+            Some(Item::Recipe(self.parse_recipe(doc.take(), false, mem::take(attributes))?))
+          },
+      },
+      At => {
+        self.presume(At)?;
+        Some(Item::Recipe(self.parse_recipe(doc.take(), true, mem::take(attributes))?))
+      },
+      _ => {
+        return Err(self.unexpected_token(&[Identifier, At, BracketL])?);
+      },
+    };
+
+    Ok(item)
+  }
+
+  /// Skip to the start of the next top-level item after an error in the item
+  /// which started at token index `start`
+  fn recover(&mut self, start: usize) {
+    let mut depth = self.tokens[..self.next]
+      .iter()
+      .fold(0, |depth, token| match token.kind {
+        Indent => depth + 1,
+        Dedent => depth - 1,
+        _ => depth,
+      });
+
+    let at_line_start = self.tokens[..self.next]
+      .iter()
+      .rev()
+      .find(|token| token.kind != Whitespace)
+      .map_or(true, |token| token.kind == Eol || token.kind == Dedent);
+
+    // the item may have been consumed in its entirety before the error was
+    // found, in which case there is nothing to skip
+    if self.next > start && depth == 0 && at_line_start && !self.next_is(Indent) {
+      return;
+    }
+
+    while let Some(token) = self.tokens.get(self.next) {
+      match token.kind {
+        Eof => return,
+        Indent => depth += 1,
+        Dedent => {
+          depth -= 1;
+          if depth == 0 {
+            self.next += 1;
+            return;
+          }
+        },
+        Eol if depth == 0 => {
+          self.next += 1;
+          if !self.next_is(Indent) {
+            return;
+          }
+          continue;
+        },
+        _ => {},
+      }
+
+      self.next += 1;
+    }
+  }
+
+  /// Return an error if attributes were not followed by a recipe
+  fn check_attributes_consumed(
+    attributes: &[(Name<'src>, Attribute<'src>)],
+  ) -> CompilationResult<'src, ()> {
+    if let Some((name, _)) = attributes.first() {
+      return Err(name.error(CompilationErrorKind::AttributeWithoutRecipe {
+        attribute: name.lexeme(),
+      }));
+    }
+
+    Ok(())
+  }
+
+  /// Take the attributes preceding an item that isn't a recipe, returning an
+  /// error if any of them may only be applied to recipes
+  fn item_attributes(
+    attributes: &mut Vec<(Name<'src>, Attribute<'src>)>,
+  ) -> CompilationResult<'src, Vec<Attribute<'src>>> {
+    if let Some((name, _)) = attributes
+      .iter()
+      .find(|(_name, attribute)| !attribute.applies_to_any_item())
+    {
+      return Err(name.error(CompilationErrorKind::AttributeWithoutRecipe {
+        attribute: name.lexeme(),
+      }));
+    }
+
+    Ok(
+      mem::take(attributes)
+        .into_iter()
+        .map(|(_name, attribute)| attribute)
+        .collect(),
+    )
   }
 
   /// Parse an alias, e.g `alias name := target`
-  fn parse_alias(&mut self) -> CompilationResult<'src, Alias<'src, Name<'src>>> {
+  fn parse_alias(
+    &mut self,
+    attributes: Vec<Attribute<'src>>,
+  ) -> CompilationResult<'src, Alias<'src, Name<'src>>> {
     self.presume_name(keyword::ALIAS)?;
     let name = self.parse_name()?;
     self.presume_any(&[Equals, ColonEquals])?;
     let target = self.parse_name()?;
     self.expect_eol()?;
-    Ok(Alias { name, target })
+    Ok(Alias {
+      attributes,
+      name,
+      target,
+    })
   }
 
   /// Parse an assignment, e.g. `foo := bar`
-  fn parse_assignment(&mut self, export: bool) -> CompilationResult<'src, Assignment<'src>> {
+  fn parse_assignment(
+    &mut self,
+    export: bool,
+    attributes: Vec<Attribute<'src>>,
+  ) -> CompilationResult<'src, Assignment<'src>> {
     let name = self.parse_name()?;
     self.presume_any(&[Equals, ColonEquals])?;
     let value = self.parse_expression()?;
     self.expect_eol()?;
     Ok(Assignment {
+      attributes,
       name,
       export,
       value,
@@ -486,8 +689,9 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   /// Parse a recipe
   fn parse_recipe(
     &mut self,
-    doc: Option<&'src str>,
+    doc: Option<Cow<'src, str>>,
     quiet: bool,
+    attributes: Vec<(Name<'src>, Attribute<'src>)>,
   ) -> CompilationResult<'src, UnresolvedRecipe<'src>> {
     let name = self.parse_name()?;
 
@@ -547,7 +751,24 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     let body = self.parse_body()?;
 
+    // a `[doc]` attribute overrides the doc comment
+    let doc = attributes
+      .iter()
+      .find_map(|(_name, attribute)| match attribute {
+        Attribute::Doc {
+          parameter: None,
+          doc,
+          ..
+        } => Some(doc.cooked.clone()),
+        _ => None,
+      })
+      .or(doc);
+
     Ok(Recipe {
+      attributes: attributes
+        .into_iter()
+        .map(|(_name, attribute)| attribute)
+        .collect(),
       private: name.lexeme().starts_with('_'),
       shebang: body.first().map(Line::is_shebang).unwrap_or(false),
       parameters: positional.into_iter().chain(variadic).collect(),
@@ -559,6 +780,291 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     })
   }
 
+  /// Parse a recipe attribute, e.g. `[timeout("10m")]`
+  fn parse_attribute(&mut self) -> CompilationResult<'src, (Name<'src>, Attribute<'src>)> {
+    self.presume(BracketL)?;
+
+    let name = self.parse_name()?;
+
+    let mut arguments = Vec::new();
+    let mut keyword_arguments: Vec<(Name<'src>, Token<'src>, StringLiteral<'src>)> = Vec::new();
+
+    if self.accepted(ParenL)? {
+      while !self.next_is(ParenR) {
+        if self.next_are(&[Identifier, Equals]) {
+          let keyword = self.parse_name()?;
+          self.presume(Equals)?;
+
+          if keyword_arguments
+            .iter()
+            .any(|(previous, _, _)| previous.lexeme() == keyword.lexeme())
+          {
+            return Err(keyword.error(CompilationErrorKind::DuplicateAttributeArgument {
+              attribute: name.lexeme(),
+              argument:  keyword.lexeme(),
+            }));
+          }
+
+          let token = self.next()?;
+          let string_literal = self.parse_string_literal()?;
+          keyword_arguments.push((keyword, token, string_literal));
+        } else if self.next_is(Integer) {
+          let token = self.presume(Integer)?;
+          arguments.push((token, StringLiteral {
+            raw:    token.lexeme(),
+            cooked: Cow::Borrowed(token.lexeme()),
+          }));
+        } else {
+          let token = self.next()?;
+          let string_literal = self
+            .parse_string_literal()
+            .expected(&[Integer, ParenR, Identifier])?;
+          arguments.push((token, string_literal));
+        }
+
+        if !self.accepted(Comma)? {
+          break;
+        }
+      }
+
+      self.expect(ParenR)?;
+    }
+
+    self.expect(BracketR).expected(if arguments.is_empty() && keyword_arguments.is_empty() {
+      &[ParenL]
+    } else {
+      &[]
+    })?;
+
+    self.expect_eol()?;
+
+    let attribute = match name.lexeme() {
+      keyword::ALLOW => {
+        if arguments.is_empty() {
+          return Err(
+            name.error(CompilationErrorKind::AttributeArgumentCountMismatch {
+              attribute: name.lexeme(),
+              found:     0,
+              expected:  1,
+            }),
+          );
+        }
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        let mut lints = Vec::new();
+
+        for (token, lint) in &arguments {
+          lints.push(lint.cooked.parse().map_err(|()| {
+            token.error(CompilationErrorKind::InvalidAttributeArgument {
+              attribute: name.lexeme(),
+              value:     lint.raw,
+              expected:  "the name of a lint",
+            })
+          })?);
+        }
+
+        Attribute::Allow { lints }
+      },
+      keyword::CHOICES => {
+        if arguments.len() < 2 {
+          return Err(
+            name.error(CompilationErrorKind::AttributeArgumentCountMismatch {
+              attribute: name.lexeme(),
+              found:     arguments.len(),
+              expected:  2,
+            }),
+          );
+        }
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        let mut arguments = arguments.into_iter();
+        let (token, parameter) = arguments.next().unwrap();
+
+        Attribute::Choices {
+          parameter: parameter.raw,
+          choices: arguments.map(|(_token, choice)| choice).collect(),
+          token,
+        }
+      },
+      keyword::DOC => {
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        match arguments.as_slice() {
+          [(token, doc)] => Attribute::Doc {
+            parameter: None,
+            token:     *token,
+            doc:       doc.clone(),
+          },
+          [(token, parameter), (_, doc)] => Attribute::Doc {
+            parameter: Some(parameter.raw),
+            token:     *token,
+            doc:       doc.clone(),
+          },
+          _ =>
+            return Err(
+              name.error(CompilationErrorKind::AttributeArgumentCountMismatch {
+                attribute: name.lexeme(),
+                found:     arguments.len(),
+                expected:  1,
+              }),
+            ),
+        }
+      },
+      keyword::FINALLY => {
+        let (token, recipe) = Self::single_attribute_argument(name, &arguments)?;
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        Attribute::Finally {
+          recipe: recipe.clone(),
+          token:  *token,
+        }
+      },
+      keyword::GROUP => {
+        let (_token, group) = Self::single_attribute_argument(name, &arguments)?;
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        Attribute::Group {
+          group: group.clone(),
+        }
+      },
+      keyword::INPUTS => {
+        if arguments.is_empty() {
+          return Err(
+            name.error(CompilationErrorKind::AttributeArgumentCountMismatch {
+              attribute: name.lexeme(),
+              found:     0,
+              expected:  1,
+            }),
+          );
+        }
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        Attribute::Inputs {
+          patterns: arguments
+            .into_iter()
+            .map(|(_token, pattern)| pattern)
+            .collect(),
+        }
+      },
+      keyword::PATH => {
+        let (token, parameter) = Self::single_attribute_argument(name, &arguments)?;
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        Attribute::Path {
+          parameter: parameter.raw,
+          token:     *token,
+        }
+      },
+      keyword::RETRY => {
+        let (token, retries) = Self::single_attribute_argument(name, &arguments)?;
+
+        let mut retry = Retry {
+          retries:     retries.cooked.parse().ok().ok_or_else(|| {
+            token.error(CompilationErrorKind::InvalidAttributeArgument {
+              attribute: name.lexeme(),
+              value:     retries.raw,
+              expected:  "a number of retries",
+            })
+          })?,
+          delay:       None,
+          exponential: false,
+        };
+
+        for (keyword, token, value) in &keyword_arguments {
+          match keyword.lexeme() {
+            keyword::DELAY =>
+              retry.delay = Some(value.cooked.parse().map_err(|()| {
+                token.error(CompilationErrorKind::InvalidDuration {
+                  duration: value.raw,
+                })
+              })?),
+            keyword::BACKOFF =>
+              retry.exponential = match value.cooked.as_ref() {
+                Retry::BACKOFF_CONSTANT => false,
+                Retry::BACKOFF_EXPONENTIAL => true,
+                _ =>
+                  return Err(token.error(CompilationErrorKind::InvalidAttributeArgument {
+                    attribute: name.lexeme(),
+                    value:     value.raw,
+                    expected:  "`constant` or `exponential` backoff",
+                  })),
+              },
+            _ => return Err(Self::unknown_attribute_argument(name, *keyword)),
+          }
+        }
+
+        Attribute::Retry(retry)
+      },
+      keyword::TIMEOUT => {
+        let (token, duration) = Self::single_attribute_argument(name, &arguments)?;
+
+        if let Some((keyword, _, _)) = keyword_arguments.first() {
+          return Err(Self::unknown_attribute_argument(name, *keyword));
+        }
+
+        Attribute::Timeout {
+          duration: duration.cooked.parse().map_err(|()| {
+            token.error(CompilationErrorKind::InvalidDuration {
+              duration: duration.raw,
+            })
+          })?,
+        }
+      },
+      _ =>
+        return Err(name.error(CompilationErrorKind::UnknownAttribute {
+          attribute: name.lexeme(),
+        })),
+    };
+
+    Ok((name, attribute))
+  }
+
+  /// Error for a keyword argument that an attribute does not accept
+  fn unknown_attribute_argument(name: Name<'src>, keyword: Name<'src>) -> CompilationError<'src> {
+    keyword.error(CompilationErrorKind::UnknownAttributeArgument {
+      attribute: name.lexeme(),
+      argument:  keyword.lexeme(),
+    })
+  }
+
+  /// Return the only argument to an attribute, or an error if the attribute
+  /// did not receive exactly one argument
+  fn single_attribute_argument<'arguments>(
+    name: Name<'src>,
+    arguments: &'arguments [(Token<'src>, StringLiteral<'src>)],
+  ) -> CompilationResult<'src, &'arguments (Token<'src>, StringLiteral<'src>)> {
+    match arguments {
+      [argument] => Ok(argument),
+      _ => Err(
+        name.error(CompilationErrorKind::AttributeArgumentCountMismatch {
+          attribute: name.lexeme(),
+          found:     arguments.len(),
+          expected:  1,
+        }),
+      ),
+    }
+  }
+
   /// Parse a recipe parameter
   fn parse_parameter(&mut self, variadic: bool) -> CompilationResult<'src, Parameter<'src>> {
     let name = self.parse_name()?;
@@ -620,8 +1126,34 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   fn parse_set(&mut self) -> CompilationResult<'src, Set<'src>> {
     self.presume_name(keyword::SET)?;
     let name = Name::from_identifier(self.presume(Identifier)?);
-    self.presume(ColonEquals)?;
+
+    if name.lexeme() == keyword::FALLBACK {
+      return Ok(Set {
+        value: Setting::Fallback(self.parse_set_bool()?),
+        name,
+      });
+    }
+
+    self.expect(ColonEquals)?;
+
     match name.lexeme() {
+      keyword::AFTER | keyword::BEFORE => {
+        let token = self.next()?;
+
+        let hook = setting::Hook {
+          recipe: self.parse_string_literal()?.raw,
+          token,
+        };
+
+        Ok(Set {
+          value: if name.lexeme() == keyword::AFTER {
+            Setting::After(hook)
+          } else {
+            Setting::Before(hook)
+          },
+          name,
+        })
+      },
       keyword::SHELL => {
         self.expect(BracketL)?;
 
@@ -658,6 +1190,24 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       })),
     }
   }
+
+  /// Parse the value of a boolean setting, which is `true` if omitted
+  fn parse_set_bool(&mut self) -> CompilationResult<'src, bool> {
+    if !self.accepted(ColonEquals)? {
+      return Ok(true);
+    }
+
+    let identifier = self.expect(Identifier)?;
+
+    match identifier.lexeme() {
+      keyword::TRUE => Ok(true),
+      keyword::FALSE => Ok(false),
+      found => Err(identifier.error(CompilationErrorKind::ExpectedKeyword {
+        expected: vec![keyword::TRUE, keyword::FALSE],
+        found,
+      })),
+    }
+  }
 }
 
 #[cfg(test)]
@@ -726,7 +1276,9 @@ mod tests {
 
     match Parser::parse(&tokens) {
       Ok(_) => panic!("Parsing unexpectedly succeeded"),
-      Err(have) => {
+      Err(errors) => {
+        assert_eq!(errors.len(), 1, "Expected a single error: {:?}", errors);
+        let have = &errors[0];
         let want = CompilationError {
           token: Token {
             kind: have.token.kind,
@@ -738,7 +1290,7 @@ mod tests {
           },
           kind,
         };
-        assert_eq!(have, want);
+        assert_eq!(*have, want);
       },
     }
   }
@@ -1059,6 +1611,47 @@ mod tests {
     tree: (justfile (recipe bar) (recipe "foo" baz)),
   }
 
+  test! {
+    name: doc_comment_multiple_lines,
+    text: "
+      # foo
+      #
+      # bar
+      baz:
+    ",
+    tree: (justfile (recipe "foo\n\nbar" baz)),
+  }
+
+  test! {
+    name: doc_comment_multiple_lines_empty_line_clear,
+    text: "
+      # foo
+
+      # bar
+      baz:
+    ",
+    tree: (justfile (recipe "bar" baz)),
+  }
+
+  test! {
+    name: doc_comment_shebang,
+    text: "
+      #!notdoc
+      bar:
+    ",
+    tree: (justfile (recipe bar)),
+  }
+
+  test! {
+    name: doc_comment_shebang_skipped,
+    text: "
+      # foo
+      #!notdoc
+      bar:
+    ",
+    tree: (justfile (recipe "foo" bar)),
+  }
+
   test! {
     name: doc_comment_assignment_clear,
     text: "
@@ -1079,6 +1672,174 @@ mod tests {
     tree: (justfile (recipe bar)),
   }
 
+  test! {
+    name: recipe_attribute_timeout,
+    text: r#"
+      [timeout("10m")]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (timeout "10m")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_timeout_canonical,
+    text: r#"
+      [timeout('600s')]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (timeout "10m")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_doc_comment,
+    text: r#"
+      # bar
+      [timeout("1h")]
+      foo:
+    "#,
+    tree: (justfile (recipe "bar" (attributes (timeout "1h")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_quiet,
+    text: r#"
+      [timeout("30s")] # comment
+      @foo:
+    "#,
+    tree: (justfile (recipe #quiet (attributes (timeout "30s")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_retry,
+    text: r#"
+      [retry("3")]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (retry "3")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_retry_integer,
+    text: r#"
+      [retry(3)]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (retry "3")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_retry_integer_keyword_arguments,
+    text: r#"
+      [retry(3, delay="2s")]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (retry "3" (delay "2s"))) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_retry_keyword_arguments,
+    text: r#"
+      [retry("3", delay="2s", backoff="exponential",)]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (retry "3" (delay "2s") (backoff "exponential"))) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_retry_constant_backoff,
+    text: r#"
+      [retry("1", backoff="constant", delay="120s")]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (retry "1" (delay "2m"))) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_finally,
+    text: r#"
+      [finally('teardown')]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (finally "teardown")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_doc,
+    text: r#"
+      # bar
+      [doc('baz')]
+      [doc('target', 'where to build')]
+      foo target:
+    "#,
+    tree: (justfile (recipe "baz" (attributes (doc "baz") (doc "target" "where to build")) foo (params (target)))),
+  }
+
+  test! {
+    name: recipe_attribute_completions,
+    text: r#"
+      [choices('target', "staging", 'production')]
+      [path('file')]
+      foo target file:
+    "#,
+    tree: (justfile (recipe (attributes (choices "target" "staging" "production") (path "file")) foo (params (target) (file)))),
+  }
+
+  test! {
+    name: recipe_attribute_groups,
+    text: r#"
+      [group('deploy')]
+      [group("release")]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (group "deploy") (group "release")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_inputs,
+    text: r#"
+      [inputs('src/**/*.rs', "Cargo.toml",)]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (inputs "src/**/*.rs" "Cargo.toml")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_allow,
+    text: r#"
+      [allow("unused-parameters", 'env-var-without-default')]
+      foo bar:
+    "#,
+    tree: (justfile (recipe (attributes (allow "unused-parameters" "env-var-without-default")) foo (params (bar)))),
+  }
+
+  test! {
+    name: assignment_attribute_allow,
+    text: r#"
+      [allow("unused-variables")]
+      export x := 'y'
+    "#,
+    tree: (justfile (assignment #export (attributes (allow "unused-variables")) x "y")),
+  }
+
+  test! {
+    name: alias_attribute_allow,
+    text: r#"
+      [allow("similar-aliases")]
+      alias x := y
+    "#,
+    tree: (justfile (alias (attributes (allow "similar-aliases")) x y)),
+  }
+
+  test! {
+    name: recipe_attributes_multiple,
+    text: r#"
+      [retry("2")]
+      [timeout("1m")]
+      foo:
+    "#,
+    tree: (justfile (recipe (attributes (retry "2") (timeout "1m")) foo)),
+  }
+
   test! {
     name: string_escape_tab,
     text: r#"x := "foo\tbar""#,
@@ -1473,6 +2234,36 @@ mod tests {
     tree: (justfile (recipe a (body ("foo"))) (recipe b)),
   }
 
+  test! {
+    name: set_before,
+    text: "set before := 'banner'",
+    tree: (justfile (set before "banner")),
+  }
+
+  test! {
+    name: set_after,
+    text: "set after := \"report\"",
+    tree: (justfile (set after "report")),
+  }
+
+  test! {
+    name: set_fallback_implicit,
+    text: "set fallback",
+    tree: (justfile (set fallback true)),
+  }
+
+  test! {
+    name: set_fallback_true,
+    text: "set fallback := true",
+    tree: (justfile (set fallback true)),
+  }
+
+  test! {
+    name: set_fallback_false,
+    text: "set fallback := false",
+    tree: (justfile (set fallback false)),
+  }
+
   test! {
     name: set_shell_no_arguments,
     text: "set shell := ['tclsh']",
@@ -1531,7 +2322,7 @@ mod tests {
 
   error! {
     name:   missing_colon,
-    input:  "a b c\nd e f",
+    input:  "a b c\nd e:",
     offset:  5,
     line:   0,
     column: 5,
@@ -1582,7 +2373,7 @@ mod tests {
     line:   0,
     column: 0,
     width:  2,
-    kind:   UnexpectedToken{expected: vec![At, Identifier], found: InterpolationStart},
+    kind:   UnexpectedToken{expected: vec![At, BracketL, Identifier], found: InterpolationStart},
   }
 
   error! {
@@ -1760,6 +2551,32 @@ mod tests {
     },
   }
 
+  error! {
+    name:   set_fallback_non_boolean,
+    input:  "set fallback := yes",
+    offset: 16,
+    line:   0,
+    column: 16,
+    width:  3,
+    kind:   ExpectedKeyword {
+      expected: vec!["true", "false"],
+      found:    "yes",
+    },
+  }
+
+  error! {
+    name:   set_shell_missing_value,
+    input:  "set shell",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  0,
+    kind:   UnexpectedToken {
+      expected: vec![ColonEquals],
+      found: Eof,
+    },
+  }
+
   error! {
     name:   unknown_function,
     input:  "a = foo()",
@@ -1831,4 +2648,318 @@ mod tests {
       expected: 2,
     },
   }
+
+  error! {
+    name: unknown_attribute,
+    input: "[foo]\nbar:",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 3,
+    kind: UnknownAttribute {
+      attribute: "foo",
+    },
+  }
+
+  error! {
+    name: duplicate_attribute,
+    input: "[timeout('1s')]\n[timeout('2s')]\nbar:",
+    offset: 17,
+    line: 1,
+    column: 1,
+    width: 7,
+    kind: DuplicateAttribute {
+      attribute: "timeout",
+      first: 0,
+    },
+  }
+
+  error! {
+    name: duplicate_group_attribute,
+    input: "[group('a')]\n[group('b')]\n[group('a')]\nbar:",
+    offset: 27,
+    line: 2,
+    column: 1,
+    width: 5,
+    kind: DuplicateAttribute {
+      attribute: "group",
+      first: 0,
+    },
+  }
+
+  error! {
+    name: attribute_argument_count,
+    input: "[timeout]\nbar:",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 7,
+    kind: AttributeArgumentCountMismatch {
+      attribute: "timeout",
+      found: 0,
+      expected: 1,
+    },
+  }
+
+  error! {
+    name: duplicate_attribute_doc,
+    input: "[doc('a', 'b')]\n[doc('c')]\n[doc('d')]\nbar a:",
+    offset: 28,
+    line: 2,
+    column: 1,
+    width: 3,
+    kind: DuplicateAttribute {
+      attribute: "doc",
+      first: 1,
+    },
+  }
+
+  error! {
+    name: attribute_doc_argument_count,
+    input: "[doc('a', 'b', 'c')]\nbar:",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 3,
+    kind: AttributeArgumentCountMismatch {
+      attribute: "doc",
+      found: 3,
+      expected: 1,
+    },
+  }
+
+  error! {
+    name: attribute_invalid_duration,
+    input: "[timeout('10')]\nbar:",
+    offset: 9,
+    line: 0,
+    column: 9,
+    width: 4,
+    kind: InvalidDuration {
+      duration: "10",
+    },
+  }
+
+  error! {
+    name: attribute_retry_invalid_count,
+    input: "[retry('x')]\nbar:",
+    offset: 7,
+    line: 0,
+    column: 7,
+    width: 3,
+    kind: InvalidAttributeArgument {
+      attribute: "retry",
+      value: "x",
+      expected: "a number of retries",
+    },
+  }
+
+  error! {
+    name: attribute_retry_invalid_backoff,
+    input: "[retry('1', backoff='linear')]\nbar:",
+    offset: 20,
+    line: 0,
+    column: 20,
+    width: 8,
+    kind: InvalidAttributeArgument {
+      attribute: "retry",
+      value: "linear",
+      expected: "`constant` or `exponential` backoff",
+    },
+  }
+
+  error! {
+    name: attribute_retry_invalid_delay,
+    input: "[retry('1', delay='soon')]\nbar:",
+    offset: 18,
+    line: 0,
+    column: 18,
+    width: 6,
+    kind: InvalidDuration {
+      duration: "soon",
+    },
+  }
+
+  error! {
+    name: attribute_unknown_argument,
+    input: "[retry('1', wait='1s')]\nbar:",
+    offset: 12,
+    line: 0,
+    column: 12,
+    width: 4,
+    kind: UnknownAttributeArgument {
+      attribute: "retry",
+      argument: "wait",
+    },
+  }
+
+  error! {
+    name: attribute_timeout_keyword_argument,
+    input: "[timeout('1s', delay='1s')]\nbar:",
+    offset: 15,
+    line: 0,
+    column: 15,
+    width: 5,
+    kind: UnknownAttributeArgument {
+      attribute: "timeout",
+      argument: "delay",
+    },
+  }
+
+  error! {
+    name: attribute_duplicate_argument,
+    input: "[retry('1', delay='1s', delay='2s')]\nbar:",
+    offset: 24,
+    line: 0,
+    column: 24,
+    width: 5,
+    kind: DuplicateAttributeArgument {
+      attribute: "retry",
+      argument: "delay",
+    },
+  }
+
+  error! {
+    name: attribute_inputs_without_arguments,
+    input: "[inputs]\nbar:",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 6,
+    kind: AttributeArgumentCountMismatch {
+      attribute: "inputs",
+      found: 0,
+      expected: 1,
+    },
+  }
+
+  error! {
+    name: attribute_without_recipe_assignment,
+    input: "[timeout('10s')]\nx := 'y'",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 7,
+    kind: AttributeWithoutRecipe {
+      attribute: "timeout",
+    },
+  }
+
+  error! {
+    name: attribute_without_recipe_eof,
+    input: "foo:\n[timeout('10s')]",
+    offset: 6,
+    line: 1,
+    column: 1,
+    width: 7,
+    kind: AttributeWithoutRecipe {
+      attribute: "timeout",
+    },
+  }
+
+  error! {
+    name: attribute_without_recipe_set,
+    input: "[allow('unused-variables')]\nset shell := ['sh']",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 5,
+    kind: AttributeWithoutRecipe {
+      attribute: "allow",
+    },
+  }
+
+  error! {
+    name: attribute_allow_unknown_lint,
+    input: "[allow('unused-things')]\nfoo:",
+    offset: 7,
+    line: 0,
+    column: 7,
+    width: 15,
+    kind: InvalidAttributeArgument {
+      attribute: "allow",
+      value:     "unused-things",
+      expected:  "the name of a lint",
+    },
+  }
+
+  error! {
+    name: attribute_allow_without_arguments,
+    input: "[allow]\nfoo:",
+    offset: 1,
+    line: 0,
+    column: 1,
+    width: 5,
+    kind: AttributeArgumentCountMismatch {
+      attribute: "allow",
+      found:     0,
+      expected:  1,
+    },
+  }
+
+  error! {
+    name: attribute_unterminated,
+    input: "[timeout('10s')\nfoo:",
+    offset: 15,
+    line: 0,
+    column: 15,
+    width: 1,
+    kind: UnexpectedToken {
+      expected: vec![BracketR],
+      found: Eol,
+    },
+  }
+
+  fn error_lines(src: &str) -> Vec<usize> {
+    let src = unindent(src);
+    let tokens = Lexer::lex(&src).expect("Lexing failed in parse test...");
+    Parser::parse(&tokens)
+      .expect_err("Parsing unexpectedly succeeded")
+      .iter()
+      .map(|error| error.token.line)
+      .collect()
+  }
+
+  #[test]
+  fn recover_at_next_item() {
+    assert_eq!(
+      error_lines(
+        "
+          a: (b +)
+          c := )
+          d:
+            echo {{ ) }}
+            echo ok
+          e f g )
+          h:
+        "
+      ),
+      &[0, 1, 3, 5]
+    );
+  }
+
+  #[test]
+  fn recover_after_attribute() {
+    assert_eq!(
+      error_lines(
+        "
+          [retry('2')]
+          [retry('3')]
+          a:
+          [foo]
+          b:
+          c := )
+        "
+      ),
+      &[1, 3, 5]
+    );
+  }
+
+  #[test]
+  fn errors_are_not_bounded() {
+    assert_eq!(
+      error_lines(&")\n".repeat(Compiler::MAX_ERRORS * 2)).len(),
+      Compiler::MAX_ERRORS * 2
+    );
+  }
 }
@@ -0,0 +1,258 @@
+use crate::common::*;
+
+use unicode_width::UnicodeWidthStr;
+
+/// The recipes that running a justfile would run, in order, printed by
+/// `--plan`. Dependencies are indented under the recipe that depends on them,
+/// and are listed before it, since they run first.
+pub(crate) struct Plan<'src> {
+  steps: Vec<Step<'src>>,
+}
+
+struct Step<'src> {
+  /// Number of dependency edges between this step and a requested recipe or
+  /// hook
+  depth:      usize,
+  recipe:     &'src str,
+  /// Parameter names and evaluated values
+  parameters: Vec<(&'src str, String)>,
+  reason:     Reason<'src>,
+  /// Whether the step is skipped because an identical invocation already ran
+  skipped:    bool,
+}
+
+/// Why a recipe runs
+enum Reason<'src> {
+  After,
+  Before,
+  Dependency { parent: &'src str },
+  Finally { parent: &'src str },
+  Requested,
+}
+
+/// Walks recipes in the same order as `Justfile::run`, recording steps
+/// instead of running recipes
+struct Planner<'src: 'run, 'run> {
+  context:  &'run RecipeContext<'src, 'run>,
+  dotenv:   &'run BTreeMap<String, String>,
+  justfile: &'run Justfile<'src>,
+  ran:      BTreeSet<Vec<String>>,
+  search:   &'run Search,
+  steps:    Vec<Step<'src>>,
+}
+
+impl<'src> Plan<'src> {
+  pub(crate) fn new<'run>(
+    justfile: &'run Justfile<'src>,
+    context: &'run RecipeContext<'src, 'run>,
+    before: Option<&Recipe<'src>>,
+    grouped: Vec<(&Recipe<'src>, &[&str])>,
+    after: Option<&Recipe<'src>>,
+    dotenv: &'run BTreeMap<String, String>,
+    search: &'run Search,
+  ) -> RunResult<'src, Self> {
+    let mut planner = Planner {
+      ran: BTreeSet::new(),
+      steps: Vec::new(),
+      context,
+      dotenv,
+      justfile,
+      search,
+    };
+
+    if let Some(before) = before {
+      planner.recipe(before, &[], 0, Reason::Before)?;
+    }
+
+    for (recipe, arguments) in grouped {
+      planner.recipe(recipe, arguments, 0, Reason::Requested)?;
+    }
+
+    if let Some(after) = after {
+      planner.recipe(after, &[], 0, Reason::After)?;
+    }
+
+    Ok(Self {
+      steps: planner.steps,
+    })
+  }
+}
+
+impl<'src: 'run, 'run> Planner<'src, 'run> {
+  /// Plan `recipe`, its dependencies, and its `[finally]` recipe, mirroring
+  /// `Justfile::run_recipe`
+  fn recipe(
+    &mut self,
+    recipe: &Recipe<'src>,
+    arguments: &[&str],
+    depth: usize,
+    reason: Reason<'src>,
+  ) -> RunResult<'src, ()> {
+    self.recipe_and_dependencies(recipe, arguments, depth, reason)?;
+
+    if let Some((name, _token)) = recipe.finally() {
+      let finally = self
+        .justfile
+        .recipes
+        .get(name)
+        .ok_or_else(|| RuntimeError::Internal {
+          message: format!("unknown finally recipe `{}`", name),
+        })?;
+
+      self.recipe(finally, &[], depth, Reason::Finally {
+        parent: recipe.name(),
+      })?;
+    }
+
+    Ok(())
+  }
+
+  /// Plan `recipe` and its dependencies, mirroring
+  /// `Justfile::run_recipe_and_dependencies`
+  fn recipe_and_dependencies(
+    &mut self,
+    recipe: &Recipe<'src>,
+    arguments: &[&str],
+    depth: usize,
+    reason: Reason<'src>,
+  ) -> RunResult<'src, ()> {
+    let scope = Evaluator::evaluate_parameters(
+      self.context.config,
+      self.dotenv,
+      &recipe.parameters,
+      arguments,
+      &self.context.scope,
+      self.context.settings,
+      self.search,
+    )?;
+
+    let mut evaluator = Evaluator::recipe_evaluator(
+      self.context.config,
+      self.dotenv,
+      &scope,
+      self.context.settings,
+      self.search,
+    );
+
+    for dependency in &recipe.dependencies {
+      let mut invocation = vec![dependency.recipe.name().to_owned()];
+
+      for argument in &dependency.arguments {
+        invocation.push(evaluator.evaluate_expression(argument)?);
+      }
+
+      let arguments = invocation
+        .iter()
+        .skip(1)
+        .map(String::as_ref)
+        .collect::<Vec<&str>>();
+
+      let reason = Reason::Dependency {
+        parent: recipe.name(),
+      };
+
+      if self.ran.contains(&invocation) {
+        let parameters = self.parameters(&dependency.recipe, &arguments)?;
+        self.steps.push(Step {
+          depth: depth + 1,
+          recipe: dependency.recipe.name(),
+          skipped: true,
+          parameters,
+          reason,
+        });
+      } else {
+        self.recipe(&dependency.recipe, &arguments, depth + 1, reason)?;
+      }
+    }
+
+    self.steps.push(Step {
+      parameters: Self::values(recipe, &scope),
+      recipe: recipe.name(),
+      skipped: false,
+      depth,
+      reason,
+    });
+
+    let mut invocation = vec![recipe.name().to_owned()];
+    invocation.extend(arguments.iter().map(|argument| (*argument).to_owned()));
+    self.ran.insert(invocation);
+
+    Ok(())
+  }
+
+  /// Evaluated parameters of `recipe` when invoked with `arguments`
+  fn parameters(
+    &self,
+    recipe: &Recipe<'src>,
+    arguments: &[&str],
+  ) -> RunResult<'src, Vec<(&'src str, String)>> {
+    let scope = Evaluator::evaluate_parameters(
+      self.context.config,
+      self.dotenv,
+      &recipe.parameters,
+      arguments,
+      &self.context.scope,
+      self.context.settings,
+      self.search,
+    )?;
+
+    Ok(Self::values(recipe, &scope))
+  }
+
+  fn values(recipe: &Recipe<'src>, scope: &Scope) -> Vec<(&'src str, String)> {
+    recipe
+      .parameters
+      .iter()
+      .map(|parameter| {
+        let name = parameter.name.lexeme();
+        (name, scope.value(name).unwrap_or_default().to_owned())
+      })
+      .collect()
+  }
+}
+
+impl<'src> Display for Plan<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let invocations = self
+      .steps
+      .iter()
+      .map(|step| {
+        let mut invocation = format!("{}{}", "  ".repeat(step.depth), step.recipe);
+
+        for (name, value) in &step.parameters {
+          invocation += &format!(" {}={:?}", name, value);
+        }
+
+        invocation
+      })
+      .collect::<Vec<String>>();
+
+    let width = invocations
+      .iter()
+      .map(|invocation| UnicodeWidthStr::width(invocation.as_str()))
+      .max()
+      .unwrap_or(0);
+
+    for (step, invocation) in self.steps.iter().zip(invocations) {
+      let padding = width - UnicodeWidthStr::width(invocation.as_str());
+
+      write!(f, "{}{}  # ", invocation, " ".repeat(padding))?;
+
+      match step.reason {
+        Reason::After => write!(f, "`after` hook")?,
+        Reason::Before => write!(f, "`before` hook")?,
+        Reason::Dependency { parent } => write!(f, "dependency of `{}`", parent)?,
+        Reason::Finally { parent } => write!(f, "`finally` recipe of `{}`", parent)?,
+        Reason::Requested => write!(f, "requested")?,
+      }
+
+      if step.skipped {
+        write!(f, ", skipped because it already ran")?;
+      }
+
+      writeln!(f)?;
+    }
+
+    Ok(())
+  }
+}
@@ -2,6 +2,38 @@ use crate::common::*;
 
 pub(crate) struct Platform;
 
+#[cfg(unix)]
+impl Platform {
+  /// Make `process_group` the foreground process group of the terminal on
+  /// standard input. `SIGTTOU`, which is sent when a background process
+  /// changes the foreground process group, is blocked while doing so. Only
+  /// async-signal-safe functions are called, so this may be called between
+  /// `fork` and `exec`.
+  fn set_foreground(process_group: libc::pid_t) -> Result<(), io::Error> {
+    // SAFETY: the signal sets are initialized by `sigemptyset` before use, and
+    // the remaining calls have no memory safety requirements
+    unsafe {
+      let mut ttou = std::mem::zeroed();
+      libc::sigemptyset(&mut ttou);
+      libc::sigaddset(&mut ttou, libc::SIGTTOU);
+
+      let mut mask = std::mem::zeroed();
+      libc::pthread_sigmask(libc::SIG_BLOCK, &ttou, &mut mask);
+
+      let result = libc::tcsetpgrp(libc::STDIN_FILENO, process_group);
+      let error = io::Error::last_os_error();
+
+      libc::pthread_sigmask(libc::SIG_SETMASK, &mask, std::ptr::null_mut());
+
+      if result == 0 {
+        Ok(())
+      } else {
+        Err(error)
+      }
+    }
+  }
+}
+
 #[cfg(unix)]
 impl PlatformInterface for Platform {
   fn make_shebang_command(
@@ -18,6 +50,34 @@ impl PlatformInterface for Platform {
     Ok(cmd)
   }
 
+  fn new_process_group(command: &mut Command) -> bool {
+    use std::os::unix::process::CommandExt;
+
+    command.process_group(0);
+
+    // SAFETY: these calls have no memory safety requirements
+    let foreground = unsafe {
+      libc::isatty(libc::STDIN_FILENO) == 1
+        && libc::tcgetpgrp(libc::STDIN_FILENO) == libc::getpgrp()
+    };
+
+    if foreground {
+      // SAFETY: the closure only calls async-signal-safe functions. It runs
+      // after the child has been moved into its own process group, so
+      // `getpgrp` returns the new process group.
+      unsafe {
+        command.pre_exec(|| Self::set_foreground(libc::getpgrp()));
+      }
+    }
+
+    foreground
+  }
+
+  fn restore_foreground() -> Result<(), io::Error> {
+    // SAFETY: `getpgrp` has no memory safety requirements
+    Self::set_foreground(unsafe { libc::getpgrp() })
+  }
+
   fn set_execute_permission(path: &Path) -> Result<(), io::Error> {
     use std::os::unix::fs::PermissionsExt;
 
@@ -37,6 +97,23 @@ impl PlatformInterface for Platform {
     exit_status.signal()
   }
 
+  fn terminate_process_group(child: &mut process::Child, force: bool) -> Result<(), io::Error> {
+    use std::convert::TryFrom;
+
+    let pid = libc::pid_t::try_from(child.id())
+      .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+
+    // SAFETY: `kill` has no memory safety requirements, and since `child` has
+    // not been waited on, its process group ID cannot have been reused
+    if unsafe { libc::kill(-pid, signal) } == 0 {
+      Ok(())
+    } else {
+      Err(io::Error::last_os_error())
+    }
+  }
+
   fn to_shell_path(_working_directory: &Path, path: &Path) -> Result<String, String> {
     path
       .to_str()
@@ -70,6 +147,16 @@ impl PlatformInterface for Platform {
     Ok(cmd)
   }
 
+  fn new_process_group(_command: &mut Command) -> bool {
+    // Process groups are not used on windows, where terminating a child does
+    // not terminate its descendants
+    false
+  }
+
+  fn restore_foreground() -> Result<(), io::Error> {
+    Ok(())
+  }
+
   fn set_execute_permission(_path: &Path) -> Result<(), io::Error> {
     // it is not necessary to set an execute permission on a script on windows, so
     // this is a nop
@@ -82,6 +169,12 @@ impl PlatformInterface for Platform {
     None
   }
 
+  fn terminate_process_group(child: &mut process::Child, _force: bool) -> Result<(), io::Error> {
+    // There is no equivalent of SIGTERM on windows, so the child is killed
+    // immediately
+    child.kill()
+  }
+
   fn to_shell_path(working_directory: &Path, path: &Path) -> Result<String, String> {
     // Translate path from windows style to unix style
     let mut cygpath = Command::new("cygpath");
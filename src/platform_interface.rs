@@ -10,6 +10,18 @@ pub(crate) trait PlatformInterface {
     argument: Option<&str>,
   ) -> Result<Command, OutputError>;
 
+  /// Make the process spawned by `command` the leader of a new process group,
+  /// so that it and its descendants can be signaled together. If `just` is in
+  /// the foreground of the terminal on standard input, the new process group
+  /// takes its place, so that it can read from the terminal and receives
+  /// `^C`, and `true` is returned. `restore_foreground` must then be called
+  /// once the process has exited.
+  fn new_process_group(command: &mut Command) -> bool;
+
+  /// Put the process group of `just` back in the foreground of the terminal on
+  /// standard input
+  fn restore_foreground() -> Result<(), io::Error>;
+
   /// Set the execute permission on the file pointed to by `path`
   fn set_execute_permission(path: &Path) -> Result<(), io::Error>;
 
@@ -17,6 +29,10 @@ pub(crate) trait PlatformInterface {
   /// signal
   fn signal_from_exit_status(exit_status: process::ExitStatus) -> Option<i32>;
 
+  /// Ask the process group led by `child` to terminate if `force` is false, or
+  /// kill it if `force` is true
+  fn terminate_process_group(child: &mut process::Child, force: bool) -> Result<(), io::Error>;
+
   /// Translate a path from a "native" path to a path the interpreter expects
   fn to_shell_path(working_directory: &Path, path: &Path) -> Result<String, String>;
 }
@@ -1,6 +1,9 @@
 use crate::common::*;
 
-use std::process::{ExitStatus, Stdio};
+use std::{
+  process::{ExitStatus, Stdio},
+  thread,
+};
 
 /// Return a `RuntimeError::Signal` if the process was terminated by a signal,
 /// otherwise return an `RuntimeError::UnknownFailure`
@@ -25,8 +28,9 @@ fn error_from_signal(
 /// A recipe, e.g. `foo: bar baz`
 #[derive(PartialEq, Debug)]
 pub(crate) struct Recipe<'src, D = Dependency<'src>> {
+  pub(crate) attributes:   Vec<Attribute<'src>>,
   pub(crate) dependencies: Vec<D>,
-  pub(crate) doc:          Option<&'src str>,
+  pub(crate) doc:          Option<Cow<'src, str>>,
   pub(crate) body:         Vec<Line<'src>>,
   pub(crate) name:         Name<'src>,
   pub(crate) parameters:   Vec<Parameter<'src>>,
@@ -64,6 +68,137 @@ impl<'src, D> Recipe<'src, D> {
     self.name.line
   }
 
+  pub(crate) fn timeout(&self) -> Option<Duration> {
+    self.attributes.iter().find_map(|attribute| match attribute {
+      Attribute::Timeout { duration } => Some(duration.0),
+      _ => None,
+    })
+  }
+
+  pub(crate) fn finally(&self) -> Option<(&str, Token<'src>)> {
+    self.attributes.iter().find_map(|attribute| match attribute {
+      Attribute::Finally { recipe, token } => Some((recipe.cooked.as_ref(), *token)),
+      _ => None,
+    })
+  }
+
+  /// Values to complete `parameter` with, from `[choices]` attributes
+  pub(crate) fn choices<'a>(&'a self, parameter: &'a str) -> impl Iterator<Item = &'a str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(move |attribute| match attribute {
+        Attribute::Choices {
+          parameter: name,
+          choices,
+          ..
+        } if *name == parameter => Some(choices),
+        _ => None,
+      })
+      .flatten()
+      .map(|choice| choice.cooked.as_ref())
+  }
+
+  /// The first line of the recipe's documentation, shown by `--list`
+  pub(crate) fn doc_summary(&self) -> Option<&str> {
+    self.doc.as_deref().and_then(|doc| doc.lines().next())
+  }
+
+  /// Documentation for `parameter`, from a `[doc]` attribute
+  pub(crate) fn parameter_doc(&self, parameter: &str) -> Option<&str> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Doc {
+          parameter: Some(name),
+          doc,
+          ..
+        } if *name == parameter => Some(doc.cooked.as_ref()),
+        _ => None,
+      })
+  }
+
+  /// Whether `parameter` should be completed with file paths, because of a
+  /// `[path]` attribute
+  pub(crate) fn is_path(&self, parameter: &str) -> bool {
+    self.attributes.iter().any(|attribute| match attribute {
+      Attribute::Path { parameter: name, .. } => *name == parameter,
+      _ => false,
+    })
+  }
+
+  /// Groups from `[group]` attributes, in order
+  pub(crate) fn groups(&self) -> impl Iterator<Item = &str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Group { group } => Some(group.cooked.as_ref()),
+        _ => None,
+      })
+  }
+
+  pub(crate) fn inputs(&self) -> impl Iterator<Item = &str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Inputs { patterns } => Some(patterns),
+        _ => None,
+      })
+      .flatten()
+      .map(|pattern| pattern.cooked.as_ref())
+  }
+
+  pub(crate) fn retry(&self) -> Option<&Retry> {
+    self.attributes.iter().find_map(|attribute| match attribute {
+      Attribute::Retry(retry) => Some(retry),
+      _ => None,
+    })
+  }
+
+  /// If failed attempt number `attempt` should be retried, wait out the retry
+  /// delay and return `true`. Interrupts are never retried.
+  fn retry_after_failure(
+    &self,
+    config: &Config,
+    line_number: Option<usize>,
+    code: i32,
+    attempt: u32,
+  ) -> RunResult<'src, bool> {
+    let retry = match self.retry() {
+      Some(retry) if attempt < retry.attempts() => retry,
+      _ => return Ok(false),
+    };
+
+    InterruptHandler::check(self.name())?;
+
+    let delay = retry.delay_after(attempt);
+
+    if config.verbosity.loquacious() {
+      let color = config.color.stderr().banner();
+      let location = line_number
+        .map(|line_number| format!(" on line {}", line_number))
+        .unwrap_or_default();
+      config.sinks.eprintln(format_args!(
+        "{}===> Recipe `{}` failed{} with exit code {}, retrying in {} (attempt {} of {})...{}",
+        color.prefix(),
+        self.name,
+        location,
+        code,
+        HumanDuration(delay),
+        attempt + 1,
+        retry.attempts(),
+        color.suffix()
+      ));
+    }
+
+    thread::sleep(delay);
+
+    Ok(true)
+  }
+
   pub(crate) fn run<'run>(
     &self,
     context: &RecipeContext<'src, 'run>,
@@ -75,17 +210,21 @@ impl<'src, D> Recipe<'src, D> {
 
     if config.verbosity.loquacious() {
       let color = config.color.stderr().banner();
-      eprintln!(
+      config.sinks.eprintln(format_args!(
         "{}===> Running recipe `{}`...{}",
         color.prefix(),
         self.name,
         color.suffix()
-      );
+      ));
     }
 
     let mut evaluator =
       Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
 
+    let timeout = self.timeout().or(config.timeout);
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
     if self.shebang {
       let mut evaluated_lines = vec![];
       for line in &self.body {
@@ -94,7 +233,7 @@ impl<'src, D> Recipe<'src, D> {
 
       if config.dry_run || self.quiet {
         for line in &evaluated_lines {
-          eprintln!("{}", line);
+          config.sinks.eprintln(line);
         }
       }
 
@@ -131,7 +270,9 @@ impl<'src, D> Recipe<'src, D> {
         }
 
         if config.verbosity.grandiloquent() {
-          eprintln!("{}", config.color.doc().stderr().paint(&text));
+          config
+            .sinks
+            .eprintln(config.color.doc().stderr().paint(&text));
         }
 
         f.write_all(text.as_bytes())
@@ -175,28 +316,48 @@ impl<'src, D> Recipe<'src, D> {
       command.export(dotenv, &scope);
 
       // run it!
-      match InterruptHandler::guard(|| command.status()) {
-        Ok(exit_status) =>
-          if let Some(code) = exit_status.code() {
-            if code != 0 {
-              return Err(RuntimeError::Code {
-                recipe: self.name(),
-                line_number: None,
-                code,
-              });
-            }
-          } else {
-            return Err(error_from_signal(self.name(), None, exit_status));
+      let mut attempt = 1;
+      loop {
+        InterruptHandler::check(self.name())?;
+
+        match InterruptHandler::guard(|| command.status_until(deadline, &config.sinks)) {
+          Ok(Some(exit_status)) =>
+            if let Some(code) = exit_status.code() {
+              if code != 0 {
+                if self.retry_after_failure(config, None, code, attempt)? {
+                  attempt += 1;
+                  continue;
+                }
+
+                return Err(RuntimeError::Code {
+                  recipe: self.name(),
+                  line_number: None,
+                  code,
+                  attempts: attempt,
+                });
+              }
+            } else {
+              return Err(error_from_signal(self.name(), None, exit_status));
+            },
+          Ok(None) => {
+            return Err(RuntimeError::Timeout {
+              recipe:      self.name(),
+              line_number: None,
+              timeout:     timeout.unwrap_or_default(),
+            });
           },
-        Err(io_error) => {
-          return Err(RuntimeError::Shebang {
-            recipe: self.name(),
-            command: interpreter.to_string(),
-            argument: argument.map(String::from),
-            io_error,
-          });
-        },
-      };
+          Err(io_error) => {
+            return Err(RuntimeError::Shebang {
+              recipe: self.name(),
+              command: interpreter.to_string(),
+              argument: argument.map(String::from),
+              io_error,
+            });
+          },
+        };
+
+        break;
+      }
     } else {
       let mut lines = self.body.iter().peekable();
       let mut line_number = self.line_number() + 1;
@@ -204,6 +365,7 @@ impl<'src, D> Recipe<'src, D> {
         if lines.peek().is_none() {
           break;
         }
+        let infallible_command = lines.peek().map_or(false, |line| line.is_infallible());
         let mut evaluated = String::new();
         loop {
           if lines.peek().is_none() {
@@ -219,8 +381,16 @@ impl<'src, D> Recipe<'src, D> {
           }
         }
         let mut command = evaluated.as_str();
-        let quiet_command = command.starts_with('@');
-        if quiet_command {
+        let mut quiet_command = false;
+        let mut infallible_prefix = infallible_command;
+        loop {
+          if !quiet_command && command.starts_with('@') {
+            quiet_command = true;
+          } else if infallible_prefix && command.starts_with('-') {
+            infallible_prefix = false;
+          } else {
+            break;
+          }
           command = &command[1..];
         }
 
@@ -237,7 +407,7 @@ impl<'src, D> Recipe<'src, D> {
           } else {
             config.color
           };
-          eprintln!("{}", color.stderr().paint(command));
+          config.sinks.eprintln(color.stderr().paint(command));
         }
 
         if config.dry_run {
@@ -250,37 +420,85 @@ impl<'src, D> Recipe<'src, D> {
 
         cmd.arg(command);
 
-        if config.quiet {
+        // output discarded because of `--quiet` isn't forwarded to sinks
+        let sinks = if config.quiet {
           cmd.stderr(Stdio::null());
           cmd.stdout(Stdio::null());
-        }
+          Sinks::default()
+        } else {
+          config.sinks.clone()
+        };
 
         cmd.export(dotenv, &scope);
 
-        match InterruptHandler::guard(|| cmd.status()) {
-          Ok(exit_status) =>
-            if let Some(code) = exit_status.code() {
-              if code != 0 {
-                return Err(RuntimeError::Code {
-                  recipe: self.name(),
-                  line_number: Some(line_number),
-                  code,
-                });
-              }
-            } else {
-              return Err(error_from_signal(
-                self.name(),
-                Some(line_number),
-                exit_status,
-              ));
+        let mut attempt = 1;
+        loop {
+          InterruptHandler::check(self.name())?;
+
+          match InterruptHandler::guard(|| cmd.status_until(deadline, &sinks)) {
+            Ok(Some(exit_status)) =>
+              if let Some(code) = exit_status.code() {
+                if code != 0 {
+                  if self.retry_after_failure(config, Some(line_number), code, attempt)? {
+                    attempt += 1;
+                    continue;
+                  }
+
+                  if infallible_command {
+                    if !config.quiet {
+                      let warning = Warning::IgnoredError {
+                        recipe: self.name(),
+                        line_number,
+                        code,
+                      };
+
+                      match config.message_format {
+                        MessageFormat::Human =>
+                          if config.color.stderr().active() {
+                            config.sinks.eprintln(format_args!("{:#}", warning));
+                          } else {
+                            config.sinks.eprintln(warning);
+                          },
+                        MessageFormat::Json => config
+                          .sinks
+                          .eprintln(Diagnostic::warning(&context.search.justfile, &warning)),
+                      }
+                    }
+
+                    break;
+                  }
+
+                  return Err(RuntimeError::Code {
+                    recipe: self.name(),
+                    line_number: Some(line_number),
+                    code,
+                    attempts: attempt,
+                  });
+                }
+              } else {
+                return Err(error_from_signal(
+                  self.name(),
+                  Some(line_number),
+                  exit_status,
+                ));
+              },
+            Ok(None) => {
+              return Err(RuntimeError::Timeout {
+                recipe:      self.name(),
+                line_number: Some(line_number),
+                timeout:     timeout.unwrap_or_default(),
+              });
             },
-          Err(io_error) => {
-            return Err(RuntimeError::IoError {
-              recipe: self.name(),
-              io_error,
-            });
-          },
-        };
+            Err(io_error) => {
+              return Err(RuntimeError::IoError {
+                recipe: self.name(),
+                io_error,
+              });
+            },
+          };
+
+          break;
+        }
       }
     }
     Ok(())
@@ -293,10 +511,26 @@ impl<'src, D> Keyed<'src> for Recipe<'src, D> {
   }
 }
 
-impl<'src> Display for Recipe<'src> {
+impl<'src, D: Display> Display for Recipe<'src, D> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    if let Some(doc) = self.doc {
-      writeln!(f, "# {}", doc)?;
+    // docs from a `[doc]` attribute are printed with the other attributes
+    let documented = self.attributes.iter().any(|attribute| match attribute {
+      Attribute::Doc { parameter, .. } => parameter.is_none(),
+      _ => false,
+    });
+
+    if let (Some(doc), false) = (&self.doc, documented) {
+      for line in doc.lines() {
+        if line.is_empty() {
+          writeln!(f, "#")?;
+        } else {
+          writeln!(f, "# {}", line)?;
+        }
+      }
+    }
+
+    for attribute in &self.attributes {
+      writeln!(f, "{}", attribute)?;
     }
 
     if self.quiet {
@@ -317,14 +551,8 @@ impl<'src> Display for Recipe<'src> {
       if i == 0 {
         writeln!(f)?;
       }
-      for (j, fragment) in line.fragments.iter().enumerate() {
-        if j == 0 {
-          write!(f, "    ")?;
-        }
-        match fragment {
-          Fragment::Text { token } => write!(f, "{}", token.lexeme())?,
-          Fragment::Interpolation { expression, .. } => write!(f, "{{{{{}}}}}", expression)?,
-        }
+      if !line.is_empty() {
+        write!(f, "    {}", line)?;
       }
       if i + 1 < self.body.len() {
         writeln!(f)?;
@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use std::mem;
+
 use CompilationErrorKind::*;
 
 pub(crate) struct RecipeResolver<'src: 'run, 'run> {
@@ -9,10 +11,14 @@ pub(crate) struct RecipeResolver<'src: 'run, 'run> {
 }
 
 impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
+  /// Resolve `unresolved_recipes`, reporting problems in `errors`. Unknown
+  /// and circular dependencies are dropped, so that every recipe is resolved
+  /// and can be checked further.
   pub(crate) fn resolve_recipes(
     unresolved_recipes: Table<'src, UnresolvedRecipe<'src>>,
     assignments: &Table<'src, Assignment<'src>>,
-  ) -> CompilationResult<'src, Table<'src, Rc<Recipe<'src>>>> {
+    errors: &mut Vec<CompilationError<'src>>,
+  ) -> Table<'src, Rc<Recipe<'src>>> {
     let mut resolver = RecipeResolver {
       resolved_recipes: empty(),
       unresolved_recipes,
@@ -20,14 +26,14 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     };
 
     while let Some(unresolved) = resolver.unresolved_recipes.pop() {
-      resolver.resolve_recipe(&mut Vec::new(), unresolved)?;
+      resolver.resolve_recipe(&mut Vec::new(), unresolved, errors);
     }
 
     for recipe in resolver.resolved_recipes.values() {
       for parameter in &recipe.parameters {
         if let Some(expression) = &parameter.default {
           for variable in expression.variables() {
-            resolver.resolve_variable(&variable, &[])?;
+            errors.extend(resolver.resolve_variable(&variable, &[]).err());
           }
         }
       }
@@ -35,7 +41,11 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
       for dependency in &recipe.dependencies {
         for argument in &dependency.arguments {
           for variable in argument.variables() {
-            resolver.resolve_variable(&variable, &recipe.parameters)?;
+            errors.extend(
+              resolver
+                .resolve_variable(&variable, &recipe.parameters)
+                .err(),
+            );
           }
         }
       }
@@ -44,14 +54,75 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
         for fragment in &line.fragments {
           if let Fragment::Interpolation { expression, .. } = fragment {
             for variable in expression.variables() {
-              resolver.resolve_variable(&variable, &recipe.parameters)?;
+              errors.extend(
+                resolver
+                  .resolve_variable(&variable, &recipe.parameters)
+                  .err(),
+              );
             }
           }
         }
       }
     }
 
-    Ok(resolver.resolved_recipes)
+    // recipes in a circle that has already been reported are skipped
+    let mut circular = BTreeSet::new();
+
+    for recipe in resolver.resolved_recipes.values() {
+      if circular.contains(recipe.name()) {
+        continue;
+      }
+
+      if let Err(error) = resolver.resolve_finally(recipe) {
+        if let CircularFinallyRecipe { circle, .. } = &error.kind {
+          circular.extend(circle.iter().cloned());
+        }
+
+        errors.push(error);
+      }
+    }
+
+    resolver.resolved_recipes
+  }
+
+  /// Check that the chain of finally recipes starting at `recipe` only
+  /// contains known recipes that take no arguments, and does not loop
+  fn resolve_finally(&self, recipe: &Recipe<'src>) -> CompilationResult<'src, ()> {
+    let mut stack = vec![recipe.name()];
+    let mut current = recipe;
+
+    while let Some((name, token)) = current.finally() {
+      let finally = if let Some(finally) = self.resolved_recipes.get(name) {
+        finally
+      } else {
+        return Err(token.error(UnknownFinallyRecipe {
+          recipe:  current.name(),
+          unknown: name.to_owned(),
+        }));
+      };
+
+      let name = finally.name();
+
+      if finally.min_arguments() > 0 {
+        return Err(token.error(FinallyRecipeRequiresArguments {
+          recipe:        name,
+          min_arguments: finally.min_arguments(),
+        }));
+      }
+
+      if stack.contains(&name) {
+        stack.push(name);
+        return Err(token.error(CircularFinallyRecipe {
+          recipe: current.name(),
+          circle: stack.into_iter().skip_while(|item| *item != name).collect(),
+        }));
+      }
+
+      stack.push(name);
+      current = finally;
+    }
+
+    Ok(())
   }
 
   fn resolve_variable(
@@ -73,50 +144,55 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
   fn resolve_recipe(
     &mut self,
     stack: &mut Vec<&'src str>,
-    recipe: UnresolvedRecipe<'src>,
-  ) -> CompilationResult<'src, Rc<Recipe<'src>>> {
+    mut recipe: UnresolvedRecipe<'src>,
+    errors: &mut Vec<CompilationError<'src>>,
+  ) -> Rc<Recipe<'src>> {
     if let Some(resolved) = self.resolved_recipes.get(recipe.name()) {
-      return Ok(Rc::clone(resolved));
+      return Rc::clone(resolved);
     }
 
     stack.push(recipe.name());
 
+    let mut unresolved_dependencies = Vec::new();
     let mut dependencies: Vec<Rc<Recipe>> = Vec::new();
-    for dependency in &recipe.dependencies {
+    for dependency in mem::take(&mut recipe.dependencies) {
       let name = dependency.recipe.lexeme();
 
       if let Some(resolved) = self.resolved_recipes.get(name) {
         // dependency already resolved
         dependencies.push(Rc::clone(&resolved));
       } else if stack.contains(&name) {
-        let first = stack[0];
-        stack.push(first);
-        return Err(
-          dependency.recipe.error(CircularRecipeDependency {
-            recipe: recipe.name(),
-            circle: stack
-              .iter()
-              .skip_while(|name| **name != dependency.recipe.lexeme())
-              .cloned()
-              .collect(),
-          }),
-        );
+        errors.push(dependency.recipe.error(CircularRecipeDependency {
+          recipe: recipe.name(),
+          circle: stack
+            .iter()
+            .skip_while(|item| **item != name)
+            .cloned()
+            .chain(iter::once(name))
+            .collect(),
+        }));
+        continue;
       } else if let Some(unresolved) = self.unresolved_recipes.remove(name) {
         // resolve unresolved dependency
-        dependencies.push(self.resolve_recipe(stack, unresolved)?);
+        dependencies.push(self.resolve_recipe(stack, unresolved, errors));
       } else {
         // dependency is unknown
-        return Err(dependency.recipe.error(UnknownDependency {
+        errors.push(dependency.recipe.error(UnknownDependency {
           recipe:  recipe.name(),
           unknown: name,
         }));
+        continue;
       }
+
+      unresolved_dependencies.push(dependency);
     }
 
-    let resolved = Rc::new(recipe.resolve(dependencies)?);
+    recipe.dependencies = unresolved_dependencies;
+
+    let resolved = Rc::new(recipe.resolve(dependencies, errors));
     self.resolved_recipes.insert(Rc::clone(&resolved));
     stack.pop();
-    Ok(resolved)
+    resolved
   }
 }
 
@@ -154,6 +230,56 @@ mod tests {
     kind:   UnknownDependency{recipe: "a", unknown: "b"},
   }
 
+  analysis_error! {
+    name:   unknown_finally_recipe,
+    input:  "[finally('b')]\na:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  3,
+    kind:   UnknownFinallyRecipe{recipe: "a", unknown: "b".to_owned()},
+  }
+
+  analysis_error! {
+    name:   unknown_finally_recipe_cooked,
+    input:  "[finally(\"b\\tc\")]\na:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  6,
+    kind:   UnknownFinallyRecipe{recipe: "a", unknown: "b\tc".to_owned()},
+  }
+
+  analysis_error! {
+    name:   finally_recipe_requires_arguments,
+    input:  "[finally('b')]\na:\nb x:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  3,
+    kind:   FinallyRecipeRequiresArguments{recipe: "b", min_arguments: 1},
+  }
+
+  analysis_error! {
+    name:   self_finally_recipe,
+    input:  "[finally('a')]\na:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  3,
+    kind:   CircularFinallyRecipe{recipe: "a", circle: vec!["a", "a"]},
+  }
+
+  analysis_error! {
+    name:   circular_finally_recipe,
+    input:  "[finally('b')]\na:\n[finally('a')]\nb:",
+    offset: 27,
+    line:   2,
+    column: 9,
+    width:  3,
+    kind:   CircularFinallyRecipe{recipe: "b", circle: vec!["a", "b", "a"]},
+  }
+
   analysis_error! {
     name:   unknown_interpolation_variable,
     input:  "x:\n {{   hello}}",
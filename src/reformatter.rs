@@ -0,0 +1,446 @@
+use crate::common::*;
+
+use TokenKind::*;
+
+/// A top-level line of a justfile, as seen by the `Reformatter`
+#[derive(Debug, PartialEq)]
+enum Chunk<'src> {
+  /// One or more attributes, which are printed with the following item
+  Attributes { comment: Option<&'src str> },
+  /// A blank line
+  Blank,
+  /// A comment on a line by itself
+  Comment(&'src str),
+  /// The first line of an item, with an optional trailing comment
+  Item { comment: Option<&'src str> },
+}
+
+/// Produces the canonical form of a justfile, preserving comments, blank lines
+/// between items, and source order
+pub(crate) struct Reformatter<'src> {
+  chunks: Vec<Chunk<'src>>,
+  items:  Vec<Item<'src>>,
+}
+
+impl<'src> Reformatter<'src> {
+  pub(crate) fn reformat(src: &'src str) -> Result<String, Vec<CompilationError<'src>>> {
+    let tokens = Lexer::lex(src).map_err(|error| vec![error])?;
+
+    let module = Parser::parse(&tokens)?;
+
+    let reformatter = Self {
+      chunks: Self::chunks(&tokens),
+      items:  module.items,
+    };
+
+    Ok(reformatter.output())
+  }
+
+  /// Classify the top-level lines of `tokens`
+  fn chunks(tokens: &[Token<'src>]) -> Vec<Chunk<'src>> {
+    let mut chunks = Vec::new();
+    let mut depth = 0;
+    let mut line_start = true;
+    let mut body_blank = false;
+
+    for token in tokens {
+      match token.kind {
+        Whitespace => continue,
+        Indent => {
+          depth += 1;
+          continue;
+        },
+        Dedent => {
+          depth -= 1;
+          if depth == 0 && body_blank {
+            chunks.push(Chunk::Blank);
+          }
+          line_start = true;
+          continue;
+        },
+        _ => {},
+      }
+
+      if depth > 0 {
+        if token.kind == Eol {
+          body_blank = line_start;
+          line_start = true;
+        } else {
+          line_start = false;
+        }
+        continue;
+      }
+
+      match token.kind {
+        Eof => break,
+        Eol => {
+          if line_start {
+            chunks.push(Chunk::Blank);
+          }
+          line_start = true;
+          body_blank = false;
+          continue;
+        },
+        Comment if !line_start => {
+          if let Some(Chunk::Attributes { comment }) | Some(Chunk::Item { comment }) =
+            chunks.last_mut()
+          {
+            *comment = Some(token.lexeme());
+          }
+        },
+        _ if !line_start => {},
+        Comment => chunks.push(Chunk::Comment(token.lexeme())),
+        BracketL => chunks.push(Chunk::Attributes { comment: None }),
+        _ => chunks.push(Chunk::Item { comment: None }),
+      }
+
+      line_start = false;
+    }
+
+    chunks
+  }
+
+  fn output(self) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut items = self.items.into_iter();
+    let mut blank = false;
+
+    for chunk in self.chunks {
+      match chunk {
+        Chunk::Blank => {
+          blank = !lines.is_empty();
+          continue;
+        },
+        // attributes are printed with the item that they belong to, so
+        // comments on the same line are moved before them, and any blank line
+        // is left to be printed before the item
+        Chunk::Attributes { comment } => match comment {
+          Some(comment) => {
+            Self::push_blank(&mut lines, &mut blank);
+            lines.push(comment.trim_end().to_owned());
+          },
+          None => continue,
+        },
+        Chunk::Comment(comment) => {
+          Self::push_blank(&mut lines, &mut blank);
+          lines.push(comment.trim_end().to_owned());
+        },
+        Chunk::Item { comment } => {
+          let item = match items.next() {
+            Some(item) => item,
+            None => break,
+          };
+
+          Self::push_blank(&mut lines, &mut blank);
+
+          let (mut item_lines, header) = Self::item(item);
+
+          if let Some(comment) = comment {
+            item_lines[header].push(' ');
+            item_lines[header].push_str(comment.trim_end());
+          }
+
+          lines.extend(item_lines);
+        },
+      }
+
+      blank = false;
+    }
+
+    let mut output = lines.join("\n");
+
+    if !output.is_empty() {
+      output.push('\n');
+    }
+
+    output
+  }
+
+  fn push_blank(lines: &mut Vec<String>, blank: &mut bool) {
+    if *blank {
+      lines.push(String::new());
+      *blank = false;
+    }
+  }
+
+  /// Format `item`, returning its lines and the index of the line which
+  /// contains its name
+  fn item(item: Item<'src>) -> (Vec<String>, usize) {
+    match item {
+      Item::Alias(alias) => (
+        alias.to_string().lines().map(str::to_owned).collect(),
+        alias.attributes.len(),
+      ),
+      Item::Assignment(assignment) => {
+        let export = if assignment.export { "export " } else { "" };
+        let mut lines = assignment
+          .attributes
+          .iter()
+          .map(Attribute::to_string)
+          .collect::<Vec<String>>();
+        lines.push(format!(
+          "{}{} := {}",
+          export,
+          assignment.name.lexeme(),
+          assignment.value
+        ));
+        (lines, assignment.attributes.len())
+      },
+      Item::Recipe(mut recipe) => {
+        // doc comments are printed as ordinary comments
+        recipe.doc = None;
+
+        let mut lines = recipe
+          .to_string()
+          .lines()
+          .map(str::to_owned)
+          .collect::<Vec<String>>();
+
+        while lines.last().map(String::is_empty).unwrap_or(false) {
+          lines.pop();
+        }
+
+        (lines, recipe.attributes.len())
+      },
+      Item::Set(set) => (vec![set.to_string()], 0),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+  use testing::unindent;
+
+  macro_rules! test {
+    {
+      name:     $name:ident,
+      input:    $input:expr,
+      expected: $expected:expr,
+    } => {
+      #[test]
+      fn $name() {
+        let input = unindent($input);
+        let expected = unindent($expected);
+
+        let formatted = Reformatter::reformat(&input).unwrap();
+        assert_eq!(formatted, expected, "first pass");
+
+        let reformatted = Reformatter::reformat(&formatted).unwrap();
+        assert_eq!(reformatted, expected, "second pass");
+      }
+    }
+  }
+
+  test! {
+    name:     empty,
+    input:    "",
+    expected: "",
+  }
+
+  test! {
+    name:     source_order,
+    input:    "
+      z:
+      a := 'b'
+      alias y := z
+    ",
+    expected: "
+      z:
+      a := 'b'
+      alias y := z
+    ",
+  }
+
+  test! {
+    name:     deprecated_equals,
+    input:    "
+      a = 'b'
+      export c = 'd'
+      alias e = f
+      f:
+    ",
+    expected: "
+      a := 'b'
+      export c := 'd'
+      alias e := f
+      f:
+    ",
+  }
+
+  test! {
+    name:     comments,
+    input:    "
+      # leading comment
+
+      # doc comment
+      foo: # trailing comment
+        echo foo
+
+      x := 'y'   # trailing comment
+
+      # final comment
+    ",
+    expected: "
+      # leading comment
+
+      # doc comment
+      foo: # trailing comment
+          echo foo
+
+      x := 'y' # trailing comment
+
+      # final comment
+    ",
+  }
+
+  test! {
+    name:     blank_lines,
+    input:    "
+
+
+      a:
+        echo a
+
+
+        echo b
+
+
+
+      b:
+        echo c
+
+
+    ",
+    expected: "
+      a:
+          echo a
+
+
+          echo b
+
+      b:
+          echo c
+    ",
+  }
+
+  test! {
+    name:     whitespace,
+    input:    "
+      a   :=    'b' +    c
+      foo   bar='baz'   +rest:    a   (b   bar)
+        echo {{ bar }}
+    ",
+    expected: "
+      a := 'b' + c
+      foo bar='baz' +rest: a (b bar)
+          echo {{bar}}
+    ",
+  }
+
+  test! {
+    name:     shebang,
+    input:    "
+      foo:
+       #!/usr/bin/env sh
+       if true; then
+         echo foo
+       fi
+    ",
+    expected: "
+      foo:
+          #!/usr/bin/env sh
+          if true; then
+            echo foo
+          fi
+    ",
+  }
+
+  test! {
+    name:     attributes,
+    input:    "
+      [retry(\"3\")] # retry flaky tests
+      # run the tests
+      [timeout(\"10m\")]
+      @test:
+        cargo test
+    ",
+    expected: "
+      # retry flaky tests
+      # run the tests
+      [retry(3)]
+      [timeout(\"10m\")]
+      @test:
+          cargo test
+    ",
+  }
+
+  test! {
+    name:     blank_lines_before_attributes,
+    input:    "
+      a := 'b'
+
+      [group('x')]
+      foo:
+
+      b:
+        echo b
+
+      [timeout(\"1s\")]
+      c:
+    ",
+    expected: "
+      a := 'b'
+
+      [group('x')]
+      foo:
+
+      b:
+          echo b
+
+      [timeout(\"1s\")]
+      c:
+    ",
+  }
+
+  test! {
+    name:     allow_attributes,
+    input:    "
+      [allow('unused-variables')]
+      x:='y'
+      [allow( 'similar-aliases' )]
+      alias tset := test
+      test:
+    ",
+    expected: "
+      [allow(\"unused-variables\")]
+      x := 'y'
+      [allow(\"similar-aliases\")]
+      alias tset := test
+      test:
+    ",
+  }
+
+  test! {
+    name:     settings,
+    input:    "
+      set shell :=   ['bash',   '-c']
+      set before := 'banner'
+      banner:
+    ",
+    expected: "
+      set shell := ['bash', '-c']
+      set before := 'banner'
+      banner:
+    ",
+  }
+
+  #[test]
+  fn doc_comment() {
+    let formatted = Reformatter::reformat("# hello\n[retry('2')]\nfoo:\n").unwrap();
+    let justfile = testing::compile(&formatted);
+    assert_eq!(justfile.recipes["foo"].doc.as_deref(), Some("hello"));
+  }
+}
@@ -0,0 +1,98 @@
+use crate::common::*;
+
+/// How a failing recipe line or shebang script is retried, configured with
+/// the `[retry(3, delay="2s", backoff="exponential")]` attribute
+#[derive(Debug, PartialEq)]
+pub(crate) struct Retry {
+  /// Maximum number of times to rerun a failing command
+  pub(crate) retries:     u32,
+  /// How long to wait before the first retry
+  pub(crate) delay:       Option<HumanDuration>,
+  /// Double the delay after each retry
+  pub(crate) exponential: bool,
+}
+
+impl Retry {
+  pub(crate) const BACKOFF_CONSTANT: &'static str = "constant";
+  pub(crate) const BACKOFF_EXPONENTIAL: &'static str = "exponential";
+
+  /// Total number of attempts, including the first
+  pub(crate) fn attempts(&self) -> u32 {
+    self.retries.saturating_add(1)
+  }
+
+  /// How long to wait after failed attempt number `attempt`, starting at one
+  pub(crate) fn delay_after(&self, attempt: u32) -> Duration {
+    let delay = self.delay.map(|delay| delay.0).unwrap_or_default();
+
+    if self.exponential {
+      let factor = 2u32.checked_pow(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+      delay.checked_mul(factor).unwrap_or(delay)
+    } else {
+      delay
+    }
+  }
+}
+
+impl Display for Retry {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.retries)?;
+
+    if let Some(delay) = self.delay {
+      write!(f, ", {}=\"{}\"", keyword::DELAY, delay)?;
+    }
+
+    if self.exponential {
+      write!(
+        f,
+        ", {}=\"{}\"",
+        keyword::BACKOFF,
+        Self::BACKOFF_EXPONENTIAL
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn constant_delay() {
+    let retry = Retry {
+      retries:     3,
+      delay:       Some(HumanDuration(Duration::from_secs(2))),
+      exponential: false,
+    };
+
+    assert_eq!(retry.attempts(), 4);
+    assert_eq!(retry.delay_after(1), Duration::from_secs(2));
+    assert_eq!(retry.delay_after(3), Duration::from_secs(2));
+  }
+
+  #[test]
+  fn exponential_delay() {
+    let retry = Retry {
+      retries:     3,
+      delay:       Some(HumanDuration(Duration::from_secs(2))),
+      exponential: true,
+    };
+
+    assert_eq!(retry.delay_after(1), Duration::from_secs(2));
+    assert_eq!(retry.delay_after(2), Duration::from_secs(4));
+    assert_eq!(retry.delay_after(3), Duration::from_secs(8));
+  }
+
+  #[test]
+  fn no_delay() {
+    let retry = Retry {
+      retries:     1,
+      delay:       None,
+      exponential: true,
+    };
+
+    assert_eq!(retry.delay_after(1), Duration::from_secs(0));
+  }
+}
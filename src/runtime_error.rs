@@ -17,6 +17,7 @@ pub(crate) enum RuntimeError<'src> {
     recipe:      &'src str,
     line_number: Option<usize>,
     code:        i32,
+    attempts:    u32,
   },
   Cygpath {
     recipe:       &'src str,
@@ -32,6 +33,9 @@ pub(crate) enum RuntimeError<'src> {
   Internal {
     message: String,
   },
+  Interrupted {
+    recipe: &'src str,
+  },
   IoError {
     recipe:   &'src str,
     io_error: io::Error,
@@ -47,6 +51,11 @@ pub(crate) enum RuntimeError<'src> {
     line_number: Option<usize>,
     signal:      i32,
   },
+  Timeout {
+    recipe:      &'src str,
+    line_number: Option<usize>,
+    timeout:     Duration,
+  },
   TmpdirIoError {
     recipe:   &'src str,
     io_error: io::Error,
@@ -77,13 +86,14 @@ impl<'src> Error for RuntimeError<'src> {
         output_error: OutputError::Code(code),
         ..
       } => code,
+      Self::Interrupted { .. } => 130,
       _ => EXIT_FAILURE,
     }
   }
 }
 
 impl<'src> RuntimeError<'src> {
-  fn context(&self) -> Option<Token> {
+  pub(crate) fn context(&self) -> Option<Token> {
     use RuntimeError::*;
     match self {
       FunctionCall { function, .. } => Some(function.token()),
@@ -91,34 +101,73 @@ impl<'src> RuntimeError<'src> {
       _ => None,
     }
   }
-}
 
-impl<'src> Display for RuntimeError<'src> {
-  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+  /// The one-based number of the recipe line that failed, if known
+  pub(crate) fn line_number(&self) -> Option<usize> {
     use RuntimeError::*;
+    match self {
+      Code { line_number, .. }
+      | Signal { line_number, .. }
+      | Timeout { line_number, .. }
+      | Unknown { line_number, .. } => *line_number,
+      _ => None,
+    }
+  }
 
-    let color = if f.alternate() {
-      Color::always()
-    } else {
-      Color::never()
-    };
-    let message = color.message();
-    write!(f, "{}", message.prefix())?;
+  /// A stable code identifying this kind of error, used in diagnostics
+  /// printed with `--message-format json`
+  pub(crate) fn diagnostic_code(&self) -> &'static str {
+    use RuntimeError::*;
 
     match self {
-      UnknownRecipes {
-        recipes,
-        suggestion,
-      } => {
+      ArgumentCountMismatch { .. } => "E1001",
+      Backtick { .. } => "E1002",
+      Code { .. } => "E1003",
+      Cygpath { .. } => "E1004",
+      Dotenv { .. } => "E1005",
+      FunctionCall { .. } => "E1006",
+      Internal { .. } => "E1007",
+      Interrupted { .. } => "E1008",
+      IoError { .. } => "E1009",
+      Shebang { .. } => "E1010",
+      Signal { .. } => "E1011",
+      Timeout { .. } => "E1012",
+      TmpdirIoError { .. } => "E1013",
+      UnknownOverrides { .. } => "E1014",
+      UnknownRecipes { .. } => "E1015",
+      Unknown { .. } => "E1016",
+      NoRecipes => "E1017",
+      DefaultRecipeRequiresArguments { .. } => "E1018",
+    }
+  }
+
+  pub(crate) fn suggestion(&self) -> Option<Suggestion<'src>> {
+    match self {
+      Self::UnknownRecipes { suggestion, .. } => *suggestion,
+      _ => None,
+    }
+  }
+
+  /// The error message, without source context or suggestion
+  pub(crate) fn message(&self) -> String {
+    let mut message = String::new();
+    self
+      .write_message(&mut message, Color::never())
+      .expect("writing to a string cannot fail");
+    message.trim_end().to_owned()
+  }
+
+  fn write_message(&self, f: &mut dyn fmt::Write, color: Color) -> fmt::Result {
+    use RuntimeError::*;
+
+    match self {
+      UnknownRecipes { recipes, .. } => {
         write!(
           f,
           "Justfile does not contain {} {}.",
           Count("recipe", recipes.len()),
           List::or_ticked(recipes),
         )?;
-        if let Some(suggestion) = *suggestion {
-          write!(f, "\n{}", suggestion)?;
-        }
       },
       UnknownOverrides { overrides } => {
         write!(
@@ -178,7 +227,8 @@ impl<'src> Display for RuntimeError<'src> {
         recipe,
         line_number,
         code,
-      } =>
+        attempts,
+      } => {
         if let Some(n) = line_number {
           write!(
             f,
@@ -187,7 +237,12 @@ impl<'src> Display for RuntimeError<'src> {
           )?;
         } else {
           write!(f, "Recipe `{}` failed with exit code {}", recipe, code)?;
-        },
+        }
+
+        if *attempts > 1 {
+          write!(f, " after {} attempts", attempts)?;
+        }
+      },
       Cygpath {
         recipe,
         output_error,
@@ -286,6 +341,27 @@ impl<'src> Display for RuntimeError<'src> {
         } else {
           write!(f, "Recipe `{}` was terminated by signal {}", recipe, signal)?;
         },
+      Timeout {
+        recipe,
+        line_number,
+        timeout,
+      } =>
+        if let Some(n) = line_number {
+          write!(
+            f,
+            "Recipe `{}` timed out on line {} after {}",
+            recipe,
+            n,
+            HumanDuration(*timeout)
+          )?;
+        } else {
+          write!(
+            f,
+            "Recipe `{}` timed out after {}",
+            recipe,
+            HumanDuration(*timeout)
+          )?;
+        },
       Unknown {
         recipe,
         line_number,
@@ -376,6 +452,9 @@ impl<'src> Display for RuntimeError<'src> {
           Count("argument", *min_arguments),
         )?;
       },
+      Interrupted { recipe } => {
+        write!(f, "Recipe `{}` was interrupted", recipe)?;
+      },
       Internal { message } => {
         write!(
           f,
@@ -386,6 +465,26 @@ impl<'src> Display for RuntimeError<'src> {
       },
     }
 
+    Ok(())
+  }
+}
+
+impl<'src> Display for RuntimeError<'src> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    let color = if f.alternate() {
+      Color::always()
+    } else {
+      Color::never()
+    };
+    let message = color.message();
+    write!(f, "{}", message.prefix())?;
+
+    self.write_message(f, color)?;
+
+    if let Some(suggestion) = self.suggestion() {
+      write!(f, "\n{}", suggestion)?;
+    }
+
     write!(f, "{}", message.suffix())?;
 
     if let Some(token) = self.context() {
@@ -23,6 +23,7 @@ impl<'src, 'run> Scope<'src, 'run> {
 
   pub(crate) fn bind(&mut self, export: bool, name: Name<'src>, value: String) {
     self.bindings.insert(Binding {
+      attributes: Vec::new(),
       name,
       export,
       value,
@@ -3,6 +3,8 @@ use crate::common::*;
 use std::path::Component;
 
 pub(crate) const FILENAME: &str = "justfile";
+pub(crate) const STDIN: &str = "-";
+const FILENAMES: &[&str] = &[FILENAME, ".justfile", "*.just"];
 const PROJECT_ROOT_CHILDREN: &[&str] = &[".bzr", ".git", ".hg", ".svn", "_darcs"];
 
 pub(crate) struct Search {
@@ -40,6 +42,13 @@ impl Search {
         })
       },
 
+      SearchConfig::GlobalJustfile => Self::global(invocation_directory),
+
+      SearchConfig::Stdin { working_directory } => Ok(Self::stdin(
+        invocation_directory,
+        working_directory.as_deref(),
+      )),
+
       SearchConfig::WithJustfile { justfile } => {
         let justfile = Self::clean(invocation_directory, justfile);
 
@@ -61,6 +70,89 @@ impl Search {
     }
   }
 
+  /// Read the justfile from standard input. Since it has no parent
+  /// directory, the working directory defaults to the invocation directory.
+  fn stdin(invocation_directory: &Path, working_directory: Option<&Path>) -> Self {
+    Self {
+      justfile:          PathBuf::from(STDIN),
+      working_directory: working_directory
+        .map(|working_directory| Self::clean(invocation_directory, working_directory))
+        .unwrap_or_else(|| invocation_directory.to_owned()),
+    }
+  }
+
+  /// Whether the justfile is read from standard input
+  pub(crate) fn is_stdin(&self) -> bool {
+    self.justfile == Path::new(STDIN)
+  }
+
+  /// Use the global justfile, with the working directory set to the
+  /// invocation directory
+  pub(crate) fn global(invocation_directory: &Path) -> SearchResult<Self> {
+    let candidates = Self::global_candidates();
+
+    let justfile = candidates
+      .iter()
+      .find(|candidate| candidate.is_file())
+      .cloned()
+      .ok_or(SearchError::GlobalJustfileNotFound { candidates })?;
+
+    Ok(Self {
+      working_directory: invocation_directory.to_owned(),
+      justfile,
+    })
+  }
+
+  /// Paths where the global justfile may be, in order of preference:
+  /// `$XDG_CONFIG_HOME/just/justfile`, defaulting to
+  /// `~/.config/just/justfile` if `$XDG_CONFIG_HOME` is unset, empty, or
+  /// relative, and `~/.justfile`
+  fn global_candidates() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+
+    let config = env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .filter(|config| config.is_absolute())
+      .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    let mut candidates = Vec::new();
+
+    if let Some(config) = config {
+      candidates.push(config.join("just").join(FILENAME));
+    }
+
+    if let Some(home) = home {
+      candidates.push(home.join(".justfile"));
+    }
+
+    candidates
+  }
+
+  /// Search for the next justfile, starting in the parent of the directory
+  /// containing the current justfile
+  pub(crate) fn parent(&self) -> SearchResult<Self> {
+    let current = self.justfile.parent().ok_or(SearchError::NotFound)?;
+
+    if Self::stop_at_root()
+      && PROJECT_ROOT_CHILDREN
+        .iter()
+        .any(|child| current.join(child).exists())
+    {
+      return Err(SearchError::NotFound);
+    }
+
+    let directory = current.parent().ok_or(SearchError::NotFound)?;
+
+    let justfile = Self::justfile(directory)?;
+
+    let working_directory = Self::working_directory_from_justfile(&justfile)?;
+
+    Ok(Self {
+      justfile,
+      working_directory,
+    })
+  }
+
   pub(crate) fn init(
     search_config: &SearchConfig,
     invocation_directory: &Path,
@@ -90,6 +182,23 @@ impl Search {
         })
       },
 
+      // initialize `~/.justfile`, unless there already is a global justfile
+      SearchConfig::GlobalJustfile => match Self::global(invocation_directory) {
+        Err(SearchError::GlobalJustfileNotFound { candidates }) => match candidates.last() {
+          Some(justfile) => Ok(Self {
+            justfile:          justfile.to_owned(),
+            working_directory: invocation_directory.to_owned(),
+          }),
+          None => Err(SearchError::GlobalJustfileNotFound { candidates }),
+        },
+        result => result,
+      },
+
+      SearchConfig::Stdin { working_directory } => Ok(Self::stdin(
+        invocation_directory,
+        working_directory.as_deref(),
+      )),
+
       SearchConfig::WithJustfile { justfile } => {
         let justfile = Self::clean(invocation_directory, justfile);
 
@@ -111,9 +220,64 @@ impl Search {
     }
   }
 
+  /// Names that justfiles may have, in order of preference, taken from the
+  /// comma-separated `$JUST_JUSTFILE_NAMES` if set, and otherwise `justfile`,
+  /// `.justfile`, and `*.just`
+  fn names() -> Vec<String> {
+    let names = env::var("JUST_JUSTFILE_NAMES")
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|name| !name.is_empty())
+      .map(str::to_owned)
+      .collect::<Vec<String>>();
+
+    if names.is_empty() {
+      FILENAMES.iter().map(|name| (*name).to_owned()).collect()
+    } else {
+      names
+    }
+  }
+
+  /// Whether `file_name` matches the justfile name `name`, ignoring case. A
+  /// leading `*` in `name` matches any non-empty prefix.
+  fn matches(file_name: &str, name: &str) -> bool {
+    let file_name = file_name.to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+
+    if let Some(suffix) = name.strip_prefix('*') {
+      file_name.len() > suffix.len() && file_name.ends_with(suffix)
+    } else {
+      file_name == name
+    }
+  }
+
   fn justfile(directory: &Path) -> SearchResult<PathBuf> {
+    Self::justfile_named(
+      directory,
+      &Self::names(),
+      Self::stop_at_root(),
+    )
+  }
+
+  /// Whether `$JUST_SEARCH_STOP_AT_ROOT` is set and non-empty, in which case
+  /// searches never leave the first directory that looks like a project root
+  fn stop_at_root() -> bool {
+    env::var_os("JUST_SEARCH_STOP_AT_ROOT").map_or(false, |value| !value.is_empty())
+  }
+
+  /// Search `directory` and its ancestors for a justfile with one of `names`.
+  /// Names are tried in order in each directory, and more than one file
+  /// matching the same name is an error. If `stop_at_root` is true, the
+  /// search ends at the first directory that looks like a project root.
+  fn justfile_named(
+    directory: &Path,
+    names: &[String],
+    stop_at_root: bool,
+  ) -> SearchResult<PathBuf> {
     for directory in directory.ancestors() {
-      let mut candidates = Vec::new();
+      let mut file_names = Vec::new();
+      let mut project_root = false;
 
       let entries = fs::read_dir(directory).map_err(|io_error| SearchError::Io {
         io_error,
@@ -124,17 +288,35 @@ impl Search {
           io_error,
           directory: directory.to_owned(),
         })?;
+        if PROJECT_ROOT_CHILDREN.contains(&entry.file_name().to_string_lossy().as_ref()) {
+          project_root = true;
+        }
+        if entry.path().is_dir() {
+          continue;
+        }
         if let Some(name) = entry.file_name().to_str() {
-          if name.eq_ignore_ascii_case(FILENAME) {
-            candidates.push(entry.path());
-          }
+          file_names.push(name.to_owned());
+        }
+      }
+
+      file_names.sort();
+
+      for name in names {
+        let mut candidates = file_names
+          .iter()
+          .filter(|file_name| Self::matches(file_name, name))
+          .map(|file_name| directory.join(file_name))
+          .collect::<Vec<PathBuf>>();
+
+        if candidates.len() == 1 {
+          return Ok(candidates.pop().unwrap());
+        } else if candidates.len() > 1 {
+          return Err(SearchError::MultipleCandidates { candidates });
         }
       }
 
-      if candidates.len() == 1 {
-        return Ok(candidates.pop().unwrap());
-      } else if candidates.len() > 1 {
-        return Err(SearchError::MultipleCandidates { candidates });
+      if stop_at_root && project_root {
+        break;
       }
     }
 
@@ -341,6 +523,160 @@ mod tests {
     assert_eq!(search.working_directory, sub);
   }
 
+  fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| (*name).to_owned()).collect()
+  }
+
+  #[test]
+  fn found_hidden() {
+    let tmp = tmptree! {
+      ".justfile": "",
+    };
+
+    let justfile = Search::justfile_named(tmp.path(), &names(FILENAMES), false).unwrap();
+
+    assert_eq!(justfile, tmp.path().join(".justfile"));
+  }
+
+  #[test]
+  fn found_just_extension() {
+    let tmp = tmptree! {
+      "build.just": "",
+      "build.justfile": "",
+      ".just": "",
+    };
+
+    let justfile = Search::justfile_named(tmp.path(), &names(FILENAMES), false).unwrap();
+
+    assert_eq!(justfile, tmp.path().join("build.just"));
+  }
+
+  #[test]
+  fn earlier_names_preferred() {
+    let tmp = tmptree! {
+      justfile: "",
+      ".justfile": "",
+      "build.just": "",
+    };
+
+    let justfile = Search::justfile_named(tmp.path(), &names(FILENAMES), false).unwrap();
+
+    assert_eq!(justfile, tmp.path().join("justfile"));
+  }
+
+  #[test]
+  fn multiple_candidates_with_same_name() {
+    let tmp = tmptree! {
+      "build.just": "",
+      "test.just": "",
+    };
+
+    match Search::justfile_named(tmp.path(), &names(FILENAMES), false) {
+      Err(SearchError::MultipleCandidates { candidates }) => assert_eq!(
+        candidates,
+        &[tmp.path().join("build.just"), tmp.path().join("test.just")]
+      ),
+      result => panic!("Multiple candidates error was expected: {:?}", result),
+    }
+  }
+
+  #[test]
+  fn directories_ignored() {
+    let tmp = tmptree! {
+      "build.just": {},
+      sub: {},
+    };
+
+    match Search::justfile_named(&tmp.path().join("sub"), &names(&["*.just"]), true) {
+      Err(SearchError::NotFound) => {},
+      result => panic!("No justfile found error was expected: {:?}", result),
+    }
+  }
+
+  #[test]
+  fn custom_names() {
+    let tmp = tmptree! {
+      justfile: "",
+      "Taskfile": "",
+    };
+
+    let justfile = Search::justfile_named(tmp.path(), &names(&["taskfile"]), false).unwrap();
+
+    assert_eq!(justfile, tmp.path().join("Taskfile"));
+  }
+
+  #[test]
+  fn stop_at_root() {
+    let tmp = tmptree! {
+      justfile: "",
+      project: {
+        ".git": {},
+        sub: {},
+      },
+    };
+
+    let sub = tmp.path().join("project/sub");
+
+    match Search::justfile_named(&sub, &names(FILENAMES), true) {
+      Err(SearchError::NotFound) => {},
+      result => panic!("No justfile found error was expected: {:?}", result),
+    }
+
+    let justfile = Search::justfile_named(&sub, &names(FILENAMES), false).unwrap();
+
+    assert_eq!(justfile, tmp.path().join("justfile"));
+  }
+
+  #[test]
+  fn stop_at_root_searches_root() {
+    let tmp = tmptree! {
+      project: {
+        ".hg": {},
+        justfile: "",
+        sub: {},
+      },
+    };
+
+    let justfile =
+      Search::justfile_named(&tmp.path().join("project/sub"), &names(FILENAMES), true).unwrap();
+
+    assert_eq!(justfile, tmp.path().join("project/justfile"));
+  }
+
+  #[test]
+  fn matches() {
+    assert!(Search::matches("justfile", "justfile"));
+    assert!(Search::matches("JUSTFILE", "justfile"));
+    assert!(Search::matches("build.just", "*.just"));
+    assert!(Search::matches("Build.JUST", "*.just"));
+    assert!(!Search::matches(".just", "*.just"));
+    assert!(!Search::matches("build.justfile", "*.just"));
+    assert!(!Search::matches("justfile.bak", "justfile"));
+  }
+
+  #[test]
+  fn stdin() {
+    let invocation_directory = Path::new("/foo");
+
+    let search_config = SearchConfig::Stdin {
+      working_directory: None,
+    };
+
+    let search = Search::find(&search_config, invocation_directory).unwrap();
+
+    assert!(search.is_stdin());
+    assert_eq!(search.working_directory, invocation_directory);
+
+    let search_config = SearchConfig::Stdin {
+      working_directory: Some(PathBuf::from("../bar")),
+    };
+
+    let search = Search::find(&search_config, invocation_directory).unwrap();
+
+    assert!(search.is_stdin());
+    assert_eq!(search.working_directory, Path::new("/bar"));
+  }
+
   #[test]
   fn clean() {
     let cases = &[
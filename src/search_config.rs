@@ -9,6 +9,13 @@ pub(crate) enum SearchConfig {
   FromInvocationDirectory,
   /// As in `Invocation`, but start from `search_directory`.
   FromSearchDirectory { search_directory: PathBuf },
+  /// Use the user's global justfile, with the working directory set to the
+  /// invocation directory.
+  GlobalJustfile,
+  /// Read the justfile from standard input, with the working directory set
+  /// to `working_directory`, if given, and otherwise the invocation
+  /// directory.
+  Stdin { working_directory: Option<PathBuf> },
   /// Use user-specified justfile, with the working directory set to the
   /// directory that contains it.
   WithJustfile { justfile: PathBuf },
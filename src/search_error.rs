@@ -24,10 +24,29 @@ pub(crate) enum SearchError {
   },
   #[snafu(display("No justfile found"))]
   NotFound,
+  #[snafu(display(
+    "No global justfile found, looked for {}",
+    List::or_ticked(candidates.iter().map(|candidate| candidate.display()))
+  ))]
+  GlobalJustfileNotFound { candidates: Vec<PathBuf> },
   #[snafu(display("Justfile path had no parent: {}", path.display()))]
   JustfileHadNoParent { path: PathBuf },
 }
 
+impl SearchError {
+  /// A stable code identifying this kind of error, used in diagnostics
+  /// printed with `--message-format json`
+  pub(crate) fn diagnostic_code(&self) -> &'static str {
+    match self {
+      Self::MultipleCandidates { .. } => "E2001",
+      Self::Io { .. } => "E2002",
+      Self::NotFound => "E2003",
+      Self::GlobalJustfileNotFound { .. } => "E2004",
+      Self::JustfileHadNoParent { .. } => "E2005",
+    }
+  }
+}
+
 impl Error for SearchError {}
 
 #[cfg(test)]
@@ -2,11 +2,38 @@ use crate::common::*;
 
 #[derive(Debug)]
 pub(crate) enum Setting<'src> {
+  After(Hook<'src>),
+  Before(Hook<'src>),
+  Fallback(bool),
   Shell(Shell<'src>),
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct Hook<'src> {
+  pub(crate) recipe: &'src str,
+  pub(crate) token:  Token<'src>,
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct Shell<'src> {
   pub(crate) command:   StringLiteral<'src>,
   pub(crate) arguments: Vec<StringLiteral<'src>>,
 }
+
+impl<'src> Display for Setting<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Setting::After(hook) | Setting::Before(hook) => write!(f, "{}", hook.token.lexeme()),
+      Setting::Fallback(value) => write!(f, "{}", value),
+      Setting::Shell(shell) => {
+        write!(f, "[{}", shell.command)?;
+
+        for argument in &shell.arguments {
+          write!(f, ", {}", argument)?;
+        }
+
+        write!(f, "]")
+      },
+    }
+  }
+}
@@ -2,12 +2,20 @@ use crate::common::*;
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct Settings<'src> {
-  pub(crate) shell: Option<setting::Shell<'src>>,
+  pub(crate) after:    Option<&'src str>,
+  pub(crate) before:   Option<&'src str>,
+  pub(crate) fallback: bool,
+  pub(crate) shell:    Option<setting::Shell<'src>>,
 }
 
 impl<'src> Settings<'src> {
   pub(crate) fn new() -> Settings<'src> {
-    Settings { shell: None }
+    Settings {
+      after:    None,
+      before:   None,
+      fallback: false,
+      shell:    None,
+    }
   }
 
   pub(crate) fn shell_command(&self, config: &Config) -> Command {
@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Severity {
+  Error,
+  Warning,
+}
@@ -0,0 +1,107 @@
+use crate::common::*;
+
+use std::{
+  io::Read,
+  process::{Child, Stdio},
+  sync::{Arc, PoisonError},
+  thread::{self, JoinHandle},
+};
+
+/// A writer shared between the threads which forward a child's output
+pub(crate) type Sink = Arc<Mutex<dyn Write + Send>>;
+
+/// Where recipe output, and the messages that `just` prints while running
+/// recipes, are written. Streams without a sink are inherited from `just`.
+#[derive(Clone, Default)]
+pub(crate) struct Sinks {
+  pub(crate) stdout: Option<Sink>,
+  pub(crate) stderr: Option<Sink>,
+}
+
+impl Sinks {
+  /// Whether any output is redirected to a sink
+  pub(crate) fn active(&self) -> bool {
+    self.stdout.is_some() || self.stderr.is_some()
+  }
+
+  /// Print `message` followed by a newline to the stderr sink, or to stderr
+  pub(crate) fn eprintln(&self, message: impl Display) {
+    match &self.stderr {
+      Some(stderr) => {
+        let mut stderr = stderr.lock().unwrap_or_else(PoisonError::into_inner);
+        // like `eprintln!`, but errors are ignored rather than panicking
+        writeln!(stderr, "{}", message).ok();
+      },
+      None => eprintln!("{}", message),
+    }
+  }
+
+  /// Pipe the output of `command` so that it can be forwarded to sinks
+  pub(crate) fn pipe(&self, command: &mut Command) {
+    if self.stdout.is_some() {
+      command.stdout(Stdio::piped());
+    }
+
+    if self.stderr.is_some() {
+      command.stderr(Stdio::piped());
+    }
+  }
+
+  /// Start threads which copy the piped output of `child` to sinks until the
+  /// child closes its output
+  pub(crate) fn forward(&self, child: &mut Child) -> Vec<JoinHandle<()>> {
+    let mut threads = Vec::new();
+
+    if let (Some(sink), Some(stdout)) = (&self.stdout, child.stdout.take()) {
+      threads.push(Self::copy(stdout, sink.clone()));
+    }
+
+    if let (Some(sink), Some(stderr)) = (&self.stderr, child.stderr.take()) {
+      threads.push(Self::copy(stderr, sink.clone()));
+    }
+
+    threads
+  }
+
+  fn copy(mut source: impl Read + Send + 'static, sink: Sink) -> JoinHandle<()> {
+    thread::spawn(move || {
+      let mut buffer = [0; 8192];
+
+      while let Ok(n) = source.read(&mut buffer) {
+        if n == 0 {
+          break;
+        }
+
+        let mut sink = sink.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if sink.write_all(&buffer[..n]).is_err() {
+          break;
+        }
+      }
+    })
+  }
+}
+
+impl Debug for Sinks {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("Sinks")
+      .field("stdout", &self.stdout.is_some())
+      .field("stderr", &self.stderr.is_some())
+      .finish()
+  }
+}
+
+impl PartialEq for Sinks {
+  fn eq(&self, other: &Self) -> bool {
+    fn same(a: Option<&Sink>, b: Option<&Sink>) -> bool {
+      match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
+      }
+    }
+
+    same(self.stdout.as_ref(), other.stdout.as_ref())
+      && same(self.stderr.as_ref(), other.stderr.as_ref())
+  }
+}
@@ -1,6 +1,6 @@
 use crate::common::*;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub(crate) struct StringLiteral<'src> {
   pub(crate) raw:    &'src str,
   pub(crate) cooked: Cow<'src, str>,
@@ -2,16 +2,46 @@ use crate::common::*;
 
 #[derive(PartialEq, Clone, Debug)]
 pub(crate) enum Subcommand {
+  Choose {
+    overrides: BTreeMap<String, String>,
+  },
+  Complete {
+    shell: String,
+    words: Vec<String>,
+  },
   Completions {
     shell: String,
   },
-  Dump,
+  Docs,
+  Dump {
+    format: DumpFormat,
+  },
   Edit,
   Evaluate {
     overrides: BTreeMap<String, String>,
   },
+  Format {
+    check: bool,
+  },
+  Graph {
+    format: GraphFormat,
+    recipe: Option<String>,
+  },
+  Groups {
+    unsorted: bool,
+  },
   Init,
-  List,
+  Lint,
+  List {
+    heading:  String,
+    prefix:   String,
+    unsorted: bool,
+  },
+  Lsp,
+  Plan {
+    overrides: BTreeMap<String, String>,
+    arguments: Vec<String>,
+  },
   Run {
     overrides: BTreeMap<String, String>,
     arguments: Vec<String>,
@@ -20,18 +50,64 @@ pub(crate) enum Subcommand {
     name: String,
   },
   Summary,
+  Usage {
+    name: String,
+  },
   Variables,
 }
 
-const FISH_RECIPE_COMPLETIONS: &str = r#"function __fish_just_complete_recipes
-    just --summary 2> /dev/null | tr " " "\n" || echo ""
-end
+const BASH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
+  (
+    r#"            if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then"#,
+    r#"            if [[ ${cur} == -* ]] ; then"#,
+  ),
+  (
+    r#"                --graph)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --show)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                    -s)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --usage)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+"#,
+    "",
+  ),
+  (
+    r#"            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;"#,
+    r#"            local words line="${COMP_LINE:0:COMP_POINT}"
+            read -ra words <<< "${line}"
+            if [[ ${line} == *[[:space:]] ]]; then
+                words+=("")
+            fi
+            local IFS=$'\n'
+            COMPREPLY=( $(just --complete bash -- "${words[@]:1}" 2> /dev/null) )
+            if [[ ${#COMPREPLY[@]} -eq 1 && ${COMPREPLY[0]} == *[/=] ]]; then
+                compopt -o nospace
+            fi
+            return 0
+            ;;"#,
+  ),
+];
 
-# don't suggest files right off
-complete -c just -n "__fish_is_first_arg" --no-files
+const FISH_RECIPE_COMPLETIONS: &str = r#"function __fish_just_complete
+    set -l words (commandline -opc)
+    set -e words[1]
+    just --complete fish -- $words (commandline -ct) 2> /dev/null
+end
 
-# complete recipes
-complete -c just -a '(__fish_just_complete_recipes)'
+# complete recipes, parameters, and variables
+complete -c just -f -a '(__fish_just_complete)'
 
 # autogenerated completions
 "#;
@@ -51,6 +127,10 @@ const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
     r#"'-s+[Show information about <RECIPE>]: :_just_commands' \
 '--show=[Show information about <RECIPE>]: :_just_commands' \"#,
   ),
+  (
+    r#"'--usage=[Print how to invoke <RECIPE>]' \"#,
+    r#"'--usage=[Print how to invoke <RECIPE>]: :_just_commands' \"#,
+  ),
   (
     "'::ARGUMENTS -- Overrides and recipe(s) to run, defaulting to the first recipe in the \
      justfile:_files' \\
@@ -60,28 +140,9 @@ const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
     r#")
 
     _arguments "${_arguments_options[@]}" $common \
-        '1: :_just_commands' \
-        '*: :->args' \
+        '*: :_just_commands' \
         && ret=0
 
-    case $state in
-        args)
-            curcontext="${curcontext%:*}-${words[2]}:"
-
-            local lastarg=${words[${#words}]}
-
-            if [[ ${lastarg} = */* ]]; then
-                # Arguments contain slash would be recognised as a file
-                _arguments -s -S $common '*:: :_files'
-            else
-                # Show usage message
-                _message "`just --show ${words[2]}`"
-                # Or complete with other commands
-                #_arguments -s -S $common '*:: :_just_commands'
-            fi
-        ;;
-    esac
-
     return ret
 "#,
   ),
@@ -90,7 +151,7 @@ const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
 \x20\x20\x20\x20\x20\x20\x20\x20
     )",
     r#"    local commands; commands=(
-        ${${${(M)"${(f)$(_call_program commands just --list)}":#    *}/ ##/}/ ##/:Args: }
+        ${(f)"$(_call_program commands just --complete zsh -- ${(@q)words[2,CURRENT-1]} ${(q)PREFIX} 2> /dev/null)"}
     )
 "#,
   ),
@@ -110,6 +171,12 @@ _just "$@""#,
 ];
 
 impl Subcommand {
+  /// Whether recipes are only being planned, with `--plan`, so backticks
+  /// shouldn't be run
+  pub(crate) fn is_plan(&self) -> bool {
+    matches!(self, Self::Plan { .. })
+  }
+
   pub(crate) fn completions(shell: &str) -> Result<(), i32> {
     fn replace(haystack: &mut String, needle: &str, replacement: &str) -> Result<(), i32> {
       if let Some(index) = haystack.find(needle) {
@@ -130,10 +197,16 @@ impl Subcommand {
 
     let buffer = Vec::new();
     let mut cursor = Cursor::new(buffer);
-    Config::app().gen_completions_to(env!("CARGO_PKG_NAME"), shell, &mut cursor);
+    Config::completions_app().gen_completions_to(env!("CARGO_PKG_NAME"), shell, &mut cursor);
     let buffer = cursor.into_inner();
     let mut script = String::from_utf8(buffer).expect("Clap completion not UTF-8");
 
+    if let clap::Shell::Bash = shell {
+      for (needle, replacement) in BASH_COMPLETION_REPLACEMENTS {
+        replace(&mut script, needle, replacement)?;
+      }
+    }
+
     if let clap::Shell::Zsh = shell {
       for (needle, replacement) in ZSH_COMPLETION_REPLACEMENTS {
         replace(&mut script, needle, replacement)?;
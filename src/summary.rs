@@ -28,7 +28,13 @@ pub fn summary(path: &Path) -> Result<Result<Summary, String>, io::Error> {
 
   match Compiler::compile(&text) {
     Ok(justfile) => Ok(Ok(Summary::new(justfile))),
-    Err(compilation_error) => Ok(Err(compilation_error.to_string())),
+    Err(compilation_errors) => Ok(Err(
+      compilation_errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("\n"),
+    )),
   }
 }
 
@@ -5,7 +5,14 @@ use pretty_assertions::assert_eq;
 pub(crate) fn compile(text: &str) -> Justfile {
   match Compiler::compile(text) {
     Ok(justfile) => justfile,
-    Err(error) => panic!("Expected successful compilation but got error:\n {}", error),
+    Err(errors) => panic!(
+      "Expected successful compilation but got errors:\n {}",
+      errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("\n")
+    ),
   }
 }
 
@@ -63,7 +70,9 @@ pub(crate) fn analysis_error(
 
   match Analyzer::analyze(module) {
     Ok(_) => panic!("Analysis unexpectedly succeeded"),
-    Err(have) => {
+    Err(errors) => {
+      assert_eq!(errors.len(), 1, "Expected a single error: {:?}", errors);
+      let have = &errors[0];
       let want = CompilationError {
         token: Token {
           kind: have.token.kind,
@@ -75,7 +84,7 @@ pub(crate) fn analysis_error(
         },
         kind,
       };
-      assert_eq!(have, want);
+      assert_eq!(*have, want);
     },
   }
 }
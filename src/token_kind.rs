@@ -16,6 +16,7 @@ pub(crate) enum TokenKind {
   Equals,
   Identifier,
   Indent,
+  Integer,
   InterpolationEnd,
   InterpolationStart,
   ParenL,
@@ -46,6 +47,7 @@ impl Display for TokenKind {
       Equals => "'='",
       Identifier => "identifier",
       Indent => "indent",
+      Integer => "integer",
       InterpolationEnd => "'}}'",
       InterpolationStart => "'{{'",
       ParenL => "'('",
@@ -3,10 +3,13 @@ use crate::common::*;
 pub(crate) type UnresolvedRecipe<'src> = Recipe<'src, UnresolvedDependency<'src>>;
 
 impl<'src> UnresolvedRecipe<'src> {
+  /// Resolve dependencies to `resolved`, reporting dependencies that are
+  /// passed the wrong number of arguments in `errors`
   pub(crate) fn resolve(
     self,
     resolved: Vec<Rc<Recipe<'src>>>,
-  ) -> CompilationResult<'src, Recipe<'src>> {
+    errors: &mut Vec<CompilationError<'src>>,
+  ) -> Recipe<'src> {
     assert_eq!(self.dependencies.len(), resolved.len());
     for (unresolved, resolved) in self.dependencies.iter().zip(&resolved) {
       assert_eq!(unresolved.recipe.lexeme(), resolved.name.lexeme());
@@ -14,7 +17,7 @@ impl<'src> UnresolvedRecipe<'src> {
         .argument_range()
         .contains(&unresolved.arguments.len())
       {
-        return Err(unresolved.recipe.error(
+        errors.push(unresolved.recipe.error(
           CompilationErrorKind::DependencyArgumentCountMismatch {
             dependency: unresolved.recipe.lexeme(),
             found:      unresolved.arguments.len(),
@@ -35,7 +38,8 @@ impl<'src> UnresolvedRecipe<'src> {
       })
       .collect();
 
-    Ok(Recipe {
+    Recipe {
+      attributes: self.attributes,
       doc: self.doc,
       body: self.body,
       name: self.name,
@@ -44,6 +48,6 @@ impl<'src> UnresolvedRecipe<'src> {
       quiet: self.quiet,
       shebang: self.shebang,
       dependencies,
-    })
+    }
   }
 }
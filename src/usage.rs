@@ -0,0 +1,239 @@
+use crate::common::*;
+
+use unicode_width::UnicodeWidthStr;
+
+/// How to invoke a recipe, printed by `--usage` and `just RECIPE --help`
+pub(crate) struct Usage<'src: 'run, 'run> {
+  justfile: &'run Justfile<'src>,
+  recipe:   &'run Recipe<'src>,
+}
+
+impl<'src: 'run, 'run> Usage<'src, 'run> {
+  pub(crate) fn new(justfile: &'run Justfile<'src>, recipe: &'run Recipe<'src>) -> Self {
+    Self { justfile, recipe }
+  }
+
+  /// A parameter as it appears in the usage line: required parameters in
+  /// angle brackets, optional parameters in square brackets, and variadic
+  /// parameters followed by `...`
+  fn placeholder(parameter: &Parameter) -> String {
+    let name = parameter.name.lexeme();
+
+    let mut placeholder = if parameter.default.is_some() {
+      format!("[{}]", name)
+    } else {
+      format!("<{}>", name)
+    };
+
+    if parameter.variadic {
+      placeholder += "...";
+    }
+
+    placeholder
+  }
+}
+
+impl<'src: 'run, 'run> Display for Usage<'src, 'run> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let recipe = self.recipe;
+
+    if let Some(doc) = &recipe.doc {
+      writeln!(f, "{}", doc)?;
+      writeln!(f)?;
+    }
+
+    write!(f, "Usage: just {}", recipe.name())?;
+    for parameter in &recipe.parameters {
+      write!(f, " {}", Self::placeholder(parameter))?;
+    }
+    writeln!(f)?;
+
+    if !recipe.parameters.is_empty() {
+      let placeholders = recipe
+        .parameters
+        .iter()
+        .map(Self::placeholder)
+        .collect::<Vec<String>>();
+
+      let width = placeholders
+        .iter()
+        .map(|placeholder| UnicodeWidthStr::width(placeholder.as_str()))
+        .max()
+        .unwrap_or(0);
+
+      writeln!(f)?;
+      writeln!(f, "Arguments:")?;
+
+      for (parameter, placeholder) in recipe.parameters.iter().zip(placeholders) {
+        let mut notes = Vec::new();
+
+        if let Some(doc) = recipe.parameter_doc(parameter.name.lexeme()) {
+          notes.push(doc.to_owned());
+        }
+
+        let choices = recipe
+          .choices(parameter.name.lexeme())
+          .collect::<Vec<&str>>();
+
+        if !choices.is_empty() {
+          notes.push(format!("[possible values: {}]", choices.join(", ")));
+        }
+
+        if recipe.is_path(parameter.name.lexeme()) {
+          notes.push("[path]".to_owned());
+        }
+
+        if let Some(default) = &parameter.default {
+          notes.push(format!("[default: {}]", default));
+        }
+
+        if notes.is_empty() {
+          writeln!(f, "  {}", placeholder)?;
+        } else {
+          let padding = width - UnicodeWidthStr::width(placeholder.as_str());
+          writeln!(
+            f,
+            "  {}{}  {}",
+            placeholder,
+            " ".repeat(padding),
+            notes.join(" ")
+          )?;
+        }
+      }
+    }
+
+    // required parameters are given their first choice, if any, or their name
+    let mut example = format!("just {}", recipe.name());
+    for parameter in &recipe.parameters {
+      if parameter.default.is_none() {
+        let name = parameter.name.lexeme();
+        let value = recipe.choices(name).next().unwrap_or(name);
+        example += &format!(" {}", value);
+      }
+    }
+
+    writeln!(f)?;
+    writeln!(f, "Example:")?;
+    writeln!(f, "  {}", example)?;
+
+    let aliases = self
+      .justfile
+      .aliases
+      .values()
+      .filter(|alias| alias.target.name() == recipe.name())
+      .map(|alias| alias.name.lexeme())
+      .collect::<Vec<&str>>();
+
+    if !aliases.is_empty() {
+      writeln!(f)?;
+      writeln!(f, "Aliases: {}", aliases.join(", "))?;
+    }
+
+    if !recipe.dependencies.is_empty() {
+      let dependencies = recipe
+        .dependencies
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+      writeln!(f)?;
+      writeln!(f, "Dependencies: {}", dependencies.join(" "))?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  macro_rules! test {
+    {
+      name: $name:ident,
+      justfile: $justfile:expr,
+      recipe: $recipe:expr,
+      usage: $usage:expr,
+    } => {
+      #[test]
+      fn $name() {
+        let src = testing::unindent($justfile);
+        let justfile = testing::compile(&src);
+        let recipe = justfile.get_recipe($recipe).unwrap();
+
+        let have = Usage::new(&justfile, recipe).to_string();
+
+        assert_eq!(have, testing::unindent($usage));
+      }
+    }
+  }
+
+  test! {
+    name: no_parameters,
+    justfile: "
+      build:
+    ",
+    recipe: "build",
+    usage: "
+      Usage: just build
+
+      Example:
+        just build
+    ",
+  }
+
+  test! {
+    name: parameters,
+    justfile: "
+      # deploy the project
+      [choices('target', 'staging', 'production')]
+      [path('config')]
+      [doc('config', 'deploy configuration')]
+      deploy target config='deploy.toml' +flags='': build (test 'unit')
+
+      build:
+
+      test kind:
+
+      alias d := deploy
+      alias ship := deploy
+    ",
+    recipe: "deploy",
+    usage: "
+      deploy the project
+
+      Usage: just deploy <target> [config] [flags]...
+
+      Arguments:
+        <target>    [possible values: staging, production]
+        [config]    deploy configuration [path] [default: 'deploy.toml']
+        [flags]...  [default: '']
+
+      Example:
+        just deploy staging
+
+      Aliases: d, ship
+
+      Dependencies: build (test 'unit')
+    ",
+  }
+
+  test! {
+    name: required_variadic,
+    justfile: "
+      backup +files:
+    ",
+    recipe: "backup",
+    usage: "
+      Usage: just backup <files>...
+
+      Arguments:
+        <files>...
+
+      Example:
+        just backup files
+    ",
+  }
+}
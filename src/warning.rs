@@ -4,24 +4,84 @@ use Warning::*;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum Warning<'src> {
-  DeprecatedEquals { equals: Token<'src> },
+  DeprecatedEquals {
+    equals: Token<'src>,
+  },
+  EnvVarWithoutDefault {
+    recipe:   &'src str,
+    function: Name<'src>,
+  },
+  IgnoredError {
+    recipe:      &'src str,
+    line_number: usize,
+    code:        i32,
+  },
+  SimilarAlias {
+    alias:   Name<'src>,
+    similar: &'src str,
+  },
+  UnusedBacktick {
+    variable: Name<'src>,
+  },
+  UnusedParameter {
+    recipe:    &'src str,
+    parameter: Name<'src>,
+  },
+  UnusedPrivateRecipe {
+    recipe: Name<'src>,
+  },
+  UnusedVariable {
+    variable: Name<'src>,
+  },
 }
 
 impl<'src> Warning<'src> {
-  fn context(&self) -> Option<&Token<'src>> {
+  pub(crate) fn context(&self) -> Option<Token<'src>> {
     match self {
-      DeprecatedEquals { equals } => Some(equals),
+      DeprecatedEquals { equals } => Some(*equals),
+      EnvVarWithoutDefault { function, .. } => Some(function.token()),
+      IgnoredError { .. } => None,
+      SimilarAlias { alias, .. } => Some(alias.token()),
+      UnusedBacktick { variable } | UnusedVariable { variable } => Some(variable.token()),
+      UnusedParameter { parameter, .. } => Some(parameter.token()),
+      UnusedPrivateRecipe { recipe } => Some(recipe.token()),
     }
   }
-}
 
-impl Display for Warning<'_> {
-  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    let warning = Color::fmt(f).warning();
-    let message = Color::fmt(f).message();
+  /// The one-based number of the recipe line that the warning refers to, if
+  /// it doesn't refer to a location in the justfile source
+  pub(crate) fn line_number(&self) -> Option<usize> {
+    match self {
+      IgnoredError { line_number, .. } => Some(*line_number),
+      _ => None,
+    }
+  }
 
-    write!(f, "{} {}", warning.paint("warning:"), message.prefix())?;
+  /// A stable code identifying this kind of warning, used in diagnostics
+  /// printed with `--message-format json`
+  pub(crate) fn diagnostic_code(&self) -> &'static str {
+    match self {
+      DeprecatedEquals { .. } => "W0001",
+      IgnoredError { .. } => "W0002",
+      EnvVarWithoutDefault { .. } => "W0003",
+      SimilarAlias { .. } => "W0004",
+      UnusedBacktick { .. } => "W0005",
+      UnusedParameter { .. } => "W0006",
+      UnusedPrivateRecipe { .. } => "W0007",
+      UnusedVariable { .. } => "W0008",
+    }
+  }
 
+  /// The warning message, without source context
+  pub(crate) fn message(&self) -> String {
+    let mut message = String::new();
+    self
+      .write_message(&mut message)
+      .expect("writing to a string cannot fail");
+    message
+  }
+
+  fn write_message(&self, f: &mut dyn fmt::Write) -> fmt::Result {
     match self {
       DeprecatedEquals { .. } => {
         writeln!(
@@ -33,8 +93,67 @@ impl Display for Warning<'_> {
           "Please see this issue for more details: https://github.com/casey/just/issues/379"
         )?;
       },
+      EnvVarWithoutDefault { recipe, .. } => {
+        write!(
+          f,
+          "Recipe `{}` calls `env_var`, which fails if the variable is not set. Consider using \
+           `env_var_or_default`",
+          recipe
+        )?;
+      },
+      IgnoredError {
+        recipe,
+        line_number,
+        code,
+      } => {
+        write!(
+          f,
+          "Recipe `{}` failed on line {} with exit code {}, ignoring",
+          recipe, line_number, code
+        )?;
+      },
+      SimilarAlias { alias, similar } => {
+        write!(
+          f,
+          "Alias `{}` is nearly identical to `{}`, so a typo may run the wrong recipe",
+          alias, similar
+        )?;
+      },
+      UnusedBacktick { variable } => {
+        write!(
+          f,
+          "Variable `{}` contains a backtick that runs on every invocation, but no recipe uses it",
+          variable
+        )?;
+      },
+      UnusedParameter { recipe, parameter } => {
+        write!(
+          f,
+          "Parameter `{}` of recipe `{}` is never used",
+          parameter, recipe
+        )?;
+      },
+      UnusedPrivateRecipe { recipe } => {
+        write!(f, "Private recipe `{}` is never used", recipe)?;
+      },
+      UnusedVariable { variable } => {
+        write!(f, "Variable `{}` is never used", variable)?;
+      },
     }
 
+    Ok(())
+  }
+}
+
+impl Display for Warning<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let warning = Color::fmt(f).warning();
+    let message = Color::fmt(f).message();
+
+    write!(f, "{} {}", warning.paint("warning:"), message.prefix())?;
+
+    self.write_message(f)?;
+
     write!(f, "{}", message.suffix())?;
 
     if let Some(token) = self.context() {
@@ -0,0 +1,243 @@
+use crate::common::*;
+
+use crate::command_ext;
+
+use std::{process::Child, thread, time::SystemTime};
+
+/// How often watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Directories that are never watched
+const IGNORED_DIRECTORIES: &[&str] = &[".git"];
+
+/// Directories that are not watched if they are in the working directory
+const IGNORED_ROOT_DIRECTORIES: &[&str] = &["target"];
+
+/// Modification time and length of each watched file
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Reruns `just` whenever the justfile or watched files change
+pub(crate) struct Watcher<'search> {
+  color:    Color,
+  patterns: Vec<FilePattern>,
+  search:   &'search Search,
+}
+
+impl<'search> Watcher<'search> {
+  /// Watch files matching `patterns`, or every file in the working directory
+  /// if `patterns` is empty
+  pub(crate) fn new(color: Color, search: &'search Search, patterns: Vec<FilePattern>) -> Self {
+    Self {
+      color,
+      patterns,
+      search,
+    }
+  }
+
+  /// Run `just` with the current arguments, minus `--watch`, and rerun it when
+  /// files change, until interrupted
+  pub(crate) fn watch(&self) -> Result<(), i32> {
+    if let Err(error) = InterruptHandler::install() {
+      warn!("Failed to set CTRL-C handler: {}", error)
+    }
+
+    InterruptHandler::instance().defer();
+
+    let mut snapshot = self.snapshot();
+
+    loop {
+      let mut foreground = false;
+
+      let spawned = self.command().and_then(|mut command| {
+        foreground = Platform::new_process_group(&mut command);
+        command.spawn()
+      });
+
+      let mut child = match spawned {
+        Ok(child) => child,
+        Err(io_error) => {
+          if foreground {
+            Platform::restore_foreground().ok();
+          }
+          eprintln!("Failed to run just in watch mode: {}", io_error);
+          return Err(EXIT_FAILURE);
+        },
+      };
+
+      let changed = loop {
+        Self::check_interrupt(Some((&mut child, foreground)));
+
+        if let Ok(Some(exit_status)) = child.try_wait() {
+          if foreground {
+            Platform::restore_foreground().ok();
+
+            // the child was in the foreground of the terminal, so it, and not
+            // `just`, received `^C`
+            if exit_status.code() == Some(130)
+              || Platform::signal_from_exit_status(exit_status) == Some(libc::SIGINT)
+            {
+              InterruptHandler::instance().interrupt();
+            }
+          }
+
+          break None;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+
+        let current = self.snapshot();
+        if current != snapshot {
+          Self::stop(&mut child, foreground);
+          break Some(current);
+        }
+      };
+
+      let mut current = if let Some(current) = changed {
+        current
+      } else {
+        loop {
+          Self::check_interrupt(None);
+
+          thread::sleep(POLL_INTERVAL);
+
+          let current = self.snapshot();
+          if current != snapshot {
+            break current;
+          }
+        }
+      };
+
+      // wait for changes to settle, so that a burst of changes causes one rerun
+      loop {
+        Self::check_interrupt(None);
+
+        thread::sleep(POLL_INTERVAL);
+
+        let next = self.snapshot();
+        if next == current {
+          break;
+        }
+        current = next;
+      }
+
+      snapshot = current;
+
+      let color = self.color.stderr().banner();
+      eprintln!(
+        "{}===> Files changed, running again...{}",
+        color.prefix(),
+        color.suffix()
+      );
+    }
+  }
+
+  /// Construct a command that runs `just` with the same arguments as the
+  /// current invocation, minus `--watch`
+  fn command(&self) -> io::Result<Command> {
+    let mut command = Command::new(env::current_exe()?);
+
+    command.args(env::args_os().skip(1).filter(|argument| {
+      let argument = argument.to_string_lossy();
+      argument != "--watch" && !argument.starts_with("--watch=")
+    }));
+
+    Ok(command)
+  }
+
+  /// If an interrupt was received, stop `child` and exit
+  fn check_interrupt(child: Option<(&mut Child, bool)>) {
+    if !InterruptHandler::instance().take_interrupted() {
+      return;
+    }
+
+    if let Some((child, foreground)) = child {
+      Self::stop(child, foreground);
+    }
+
+    InterruptHandler::instance().undefer(true);
+  }
+
+  /// Terminate `child`, and if its process group was put in the foreground of
+  /// the terminal, which `foreground` indicates, put `just` back
+  fn stop(child: &mut Child, foreground: bool) {
+    command_ext::terminate(child).ok();
+
+    if foreground {
+      Platform::restore_foreground().ok();
+    }
+  }
+
+  fn snapshot(&self) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    Self::insert(&mut snapshot, &self.search.justfile);
+
+    self.walk(
+      &mut snapshot,
+      &self.search.working_directory,
+      &mut Vec::new(),
+    );
+
+    snapshot
+  }
+
+  /// Insert every watched file in `directory`, whose path relative to the
+  /// working directory is `components`, into `snapshot`
+  fn walk(&self, snapshot: &mut Snapshot, directory: &Path, components: &mut Vec<String>) {
+    let entries = match fs::read_dir(directory) {
+      Ok(entries) => entries,
+      Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+      let name = match entry.file_name().into_string() {
+        Ok(name) => name,
+        Err(_) => continue,
+      };
+
+      let is_dir = entry
+        .file_type()
+        .map(|file_type| file_type.is_dir())
+        .unwrap_or(false);
+
+      if is_dir
+        && (IGNORED_DIRECTORIES.contains(&name.as_str())
+          || (components.is_empty() && IGNORED_ROOT_DIRECTORIES.contains(&name.as_str())))
+      {
+        continue;
+      }
+
+      components.push(name);
+
+      if is_dir {
+        self.walk(snapshot, &entry.path(), components);
+      } else if self.is_watched(components) {
+        Self::insert(snapshot, &entry.path());
+      }
+
+      components.pop();
+    }
+  }
+
+  fn is_watched(&self, components: &[String]) -> bool {
+    if self.patterns.is_empty() {
+      return true;
+    }
+
+    let components = components.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    self
+      .patterns
+      .iter()
+      .any(|pattern| pattern.matches(&components))
+  }
+
+  fn insert(snapshot: &mut Snapshot, path: &Path) {
+    if let Ok(metadata) = fs::metadata(path) {
+      snapshot.insert(
+        path.to_owned(),
+        (metadata.modified().ok(), metadata.len()),
+      );
+    }
+  }
+}
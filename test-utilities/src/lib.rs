@@ -1,4 +1,12 @@
-use std::{collections::HashMap, fs, path::Path, process::Output};
+use std::{
+  collections::HashMap,
+  ffi::OsStr,
+  fs,
+  path::Path,
+  process::{Command, Output},
+};
+
+use executable_path::executable_path;
 
 pub fn tempdir() -> tempfile::TempDir {
   tempfile::Builder::new()
@@ -20,6 +28,39 @@ pub fn assert_stdout(output: &Output, stdout: &str) {
   assert_eq!(String::from_utf8_lossy(&output.stdout), stdout);
 }
 
+/// Run `just` in `dir` with `args`, returning the exit status, stdout, and
+/// stderr
+pub fn just(dir: &Path, args: &[&str]) -> (i32, String, String) {
+  just_with_env(dir, &[], args)
+}
+
+/// Like `just`, but with each variable in `env` set to its value in the
+/// environment, or removed from the environment if its value is `None`
+pub fn just_with_env(
+  dir: &Path,
+  env: &[(&str, Option<&OsStr>)],
+  args: &[&str],
+) -> (i32, String, String) {
+  let mut command = Command::new(executable_path("just"));
+
+  command.current_dir(dir).args(args);
+
+  for (key, value) in env {
+    match value {
+      Some(value) => command.env(key, value),
+      None => command.env_remove(key),
+    };
+  }
+
+  let output = command.output().expect("just invocation failed");
+
+  (
+    output.status.code().expect("just terminated by signal"),
+    String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+    String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+  )
+}
+
 pub fn unindent(text: &str) -> String {
   // find line start and end indices
   let mut lines = Vec::new();
@@ -0,0 +1,175 @@
+use std::{
+  fs,
+  io::Write,
+  process::{Command, Output, Stdio},
+  str,
+};
+
+use executable_path::executable_path;
+use which::which;
+
+use test_utilities::tmptree;
+
+const JUSTFILE: &str = "
+# build the project
+build mode='debug':
+  echo build {{mode}}
+
+test +flags:
+  echo test {{flags}}
+
+deploy target:
+  echo deploy {{target}}
+
+_private:
+  echo private
+";
+
+/// Run `just --choose` in `dir` with `stdin`, and `$JUST_CHOOSER` set to
+/// `chooser`, or with no chooser on the `PATH` if `chooser` is `None`
+fn choose(dir: &tempfile::TempDir, chooser: Option<&str>, stdin: &str) -> Output {
+  let mut command = Command::new(executable_path("just"));
+
+  match chooser {
+    Some(chooser) => {
+      command.env("JUST_CHOOSER", chooser);
+    },
+    None => {
+      command
+        .env_remove("JUST_CHOOSER")
+        .env("PATH", "")
+        .arg("--shell")
+        .arg(which("sh").unwrap());
+    },
+  }
+
+  let mut child = command
+    .current_dir(dir.path())
+    .arg("--choose")
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+
+  child
+    .stdin
+    .take()
+    .unwrap()
+    .write_all(stdin.as_bytes())
+    .unwrap();
+
+  child.wait_with_output().unwrap()
+}
+
+#[test]
+fn chooser_receives_public_recipes_with_docs() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, Some("cat > recipes.txt && echo build"), "");
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "build debug\n");
+
+  let recipes = fs::read_to_string(tmp.path().join("recipes.txt")).unwrap();
+
+  assert_eq!(
+    recipes,
+    "build mode='debug' # build the project\ndeploy target\ntest +flags\n"
+  );
+}
+
+#[test]
+fn chooser_output_is_run_in_order() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, Some("grep -e build -e deploy | sort -r"), "production\n");
+
+  assert!(output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stdout).unwrap(),
+    "deploy production\nbuild debug\n"
+  );
+}
+
+#[test]
+fn optional_and_variadic_parameters_do_not_swallow_recipes() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, Some("grep -e build -e test"), "--release --all\n");
+
+  assert!(output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stdout).unwrap(),
+    "build debug\ntest --release --all\n"
+  );
+}
+
+#[test]
+fn chooser_failure() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, Some("exit 130"), "");
+
+  assert_eq!(output.status.code(), Some(130));
+  assert_eq!(output.stdout, b"");
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "Chooser `exit 130` failed: exit status: 130\n"
+  );
+}
+
+#[test]
+fn prompt_without_chooser() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, None, "3 build\n-v\n");
+
+  assert!(output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stdout).unwrap(),
+    "test -v\nbuild debug\n"
+  );
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .starts_with("1) build mode='debug' # build the project\n2) deploy target\n3) test +flags\n"));
+}
+
+#[test]
+fn prompt_filter() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, None, "PROJECT\n1\n");
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "build debug\n");
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .contains("filter: 1) build mode='debug' # build the project\nChoose"));
+}
+
+#[test]
+fn prompt_nothing_chosen() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = choose(&tmp, None, "\n");
+
+  assert_eq!(output.status.code(), Some(1));
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .ends_with("No recipes chosen.\n"));
+}
@@ -1,8 +1,10 @@
-use std::process::Command;
+use std::{fs, process::Command, str};
 
 use executable_path::executable_path;
 use tempfile::tempdir;
 
+use test_utilities::tmptree;
+
 #[test]
 fn output() {
   let tempdir = tempdir().unwrap();
@@ -20,3 +22,125 @@ fn output() {
 
   assert!(text.starts_with("_just() {"));
 }
+
+#[test]
+fn complete_is_not_offered() {
+  for shell in &["bash", "elvish", "fish", "powershell", "zsh"] {
+    let output = Command::new(executable_path("just"))
+      .arg("--completions")
+      .arg(shell)
+      .output()
+      .unwrap();
+
+    assert!(output.status.success());
+
+    let text = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+      !text.contains("--complete)") && !text.contains("Print completions for the last of"),
+      "{} completion script offers `--complete`",
+      shell
+    );
+  }
+}
+
+const JUSTFILE: &str = "
+mode := 'debug'
+
+# build the project
+build:
+
+[choices('target', 'staging', 'production')]
+deploy target:
+
+[path('file')]
+lint file:
+
+alias b := build
+";
+
+/// Run `just --complete <shell> -- <words>` in `dir`, returning stdout
+fn complete(dir: &tempfile::TempDir, shell: &str, words: &[&str]) -> String {
+  let output = Command::new(executable_path("just"))
+    .current_dir(dir.path())
+    .arg("--complete")
+    .arg(shell)
+    .arg("--")
+    .args(words)
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  str::from_utf8(&output.stdout).unwrap().to_owned()
+}
+
+#[test]
+fn complete_recipes_with_descriptions() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert_eq!(
+    complete(&tmp, "fish", &[""]),
+    "b\talias for `build`\nbuild\tbuild the project\ndeploy\nlint\nmode=\tvariable\n"
+  );
+}
+
+#[test]
+fn complete_choices() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert_eq!(complete(&tmp, "bash", &["deploy", "s"]), "staging\n");
+}
+
+#[test]
+fn complete_paths() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+    src: {
+      "main.rs": "",
+    },
+    ".hidden": "",
+  };
+
+  assert_eq!(complete(&tmp, "bash", &["lint", ""]), "justfile\nsrc/\n");
+  assert_eq!(complete(&tmp, "bash", &["lint", "src/m"]), "src/main.rs\n");
+  assert_eq!(complete(&tmp, "bash", &["lint", ".h"]), ".hidden\n");
+}
+
+#[test]
+fn complete_options_are_parsed() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+    "other.just": "test:",
+  };
+
+  assert_eq!(
+    complete(&tmp, "bash", &["--dry-run", "--justfile", "other.just", ""]),
+    "test\n"
+  );
+}
+
+#[test]
+fn complete_show() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert_eq!(
+    complete(&tmp, "zsh", &["--show", "b"]),
+    "b:alias for `build`\nbuild:build the project\n"
+  );
+}
+
+#[test]
+fn complete_without_justfile() {
+  let tmp = tempdir().unwrap();
+
+  fs::write(tmp.path().join(".git"), "").unwrap();
+
+  assert_eq!(complete(&tmp, "bash", &[""]), "");
+}
@@ -0,0 +1,232 @@
+use std::{ffi::OsStr, path::Path};
+
+use test_utilities::{just, just_with_env, tmptree};
+
+#[test]
+fn runs_recipe_from_parent_justfile() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@pwd",
+    package: {
+      justfile: "set fallback\n\nlocal:\n\t@echo local",
+    },
+  };
+
+  let (status, stdout, stderr) = just(&tmp.path().join("package"), &["root"]);
+
+  assert_eq!(status, 0, "{}", stderr);
+  assert_eq!(
+    Path::new(stdout.trim_end()).canonicalize().unwrap(),
+    tmp.path().canonicalize().unwrap()
+  );
+}
+
+#[test]
+fn prefers_nearest_justfile() {
+  let tmp = tmptree! {
+    justfile: "build:\n\t@echo root",
+    package: {
+      justfile: "set fallback\n\nbuild:\n\t@echo package",
+    },
+  };
+
+  let (status, stdout, _) = just(&tmp.path().join("package"), &["build"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "package\n");
+}
+
+#[test]
+fn continues_while_fallback_is_set() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    a: {
+      justfile: "set fallback\n\na:\n\t@echo a",
+      b: {
+        justfile: "set fallback\n\nb:\n\t@echo b",
+      },
+    },
+  };
+
+  let (status, stdout, _) = just(&tmp.path().join("a/b"), &["root"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "root\n");
+}
+
+#[test]
+fn stops_without_fallback() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    a: {
+      justfile: "a:\n\t@echo a",
+      b: {
+        justfile: "set fallback\n\nb:\n\t@echo b",
+      },
+    },
+  };
+
+  let (status, stdout, stderr) = just(&tmp.path().join("a/b"), &["root"]);
+
+  assert_eq!(status, 1);
+  assert_eq!(stdout, "");
+  assert_eq!(stderr, "error: Justfile does not contain recipe `root`.\n");
+}
+
+#[test]
+fn disabled_by_default() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    package: {
+      justfile: "local:\n\t@echo local",
+    },
+  };
+
+  let (status, _, stderr) = just(&tmp.path().join("package"), &["root"]);
+
+  assert_eq!(status, 1);
+  assert_eq!(stderr, "error: Justfile does not contain recipe `root`.\n");
+}
+
+#[test]
+fn disabled_with_explicit_justfile() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    package: {
+      justfile: "set fallback\n\nlocal:\n\t@echo local",
+    },
+  };
+
+  let (status, _, stderr) = just(
+    &tmp.path().join("package"),
+    &["--justfile", "justfile", "root"],
+  );
+
+  assert_eq!(status, 1);
+  assert_eq!(stderr, "error: Justfile does not contain recipe `root`.\n");
+}
+
+#[test]
+fn not_found_in_any_justfile() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    package: {
+      justfile: "set fallback\n\nlocal:\n\t@echo local",
+    },
+  };
+
+  let (status, _, stderr) = just(&tmp.path().join("package"), &["missing"]);
+
+  assert_eq!(status, 1);
+  assert_eq!(stderr, "error: Justfile does not contain recipe `missing`.\n");
+}
+
+#[test]
+fn dry_run_prints_where_recipes_resolved() {
+  let tmp = tmptree! {
+    justfile: "root:\n\techo root",
+    package: {
+      justfile: "set fallback\n\nlocal:\n\techo local",
+    },
+  };
+
+  let (status, stdout, stderr) = just(&tmp.path().join("package"), &["--dry-run", "root"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "");
+  assert_eq!(
+    stderr,
+    format!(
+      "===> Recipe `root` not found in `{}`, trying `{}`...\necho root\n",
+      tmp.path().join("package/justfile").display(),
+      tmp.path().join("justfile").display(),
+    )
+  );
+}
+
+#[test]
+fn verbose_prints_where_recipes_resolved() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    package: {
+      justfile: "set fallback\n\nlocal:\n\t@echo local",
+    },
+  };
+
+  let (status, stdout, stderr) = just(&tmp.path().join("package"), &["--verbose", "root"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "root\n");
+  assert_eq!(
+    stderr,
+    format!(
+      "===> Recipe `root` not found in `{}`, trying `{}`...\n\
+       ===> Running recipe `root`...\n\
+       echo root\n",
+      tmp.path().join("package/justfile").display(),
+      tmp.path().join("justfile").display(),
+    )
+  );
+}
+
+#[test]
+fn child_assignments_not_evaluated() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    package: {
+      justfile: "set fallback\n\nside := `echo side-effect >&2`\n\nlocal:\n\t@echo {{side}}",
+    },
+  };
+
+  let (status, stdout, stderr) = just(&tmp.path().join("package"), &["root"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "root\n");
+  assert_eq!(stderr, "");
+}
+
+#[test]
+fn overrides_of_parent_variables() {
+  let tmp = tmptree! {
+    justfile: "v := '1'\n\nroot:\n\t@echo {{v}}",
+    package: {
+      justfile: "set fallback\n\nlocal:\n\t@echo local",
+    },
+  };
+
+  let package = tmp.path().join("package");
+
+  let (status, stdout, stderr) = just(&package, &["--set", "v", "2", "root"]);
+
+  assert_eq!(status, 0, "{}", stderr);
+  assert_eq!(stdout, "2\n");
+
+  let (status, stdout, stderr) = just(&package, &["v=3", "root"]);
+
+  assert_eq!(status, 0, "{}", stderr);
+  assert_eq!(stdout, "3\n");
+}
+
+#[test]
+fn stops_at_project_root() {
+  let tmp = tmptree! {
+    justfile: "root:\n\t@echo root",
+    project: {
+      ".git": {},
+      justfile: "set fallback\n\nlocal:\n\t@echo local",
+    },
+  };
+
+  let (status, _, stderr) = just_with_env(
+    &tmp.path().join("project"),
+    &[("JUST_SEARCH_STOP_AT_ROOT", Some(OsStr::new("1")))],
+    &["root"],
+  );
+
+  assert_eq!(status, 1);
+  assert_eq!(stderr, "error: Justfile does not contain recipe `root`.\n");
+
+  let (status, stdout, _) = just(&tmp.path().join("project"), &["root"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "root\n");
+}
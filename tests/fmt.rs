@@ -0,0 +1,95 @@
+use std::{fs, process::Command, str};
+
+use executable_path::executable_path;
+
+use test_utilities::tmptree;
+
+const UNFORMATTED: &str = "# build the project
+build   target='debug':   test
+\tcargo build {{target}}
+
+x = 'y' # a variable
+test:
+\tcargo test
+";
+
+const FORMATTED: &str = "# build the project
+build target='debug': test
+    cargo build {{target}}
+
+x := 'y' # a variable
+test:
+    cargo test
+";
+
+#[test]
+fn write() {
+  let tmp = tmptree! {
+    justfile: UNFORMATTED,
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .arg("--fmt")
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("justfile")).unwrap(),
+    FORMATTED
+  );
+}
+
+#[test]
+fn check_unformatted() {
+  let tmp = tmptree! {
+    justfile: UNFORMATTED,
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--fmt", "--check"])
+    .output()
+    .unwrap();
+
+  assert_eq!(output.status.code(), Some(1));
+
+  assert_eq!(
+    str::from_utf8(&output.stdout).unwrap(),
+    " # build the project
+-build   target='debug':   test
+-\tcargo build {{target}}
++build target='debug': test
++    cargo build {{target}}
+\x20
+-x = 'y' # a variable
++x := 'y' # a variable
+ test:
+-\tcargo test
++    cargo test
+"
+  );
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("justfile")).unwrap(),
+    UNFORMATTED
+  );
+}
+
+#[test]
+fn check_formatted() {
+  let tmp = tmptree! {
+    justfile: FORMATTED,
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--fmt", "--check"])
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+  assert_eq!(output.stdout, b"");
+}
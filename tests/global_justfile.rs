@@ -0,0 +1,262 @@
+use std::{fs, path::Path};
+
+use test_utilities::{just_with_env, tmptree};
+
+/// Run just in `dir`, with `home` as the home directory and `config` as
+/// `$XDG_CONFIG_HOME`, returning the exit status, stdout, and stderr
+fn just(dir: &Path, home: &Path, config: Option<&Path>, args: &[&str]) -> (i32, String, String) {
+  just_with_env(
+    dir,
+    &[
+      ("HOME", Some(home.as_os_str())),
+      ("XDG_CONFIG_HOME", config.map(Path::as_os_str)),
+    ],
+    args,
+  )
+}
+
+#[test]
+fn xdg_config_home() {
+  let tmp = tmptree! {
+    config: {
+      just: {
+        justfile: "default:\n\t@echo config",
+      },
+    },
+    home: {
+      ".justfile": "default:\n\t@echo home",
+    },
+    project: {},
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    Some(&tmp.path().join("config")),
+    &["--global-justfile"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "config\n");
+}
+
+#[test]
+fn home_config_directory() {
+  let tmp = tmptree! {
+    home: {
+      ".config": {
+        just: {
+          justfile: "default:\n\t@echo config",
+        },
+      },
+      ".justfile": "default:\n\t@echo home",
+    },
+    project: {},
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    None,
+    &["-g"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "config\n");
+}
+
+#[test]
+fn empty_xdg_config_home() {
+  let tmp = tmptree! {
+    home: {
+      ".config": {
+        just: {
+          justfile: "default:\n\t@echo config",
+        },
+      },
+    },
+    project: {},
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    Some(Path::new("")),
+    &["-g"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "config\n");
+}
+
+#[test]
+fn relative_xdg_config_home() {
+  let tmp = tmptree! {
+    home: {
+      ".config": {
+        just: {
+          justfile: "default:\n\t@echo config",
+        },
+      },
+    },
+    project: {
+      config: {
+        just: {
+          justfile: "default:\n\t@echo relative",
+        },
+      },
+    },
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    Some(Path::new("config")),
+    &["-g"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "config\n");
+}
+
+#[test]
+fn home_justfile() {
+  let tmp = tmptree! {
+    home: {
+      ".justfile": "default:\n\t@echo home",
+    },
+    project: {},
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    None,
+    &["-g"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "home\n");
+}
+
+#[test]
+fn working_directory_is_invocation_directory() {
+  let tmp = tmptree! {
+    home: {
+      ".justfile": "default:\n\t@pwd",
+    },
+    project: {
+      justfile: "default:\n\t@echo project",
+    },
+  };
+
+  let project = tmp.path().join("project");
+
+  let (status, stdout, _) = just(&project, &tmp.path().join("home"), None, &["-g"]);
+
+  assert_eq!(status, 0);
+  assert_eq!(
+    Path::new(stdout.trim_end()).canonicalize().unwrap(),
+    project.canonicalize().unwrap()
+  );
+}
+
+#[test]
+fn not_found() {
+  let tmp = tmptree! {
+    home: {},
+  };
+
+  let home = tmp.path().join("home");
+
+  let (status, stdout, stderr) = just(tmp.path(), &home, None, &["-g"]);
+
+  assert_eq!(status, 1);
+  assert_eq!(stdout, "");
+  assert_eq!(
+    stderr,
+    format!(
+      "error: No global justfile found, looked for `{}` or `{}`\n",
+      home.join(".config/just/justfile").display(),
+      home.join(".justfile").display(),
+    )
+  );
+}
+
+#[test]
+fn fallback() {
+  let tmp = tmptree! {
+    home: {
+      ".justfile": "default:\n\t@echo home",
+    },
+    project: {},
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    None,
+    &["--global-fallback"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "home\n");
+}
+
+#[test]
+fn fallback_not_used_when_justfile_found() {
+  let tmp = tmptree! {
+    home: {
+      ".justfile": "default:\n\t@echo home",
+    },
+    project: {
+      justfile: "default:\n\t@echo project",
+    },
+  };
+
+  let (status, stdout, _) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    None,
+    &["--global-fallback"],
+  );
+
+  assert_eq!(status, 0);
+  assert_eq!(stdout, "project\n");
+}
+
+#[test]
+fn no_fallback_by_default() {
+  let tmp = tmptree! {
+    home: {
+      ".justfile": "default:\n\t@echo home",
+    },
+    project: {},
+  };
+
+  let (status, _, stderr) = just(
+    &tmp.path().join("project"),
+    &tmp.path().join("home"),
+    None,
+    &[],
+  );
+
+  assert_eq!(status, 1);
+  assert_eq!(stderr, "error: No justfile found\n");
+}
+
+#[test]
+fn init() {
+  let tmp = tmptree! {
+    home: {},
+    project: {},
+  };
+
+  let home = tmp.path().join("home");
+
+  let (status, _, _) = just(&tmp.path().join("project"), &home, None, &["-g", "--init"]);
+
+  assert_eq!(status, 0);
+  assert!(fs::read_to_string(home.join(".justfile")).is_ok());
+  assert!(!tmp.path().join("project/justfile").exists());
+}
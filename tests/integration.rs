@@ -245,6 +245,11 @@ test! {
   name: duplicate_alias,
   justfile: "alias foo := bar\nalias foo := baz\n",
   stderr: "
+    error: Alias `foo` has an unknown target `bar`
+      |
+    1 | alias foo := bar
+      |       ^^^
+
     error: Alias `foo` first defined on line 1 is redefined on line 2
       |
     2 | alias foo := baz
@@ -253,6 +258,467 @@ test! {
   status: EXIT_FAILURE,
 }
 
+test! {
+  name: multiple_parse_errors,
+  justfile: "
+    a: (b +)
+    c := )
+    d:
+      echo {{ ) }}
+      echo ok
+    e f g )
+    h:
+  ",
+  stderr: "
+    error: Expected backtick, identifier, '(', cooked string, or raw string, but found '+'
+      |
+    1 | a: (b +)
+      |       ^
+
+    error: Expected backtick, identifier, '(', cooked string, or raw string, but found ')'
+      |
+    2 | c := )
+      |      ^
+
+    error: Expected backtick, identifier, '(', cooked string, or raw string, but found ')'
+      |
+    4 |   echo {{ ) }}
+      |           ^
+
+    error: Expected ':', '=', identifier, or '+', but found ')'
+      |
+    6 | e f g )
+      |       ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: multiple_analysis_errors,
+  justfile: "
+    x := y
+    a: b c
+  ",
+  stderr: "
+    error: Variable `y` not defined
+      |
+    1 | x := y
+      |      ^
+
+    error: Recipe `a` has unknown dependency `b`
+      |
+    2 | a: b c
+      |    ^
+
+    error: Recipe `a` has unknown dependency `c`
+      |
+    2 | a: b c
+      |      ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: unknown_dependency_does_not_hide_other_errors,
+  justfile: "
+    a: b
+      echo {{ x }}
+    alias c := d
+  ",
+  stderr: "
+    error: Recipe `a` has unknown dependency `b`
+      |
+    1 | a: b
+      |    ^
+
+    error: Variable `x` not defined
+      |
+    2 |   echo {{ x }}
+      |           ^
+
+    error: Alias `c` has an unknown target `d`
+      |
+    3 | alias c := d
+      |       ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: multiple_circular_dependencies,
+  justfile: "
+    a: b
+    b: a
+    c: d
+    d: c
+  ",
+  stderr: "
+    error: Recipe `b` has circular dependency `a -> b -> a`
+      |
+    2 | b: a
+      |    ^
+
+    error: Recipe `d` has circular dependency `c -> d -> c`
+      |
+    4 | d: c
+      |    ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: multiple_errors_in_recipe,
+  justfile: "
+    a x='1' y x:
+  ",
+  stderr: "
+    error: Non-default parameter `y` follows default parameter
+      |
+    1 | a x='1' y x:
+      |         ^
+
+    error: Recipe `a` has duplicate parameter `x`
+      |
+    1 | a x='1' y x:
+      |           ^
+
+    error: Non-default parameter `x` follows default parameter
+      |
+    1 | a x='1' y x:
+      |           ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: parse_and_analysis_errors,
+  justfile: "
+    x := )
+    a: b
+    c y y:
+  ",
+  stderr: "
+    error: Expected backtick, identifier, '(', cooked string, or raw string, but found ')'
+      |
+    1 | x := )
+      |      ^
+
+    error: Recipe `c` has duplicate parameter `y`
+      |
+    3 | c y y:
+      |     ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+#[test]
+fn omitted_errors() {
+  let justfile = format!("a{}:\n", " x".repeat(41));
+
+  let mut stderr = String::new();
+
+  for i in 0..32 {
+    if i > 0 {
+      stderr.push('\n');
+    }
+    stderr.push_str(&format!(
+      "error: Recipe `a` has duplicate parameter `x`\n  |\n1 | {}  | {}^\n",
+      justfile,
+      " ".repeat(4 + i * 2),
+    ));
+  }
+
+  stderr.push_str("\n8 more errors omitted\n");
+
+  Test {
+    justfile: &justfile,
+    stderr: &stderr,
+    status: EXIT_FAILURE,
+    ..Test::default()
+  }
+  .run();
+}
+
+test! {
+  name: lint,
+  justfile: "
+    x := 'y'
+
+    foo bar:
+      echo foo
+  ",
+  args: ("--lint"),
+  stderr: "
+    warning: Variable `x` is never used
+      |
+    1 | x := 'y'
+      | ^
+    warning: Parameter `bar` of recipe `foo` is never used
+      |
+    3 | foo bar:
+      |     ^^^
+  ",
+}
+
+test! {
+  name: lint_deny_warnings,
+  justfile: "
+    [allow('unused-variables')]
+    x := 'y'
+
+    foo bar:
+      echo foo
+  ",
+  args: ("--lint", "--deny", "warnings"),
+  stderr: "
+    warning: Parameter `bar` of recipe `foo` is never used
+      |
+    4 | foo bar:
+      |     ^^^
+    Denying 1 warning because of `--deny warnings`
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: graph,
+  justfile: "
+    foo: (bar 'x')
+
+    bar a:
+
+    baz:
+  ",
+  args: ("--graph", "foo", "--graph-format", "mermaid"),
+  stdout: r#"
+    flowchart TD
+      recipe0["bar a"]
+      recipe1["foo"]
+      recipe1 -->|"'x'"| recipe0
+  "#,
+}
+
+test! {
+  name: graph_unknown_recipe,
+  justfile: "
+    foo:
+  ",
+  args: ("--graph", "fo"),
+  stderr: "
+    Justfile does not contain recipe `fo`.
+    Did you mean `foo`?
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: plan,
+  justfile: "
+    build mode='debug': (test mode) setup
+      echo {{mode}}
+
+    test mode +flags='':
+      echo test
+
+    deploy: (build 'release') setup (test 'release')
+
+    setup:
+  ",
+  args: ("--plan", "setup", "deploy"),
+  stdout: r#"
+    setup                             # requested
+        test mode="release" flags=""  # dependency of `build`
+        setup                         # dependency of `build`, skipped because it already ran
+      build mode="release"            # dependency of `deploy`
+      setup                           # dependency of `deploy`, skipped because it already ran
+      test mode="release" flags=""    # dependency of `deploy`, skipped because it already ran
+    deploy                            # requested
+  "#,
+}
+
+test! {
+  name: plan_hooks,
+  justfile: "
+    set before := 'before'
+    set after := 'after'
+
+    [finally('clean')]
+    foo a b='x':
+
+    clean:
+
+    before:
+
+    after:
+  ",
+  args: ("--plan", "foo", "1", "y", "foo", "2", "3"),
+  stdout: r#"
+    before           # `before` hook
+    foo a="1" b="y"  # requested
+    clean            # `finally` recipe of `foo`
+    foo a="2" b="3"  # requested
+    clean            # `finally` recipe of `foo`
+    after            # `after` hook
+  "#,
+}
+
+test! {
+  name: plan_does_not_run_anything,
+  justfile: "
+    x := `exit 1`
+
+    foo y=x:
+      exit 1
+  ",
+  args: ("--plan"),
+  stdout: r#"
+    foo y="`exit 1`"  # requested
+  "#,
+}
+
+test! {
+  name: plan_unknown_recipe,
+  justfile: "
+    foo:
+  ",
+  args: ("--plan", "bar"),
+  stderr: "
+    error: Justfile does not contain recipe `bar`.
+  ",
+  status: EXIT_FAILURE,
+}
+
+const GROUPS_JUSTFILE: &str = r#"
+  [group('test')]
+  test:
+
+  # deploy to production
+  [group('release')]
+  [group('deploy')]
+  deploy:
+
+  build:
+
+  [group('release')]
+  package:
+
+  _private:
+"#;
+
+test! {
+  name: list_groups,
+  justfile: GROUPS_JUSTFILE,
+  args: ("--list"),
+  stdout: "
+    Available recipes:
+        build
+
+        [deploy]
+        deploy  # deploy to production
+
+        [release]
+        deploy  # deploy to production
+        package
+
+        [test]
+        test
+  ",
+}
+
+test! {
+  name: list_groups_unsorted,
+  justfile: GROUPS_JUSTFILE,
+  args: ("--list", "--unsorted"),
+  stdout: "
+    Available recipes:
+        build
+
+        [test]
+        test
+
+        [release]
+        deploy  # deploy to production
+        package
+
+        [deploy]
+        deploy  # deploy to production
+  ",
+}
+
+test! {
+  name: list_unsorted,
+  justfile: "
+    b:
+    c:
+    a:
+  ",
+  args: ("--list", "-u"),
+  stdout: "
+    Available recipes:
+        b
+        c
+        a
+  ",
+}
+
+test! {
+  name: list_heading_and_prefix,
+  justfile: "
+    a:
+    b:
+  ",
+  args: ("--list", "--list-heading", "Recipes:\n", "--list-prefix", "- "),
+  stdout: "
+    Recipes:
+    - a
+    - b
+  ",
+}
+
+test! {
+  name: groups,
+  justfile: GROUPS_JUSTFILE,
+  args: ("--groups"),
+  stdout: "
+    Recipe groups:
+        deploy
+        release
+        test
+  ",
+}
+
+test! {
+  name: groups_unsorted,
+  justfile: GROUPS_JUSTFILE,
+  args: ("--groups", "--unsorted"),
+  stdout: "
+    Recipe groups:
+        test
+        release
+        deploy
+  ",
+}
+
+test! {
+  name: deny_warnings_without_lint,
+  justfile: "
+    x = 'y'
+
+    foo:
+      echo {{x}}
+  ",
+  args: ("--deny", "warnings"),
+  stderr: "
+    warning: `=` in assignments, exports, and aliases is being phased out on favor of `:=`
+    Please see this issue for more details: https://github.com/casey/just/issues/379
+      |
+    1 | x = 'y'
+      |   ^
+    Denying 1 warning because of `--deny warnings`
+  ",
+  status: EXIT_FAILURE,
+}
+
 test! {
   name: unknown_alias_target,
   justfile: "alias foo := bar\n",
@@ -614,7 +1080,11 @@ test! {
   justfile: "foo:
  echo hello
  echo {{`exit 111`}}
-a := `exit 222`",
+a := `exit 222`
+
+a:
+
+b:",
   args:     ("--set", "foo", "bar", "--set", "baz", "bob", "--set", "a", "b", "a", "b"),
   stderr:   "error: Variables `baz` and `foo` overridden on the command line but not present \
     in justfile\n",
@@ -626,7 +1096,11 @@ test! {
   justfile: "foo:
  echo hello
  echo {{`exit 111`}}
-a := `exit 222`",
+a := `exit 222`
+
+a:
+
+b:",
   args:     ("foo=bar", "baz=bob", "a=b", "a", "b"),
   stderr:   "error: Variables `baz` and `foo` overridden on the command line but not present \
     in justfile\n",
@@ -638,20 +1112,35 @@ test! {
   justfile: "foo:
  echo hello
  echo {{`exit 111`}}
-a := `exit 222`",
+a := `exit 222`
+
+a:
+
+b:",
   args:     ("foo=bar", "a=b", "a", "b"),
   stderr:   "error: Variable `foo` overridden on the command line but not present in justfile\n",
   status:   EXIT_FAILURE,
 }
 
 test! {
-  name:     overrides_first,
-  justfile: r#"
-foo := "foo"
-a := "a"
-baz := "baz"
-
-recipe arg:
+  name:     unknown_recipes_reported_before_unknown_overrides,
+  justfile: "foo:
+ echo hello
+ echo {{`exit 111`}}
+a := `exit 222`",
+  args:     ("foo=bar", "a=b", "a", "b"),
+  stderr:   "error: Justfile does not contain recipes `a` or `b`.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     overrides_first,
+  justfile: r#"
+foo := "foo"
+a := "a"
+baz := "baz"
+
+recipe arg:
  echo arg={{arg}}
  echo {{foo + a + baz}}"#,
   args:     ("foo=bar", "a=b", "recipe", "baz=bar"),
@@ -1030,6 +1519,24 @@ recipe a b +d:
 ",
 }
 
+test! {
+  name:     dump_json,
+  justfile: r#"
+alias r := recipe
+
+x := 'y'
+
+# this recipe does something
+recipe a b='c' +d='e': other
+ @exit 100
+
+other:
+"#,
+  args:     ("--dump", "--format", "json"),
+  stdout:   r#"{"version":1,"aliases":{"r":{"name":"r","target":"recipe","private":false}},"assignments":{"x":{"name":"x","export":false,"value":"'y'"}},"first":"recipe","recipes":{"other":{"name":"other","doc":null,"attributes":[],"parameters":[],"dependencies":[],"body":[],"private":false,"quiet":false,"shebang":false},"recipe":{"name":"recipe","doc":"this recipe does something","attributes":[],"parameters":[{"name":"a","doc":null,"default":null,"variadic":false},{"name":"b","doc":null,"default":"'c'","variadic":false},{"name":"d","doc":null,"default":"'e'","variadic":true}],"dependencies":[{"recipe":"other","arguments":[]}],"body":["@exit 100"],"private":false,"quiet":false,"shebang":false}},"settings":{"after":null,"before":null,"fallback":false,"shell":null}}
+"#,
+}
+
 test! {
   name:     mixed_whitespace,
   justfile: "bar:\n\t echo hello",
@@ -1240,6 +1747,178 @@ a Z="\t z":
   status:   EXIT_FAILURE,
 }
 
+test! {
+  name:     usage,
+  justfile: "
+    # build the project
+    build mode='debug': clean
+      echo {{mode}}
+
+    clean:
+
+    alias b := build
+  ",
+  args:     ("--usage", "b"),
+  stdout:   "
+    build the project
+
+    Usage: just build [mode]
+
+    Arguments:
+      [mode]  [default: 'debug']
+
+    Example:
+      just build
+
+    Aliases: b
+
+    Dependencies: clean
+  ",
+}
+
+test! {
+  name:     usage_recipe_help,
+  justfile: "
+    deploy target:
+      echo {{target}}
+  ",
+  args:     ("deploy", "--help"),
+  stdout:   "
+    Usage: just deploy <target>
+
+    Arguments:
+      <target>
+
+    Example:
+      just deploy target
+  ",
+}
+
+test! {
+  name:     doc_multiple_lines_list,
+  justfile: "
+    # build the project
+    #
+    # builds in debug mode by default
+    build mode='debug':
+
+    [doc('test the project')]
+    test:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        build mode='debug' # build the project
+        test               # test the project
+  ",
+}
+
+test! {
+  name:     doc_multiple_lines_show,
+  justfile: "
+    # build the project
+    #
+    # builds in debug mode by default
+    build mode='debug':
+  ",
+  args:     ("--show", "build"),
+  stdout:   "
+    # build the project
+    #
+    # builds in debug mode by default
+    build mode='debug':
+  ",
+}
+
+test! {
+  name:     doc_attribute_show,
+  justfile: "
+    # ignored
+    [doc('build the project')]
+    [doc('mode', 'debug or release')]
+    build mode:
+  ",
+  args:     ("--show", "build"),
+  stdout:   r#"
+    [doc('build the project')]
+    [doc("mode", 'debug or release')]
+    build mode:
+  "#,
+}
+
+test! {
+  name:     doc_parameter_usage,
+  justfile: "
+    # build the project
+    #
+    # builds in debug mode by default
+    [doc('mode', 'debug or release')]
+    build mode='debug':
+  ",
+  args:     ("--usage", "build"),
+  stdout:   "
+    build the project
+
+    builds in debug mode by default
+
+    Usage: just build [mode]
+
+    Arguments:
+      [mode]  debug or release [default: 'debug']
+
+    Example:
+      just build
+  ",
+}
+
+test! {
+  name:     docs,
+  justfile: "
+    export TOKEN := 'secret'
+
+    # build the project
+    build: clean
+
+    clean:
+
+    _helper:
+  ",
+  args:     ("--docs"),
+  stdout:   "
+    ## Recipes
+
+    ### `build`
+
+    build the project
+
+    - Signature: `build`
+    - Dependencies: `clean`
+    - Environment variables: `TOKEN`
+
+    ### `clean`
+
+    - Signature: `clean`
+    - Environment variables: `TOKEN`
+
+    ## Variables
+
+    ```just
+    export TOKEN := 'secret'
+    ```
+  ",
+}
+
+test! {
+  name:     usage_suggestion,
+  justfile: "
+    hello:
+  ",
+  args:     ("hell", "--help"),
+  stdout:   "",
+  stderr:   "Justfile does not contain recipe `hell`.\nDid you mean `hello`?\n",
+  status:   EXIT_FAILURE,
+}
+
 test! {
   name:     show_no_suggestion,
   justfile: r#"
@@ -2503,3 +3182,615 @@ test! {
   stderr: "",
   shell: false,
 }
+
+test! {
+  name: timeout_attribute,
+  justfile: r#"
+    [timeout("100ms")]
+    foo:
+      echo before
+      sleep 10
+      echo after
+  "#,
+  args: ("foo"),
+  stdout: "before\n",
+  stderr: "
+    echo before
+    sleep 10
+    error: Recipe `foo` timed out on line 4 after 100ms
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: timeout_attribute_not_reached,
+  justfile: r#"
+    # print a greeting
+    [timeout("1m")]
+    foo:
+      echo hello
+  "#,
+  args: ("foo"),
+  stdout: "hello\n",
+  stderr: "echo hello\n",
+}
+
+test! {
+  name: timeout_attribute_shebang,
+  justfile: r#"
+    [timeout("100ms")]
+    foo:
+      #!/usr/bin/env bash
+      exec sleep 10
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: "error: Recipe `foo` timed out after 100ms\n",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: timeout_flag,
+  justfile: "
+    foo:
+      sleep 10
+  ",
+  args: ("--timeout", "100ms", "foo"),
+  stdout: "",
+  stderr: "
+    sleep 10
+    error: Recipe `foo` timed out on line 2 after 100ms
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: timeout_attribute_overrides_flag,
+  justfile: r#"
+    [timeout("1m")]
+    foo:
+      sleep 0.5
+  "#,
+  args: ("--timeout", "100ms", "foo"),
+  stdout: "",
+  stderr: "sleep 0.5\n",
+}
+
+test! {
+  name: timeout_applies_to_whole_recipe,
+  justfile: r#"
+    [timeout("300ms")]
+    foo:
+      sleep 0.2
+      sleep 0.2
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: "
+    sleep 0.2
+    sleep 0.2
+    error: Recipe `foo` timed out on line 4 after 300ms
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: timeout_invalid_duration,
+  justfile: r#"
+    [timeout("soon")]
+    foo:
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: r#"
+    error: Invalid duration `soon`, durations are an integer followed by `ms`, `s`, `m`, or `h`
+      |
+    1 | [timeout("soon")]
+      |          ^^^^^^
+  "#,
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: retry_attribute_succeeds,
+  justfile: r#"
+    [retry(3)]
+    foo:
+      @echo x >> count; test $(wc -l < count) -ge 3
+      @echo done
+  "#,
+  args: ("foo"),
+  stdout: "done\n",
+  stderr: "",
+}
+
+test! {
+  name: retry_attribute_exhausted,
+  justfile: r#"
+    [retry("2")]
+    foo:
+      @exit 3
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: "error: Recipe `foo` failed on line 3 with exit code 3 after 3 attempts\n",
+  status: 3,
+}
+
+test! {
+  name: retry_attribute_verbose,
+  justfile: r#"
+    [retry(2, delay="10ms", backoff="exponential")]
+    foo:
+      @exit 1
+  "#,
+  args: ("--verbose", "foo"),
+  stdout: "",
+  stderr: "
+    ===> Running recipe `foo`...
+    exit 1
+    ===> Recipe `foo` failed on line 3 with exit code 1, retrying in 10ms (attempt 2 of 3)...
+    ===> Recipe `foo` failed on line 3 with exit code 1, retrying in 20ms (attempt 3 of 3)...
+    error: Recipe `foo` failed on line 3 with exit code 1 after 3 attempts
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: retry_attribute_shebang,
+  justfile: r#"
+    [retry("1")]
+    foo:
+      #!/usr/bin/env sh
+      echo x >> count
+      test $(wc -l < count) -ge 2
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: "",
+}
+
+test! {
+  name: retry_attribute_only_failing_line,
+  justfile: r#"
+    [retry("1")]
+    foo:
+      @echo first
+      @echo x >> count; test $(wc -l < count) -ge 2
+  "#,
+  args: ("foo"),
+  stdout: "first\n",
+  stderr: "",
+}
+
+test! {
+  name: retry_attribute_not_on_timeout,
+  justfile: r#"
+    [retry("3")]
+    [timeout("100ms")]
+    foo:
+      @sleep 10
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: "error: Recipe `foo` timed out on line 4 after 100ms\n",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: retry_attribute_invalid_count,
+  justfile: r#"
+    [retry("many")]
+    foo:
+  "#,
+  args: ("foo"),
+  stdout: "",
+  stderr: r#"
+    error: Invalid argument `many` to attribute `retry`, expected a number of retries
+      |
+    1 | [retry("many")]
+      |        ^^^^^^
+  "#,
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: infallible_line,
+  justfile: "
+    foo:
+      -exit 2
+      echo after
+  ",
+  args: ("foo"),
+  stdout: "after\n",
+  stderr: "
+    exit 2
+    warning: Recipe `foo` failed on line 2 with exit code 2, ignoring
+    echo after
+  ",
+}
+
+test! {
+  name: infallible_quiet_line,
+  justfile: "
+    foo:
+      -@exit 1
+      @-exit 1
+      @echo after
+  ",
+  args: ("foo"),
+  stdout: "after\n",
+  stderr: "
+    warning: Recipe `foo` failed on line 2 with exit code 1, ignoring
+    warning: Recipe `foo` failed on line 3 with exit code 1, ignoring
+  ",
+}
+
+test! {
+  name: infallible_line_success,
+  justfile: "
+    foo:
+      -@echo ok
+  ",
+  args: ("foo"),
+  stdout: "ok\n",
+  stderr: "",
+}
+
+test! {
+  name: infallible_line_dry_run,
+  justfile: "
+    foo:
+      -exit 1
+      @-exit 1
+  ",
+  args: ("--dry-run", "foo"),
+  stdout: "",
+  stderr: "
+    exit 1
+    exit 1
+  ",
+}
+
+test! {
+  name: infallible_line_dump,
+  justfile: "
+    foo:
+      -exit 1
+      -@exit 1
+      @-exit 1
+  ",
+  args: ("--dump"),
+  stdout: "
+    foo:
+        -exit 1
+        -@exit 1
+        @-exit 1
+  ",
+}
+
+test! {
+  name: infallible_line_quiet_flag,
+  justfile: "
+    foo:
+      -exit 1
+  ",
+  args: ("--quiet", "foo"),
+  stdout: "",
+  stderr: "",
+}
+
+test! {
+  name: finally_attribute_success,
+  justfile: r#"
+    [finally("teardown")]
+    test:
+      echo test
+
+    teardown:
+      echo teardown
+  "#,
+  args: ("test"),
+  stdout: "test\nteardown\n",
+  stderr: "echo test\necho teardown\n",
+}
+
+test! {
+  name: finally_attribute_failure,
+  justfile: r#"
+    [finally("teardown")]
+    test:
+      echo before
+      exit 3
+      echo after
+
+    teardown:
+      echo teardown
+  "#,
+  args: ("test"),
+  stdout: "before\nteardown\n",
+  stderr: "
+    echo before
+    exit 3
+    echo teardown
+    error: Recipe `test` failed on line 4 with exit code 3
+  ",
+  status: 3,
+}
+
+test! {
+  name: finally_attribute_cleanup_failure,
+  justfile: r#"
+    [finally("teardown")]
+    test:
+      @echo test
+
+    teardown:
+      @exit 4
+  "#,
+  args: ("test"),
+  stdout: "test\n",
+  stderr: "error: Recipe `teardown` failed on line 6 with exit code 4\n",
+  status: 4,
+}
+
+test! {
+  name: finally_attribute_original_error_preserved,
+  justfile: r#"
+    [finally("teardown")]
+    test:
+      @exit 3
+
+    teardown:
+      @exit 4
+  "#,
+  args: ("test"),
+  stdout: "",
+  stderr: "error: Recipe `test` failed on line 3 with exit code 3\n",
+  status: 3,
+}
+
+test! {
+  name: finally_attribute_dependency_failure,
+  justfile: r#"
+    start:
+      @exit 5
+
+    [finally("teardown")]
+    test: start
+      @echo test
+
+    teardown:
+      @echo teardown
+  "#,
+  args: ("test"),
+  stdout: "teardown\n",
+  stderr: "error: Recipe `start` failed on line 2 with exit code 5\n",
+  status: 5,
+}
+
+test! {
+  name: finally_attribute_dry_run,
+  justfile: r#"
+    [finally("teardown")]
+    test:
+      echo test
+
+    teardown:
+      echo teardown
+  "#,
+  args: ("--dry-run", "test"),
+  stdout: "",
+  stderr: "echo test\necho teardown\n",
+}
+
+test! {
+  name: finally_attribute_unknown_recipe,
+  justfile: r#"
+    [finally("teardown")]
+    test:
+  "#,
+  args: ("test"),
+  stdout: "",
+  stderr: r#"
+    error: Recipe `test` has unknown finally recipe `teardown`
+      |
+    1 | [finally("teardown")]
+      |          ^^^^^^^^^^
+  "#,
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: hooks,
+  justfile: r#"
+    set before := "banner"
+    set after := "report"
+
+    banner:
+      @echo banner
+
+    report:
+      @echo report
+
+    foo:
+      @echo foo
+
+    bar:
+      @echo bar
+  "#,
+  args: ("foo", "bar"),
+  stdout: "banner\nfoo\nbar\nreport\n",
+  stderr: "",
+}
+
+test! {
+  name: after_hook_failure,
+  justfile: r#"
+    set after := "report"
+
+    report:
+      @echo "error: $JUST_ERROR"
+
+    foo:
+      @exit 3
+  "#,
+  args: ("foo"),
+  stdout: "error: Recipe `foo` failed on line 7 with exit code 3\n",
+  stderr: "error: Recipe `foo` failed on line 7 with exit code 3\n",
+  status: 3,
+}
+
+test! {
+  name: after_hook_success,
+  justfile: r#"
+    set after := "report"
+
+    report:
+      @echo "[$JUST_ERROR]"
+
+    foo:
+      @echo foo
+  "#,
+  args: ("foo"),
+  stdout: "foo\n[]\n",
+  stderr: "",
+}
+
+test! {
+  name: after_hook_before_hook_failure,
+  justfile: r#"
+    set before := "banner"
+    set after := "report"
+
+    banner:
+      @exit 2
+
+    report:
+      @echo report
+
+    foo:
+      @echo foo
+  "#,
+  args: ("foo"),
+  stdout: "report\n",
+  stderr: "error: Recipe `banner` failed on line 5 with exit code 2\n",
+  status: 2,
+}
+
+test! {
+  name: hooks_not_default_recipe,
+  justfile: r#"
+    set before := "banner"
+
+    banner:
+      @echo banner
+
+    foo:
+      @echo foo
+  "#,
+  stdout: "banner\nfoo\n",
+  stderr: "",
+}
+
+test! {
+  name: hooks_excluded_from_list,
+  justfile: r#"
+    set before := "banner"
+    set after := "report"
+
+    banner:
+
+    report:
+
+    foo:
+  "#,
+  args: ("--list"),
+  stdout: "
+    Available recipes:
+        foo
+  ",
+}
+
+test! {
+  name: hooks_unknown_recipe,
+  justfile: r#"
+    set after := "report"
+  "#,
+  args: ("--list"),
+  stdout: "",
+  stderr: "
+    error: Setting `after` names unknown recipe `report`
+      |
+    1 | set after := \"report\"
+      |              ^^^^^^^^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: justfile_stdin,
+  justfile: "
+    default:
+      @echo disk
+  ",
+  args: ("--justfile", "-"),
+  stdin: "default:\n\t@echo stdin\n",
+  stdout: "stdin\n",
+}
+
+test! {
+  name: justfile_stdin_working_directory,
+  justfile: "",
+  args: ("--justfile", "-"),
+  stdin: "default:\n\t@ls justfile\n",
+  stdout: "justfile\n",
+}
+
+test! {
+  name: justfile_stdin_justfile_function,
+  justfile: "",
+  args: ("--justfile", "-"),
+  stdin: "x := justfile()\n\ndefault:\n\techo {{x}}\n",
+  stderr: "
+    error: Call to function `justfile` failed: Justfile was read from standard input and has no path
+      |
+    1 | x := justfile()
+      |      ^^^^^^^^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: justfile_stdin_justfile_directory_function,
+  justfile: "",
+  args: ("--justfile", "-"),
+  stdin: "default:\n\techo {{justfile_directory()}}\n",
+  stderr: "
+    error: Call to function `justfile_directory` failed: Justfile was read from standard input and has no directory
+      |
+    2 |     echo {{justfile_directory()}}
+      |            ^^^^^^^^^^^^^^^^^^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: justfile_stdin_edit,
+  justfile: "",
+  args: ("--justfile", "-", "--edit"),
+  stderr: "
+    error: `--edit` may not be used when the justfile is read from standard input with `--justfile -`
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: justfile_stdin_watch,
+  justfile: "",
+  args: ("--justfile", "-", "--watch"),
+  stderr: "
+    error: `--watch` may not be used when the justfile is read from standard input with `--justfile -`
+  ",
+  status: EXIT_FAILURE,
+}
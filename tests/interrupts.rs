@@ -81,4 +81,41 @@ default:
 ",
     );
   }
+
+  #[test]
+  #[ignore]
+  fn interrupt_finally() {
+    let tmp = tempdir();
+
+    fs::write(
+      tmp.path().join("justfile"),
+      "
+[finally('teardown')]
+default:
+  @sleep 1
+  @touch unreachable
+
+teardown:
+  @touch teardown
+",
+    )
+    .unwrap();
+
+    let mut child = Command::new(&executable_path("just"))
+      .current_dir(&tmp)
+      .spawn()
+      .expect("just invocation failed");
+
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_millis(500) {}
+
+    kill(child.id());
+
+    let status = child.wait().unwrap();
+
+    assert_eq!(status.code(), Some(130));
+    assert!(tmp.path().join("teardown").is_file());
+    assert!(!tmp.path().join("unreachable").is_file());
+  }
 }
@@ -0,0 +1,341 @@
+use std::{
+  io::{BufRead, BufReader, Read, Write},
+  process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use executable_path::executable_path;
+use serde_json::{json, Value};
+
+const URI: &str = "file:///project/justfile";
+
+const JUSTFILE: &str = "target := 'debug'
+
+# build the project
+build mode=target: test
+  cargo build --{{mode}}
+
+alias b := build
+
+test:
+  cargo test
+";
+
+/// A language client talking to `just --lsp` over stdio
+struct Client {
+  child:  Child,
+  stdin:  ChildStdin,
+  stdout: BufReader<ChildStdout>,
+  id:     u64,
+}
+
+impl Client {
+  fn new() -> Self {
+    let mut child = Command::new(executable_path("just"))
+      .arg("--lsp")
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn()
+      .expect("just invocation failed");
+
+    let stdin = child.stdin.take().unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+
+    let mut client = Self {
+      id: 0,
+      child,
+      stdin,
+      stdout,
+    };
+
+    let response = client.request("initialize", json!({ "capabilities": {} }));
+    assert_eq!(response["capabilities"]["textDocumentSync"], 1);
+
+    client.notify("initialized", json!({}));
+
+    client
+  }
+
+  fn send(&mut self, message: Value) {
+    let content = message.to_string();
+    write!(
+      self.stdin,
+      "Content-Length: {}\r\n\r\n{}",
+      content.len(),
+      content
+    )
+    .unwrap();
+    self.stdin.flush().unwrap();
+  }
+
+  fn receive(&mut self) -> Value {
+    let mut length = None;
+
+    loop {
+      let mut header = String::new();
+      self.stdout.read_line(&mut header).unwrap();
+      let header = header.trim_end();
+      if header.is_empty() {
+        break;
+      }
+      if let Some(value) = header.strip_prefix("Content-Length: ") {
+        length = Some(value.parse().unwrap());
+      }
+    }
+
+    let mut content = vec![0; length.unwrap()];
+    self.stdout.read_exact(&mut content).unwrap();
+    serde_json::from_slice(&content).unwrap()
+  }
+
+  fn request(&mut self, method: &str, params: Value) -> Value {
+    self.id += 1;
+
+    self.send(json!({
+      "jsonrpc": "2.0",
+      "id": self.id,
+      "method": method,
+      "params": params,
+    }));
+
+    let response = self.receive();
+    assert_eq!(response["id"], self.id);
+    response["result"].clone()
+  }
+
+  fn notify(&mut self, method: &str, params: Value) {
+    self.send(json!({
+      "jsonrpc": "2.0",
+      "method": method,
+      "params": params,
+    }));
+  }
+
+  /// Open a document and return the diagnostics published for it
+  fn open(&mut self, text: &str) -> Value {
+    self.notify(
+      "textDocument/didOpen",
+      json!({
+        "textDocument": {
+          "uri": URI,
+          "languageId": "just",
+          "version": 1,
+          "text": text,
+        },
+      }),
+    );
+
+    let notification = self.receive();
+    assert_eq!(notification["method"], "textDocument/publishDiagnostics");
+    assert_eq!(notification["params"]["uri"], URI);
+    notification["params"]["diagnostics"].clone()
+  }
+
+  fn at(&mut self, method: &str, line: u64, character: u64) -> Value {
+    self.request(
+      method,
+      json!({
+        "textDocument": { "uri": URI },
+        "position": { "line": line, "character": character },
+      }),
+    )
+  }
+
+  fn exit(mut self) -> Option<i32> {
+    assert_eq!(self.request("shutdown", Value::Null), Value::Null);
+    self.notify("exit", Value::Null);
+    self.child.wait().unwrap().code()
+  }
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+  json!({
+    "start": { "line": start.0, "character": start.1 },
+    "end": { "line": end.0, "character": end.1 },
+  })
+}
+
+#[test]
+fn diagnostics() {
+  let mut client = Client::new();
+
+  assert_eq!(client.open(JUSTFILE), json!([]));
+
+  client.notify(
+    "textDocument/didChange",
+    json!({
+      "textDocument": { "uri": URI, "version": 2 },
+      "contentChanges": [{ "text": "foo:\n  echo {{bar}}\n" }],
+    }),
+  );
+
+  let notification = client.receive();
+  assert_eq!(
+    notification["params"]["diagnostics"],
+    json!([{
+      "range": range((1, 9), (1, 12)),
+      "severity": 1,
+      "source": "just",
+      "message": "Variable `bar` not defined",
+    }])
+  );
+
+  client.notify(
+    "textDocument/didClose",
+    json!({ "textDocument": { "uri": URI } }),
+  );
+
+  let notification = client.receive();
+  assert_eq!(notification["params"]["diagnostics"], json!([]));
+
+  assert_eq!(client.exit(), Some(0));
+}
+
+#[test]
+fn definition() {
+  let mut client = Client::new();
+  client.open(JUSTFILE);
+
+  let location = |range| json!({ "uri": URI, "range": range });
+
+  // dependency
+  assert_eq!(
+    client.at("textDocument/definition", 3, 20),
+    location(range((8, 0), (8, 4)))
+  );
+
+  // alias target
+  assert_eq!(
+    client.at("textDocument/definition", 6, 12),
+    location(range((3, 0), (3, 5)))
+  );
+
+  // parameter in interpolation
+  assert_eq!(
+    client.at("textDocument/definition", 4, 19),
+    location(range((3, 6), (3, 10)))
+  );
+
+  // variable in parameter default
+  assert_eq!(
+    client.at("textDocument/definition", 3, 12),
+    location(range((0, 0), (0, 6)))
+  );
+
+  assert_eq!(client.at("textDocument/definition", 4, 4), Value::Null);
+
+  assert_eq!(client.exit(), Some(0));
+}
+
+#[test]
+fn hover() {
+  let mut client = Client::new();
+  client.open(JUSTFILE);
+
+  assert_eq!(
+    client.at("textDocument/hover", 6, 12),
+    json!({
+      "contents": {
+        "kind": "markdown",
+        "value": "```just\nbuild mode=target: test\n```\n\nbuild the project",
+      },
+      "range": range((6, 11), (6, 16)),
+    })
+  );
+
+  assert_eq!(client.exit(), Some(0));
+}
+
+#[test]
+fn completion() {
+  let mut client = Client::new();
+  client.open(JUSTFILE);
+
+  let labels = |completions: Value| {
+    completions
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|completion| completion["label"].as_str().unwrap().to_owned())
+      .collect::<Vec<String>>()
+  };
+
+  let inside = labels(client.at("textDocument/completion", 4, 2));
+  let outside = labels(client.at("textDocument/completion", 9, 2));
+
+  for label in &["build", "test", "b", "target", "env_var", "arch", "shell"] {
+    assert!(inside.contains(&label.to_string()), "missing {}", label);
+    assert!(outside.contains(&label.to_string()), "missing {}", label);
+  }
+
+  assert!(inside.contains(&"mode".to_owned()));
+  assert!(!outside.contains(&"mode".to_owned()));
+
+  assert_eq!(client.exit(), Some(0));
+}
+
+#[test]
+fn document_symbols() {
+  let mut client = Client::new();
+  client.open(JUSTFILE);
+
+  let symbols = client.request(
+    "textDocument/documentSymbol",
+    json!({ "textDocument": { "uri": URI } }),
+  );
+
+  assert_eq!(
+    symbols,
+    json!([
+      {
+        "name": "target",
+        "kind": 13,
+        "range": range((0, 0), (0, 17)),
+        "selectionRange": range((0, 0), (0, 6)),
+      },
+      {
+        "name": "build",
+        "kind": 12,
+        "range": range((3, 0), (4, 24)),
+        "selectionRange": range((3, 0), (3, 5)),
+      },
+      {
+        "name": "b",
+        "kind": 12,
+        "range": range((6, 6), (6, 16)),
+        "selectionRange": range((6, 6), (6, 7)),
+      },
+      {
+        "name": "test",
+        "kind": 12,
+        "range": range((8, 0), (9, 12)),
+        "selectionRange": range((8, 0), (8, 4)),
+      },
+    ])
+  );
+
+  assert_eq!(client.exit(), Some(0));
+}
+
+#[test]
+fn unknown_method() {
+  let mut client = Client::new();
+
+  client.send(json!({
+    "jsonrpc": "2.0",
+    "id": 100,
+    "method": "workspace/frobnicate",
+  }));
+
+  let response = client.receive();
+  assert_eq!(response["id"], 100);
+  assert_eq!(response["error"]["code"], -32601);
+
+  assert_eq!(client.exit(), Some(0));
+}
+
+#[test]
+fn exit_without_shutdown() {
+  let mut client = Client::new();
+  client.notify("exit", Value::Null);
+  assert_eq!(client.child.wait().unwrap().code(), Some(1));
+}
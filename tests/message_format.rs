@@ -0,0 +1,200 @@
+use std::{path::Path, process::Command, str};
+
+use executable_path::executable_path;
+use serde_json::{json, Value};
+
+use test_utilities::tmptree;
+
+fn diagnostics(dir: &Path, args: &[&str]) -> (Option<i32>, Vec<Value>) {
+  let output = Command::new(executable_path("just"))
+    .current_dir(dir)
+    .args(&["--message-format", "json"])
+    .args(args)
+    .output()
+    .unwrap();
+
+  let diagnostics = str::from_utf8(&output.stderr)
+    .unwrap()
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect();
+
+  (output.status.code(), diagnostics)
+}
+
+#[test]
+fn compilation_errors() {
+  let tmp = tmptree! {
+    justfile: "bar:\n  echo {{baz}}\n\nfoo:\n  echo {{qux}}\n",
+  };
+
+  let file = tmp.path().join("justfile").display().to_string();
+
+  assert_eq!(
+    diagnostics(tmp.path(), &[]),
+    (
+      Some(1),
+      vec![
+        json!({
+          "severity": "error",
+          "code": "E0028",
+          "message": "Variable `baz` not defined",
+          "file": file,
+          "line": 2,
+          "column": 10,
+          "length": 3,
+          "suggestion": null,
+        }),
+        json!({
+          "severity": "error",
+          "code": "E0028",
+          "message": "Variable `qux` not defined",
+          "file": file,
+          "line": 5,
+          "column": 10,
+          "length": 3,
+          "suggestion": null,
+        }),
+      ]
+    )
+  );
+}
+
+#[test]
+fn warnings() {
+  let tmp = tmptree! {
+    justfile: "x = 'y'\n\nfoo:\n  @true\n",
+  };
+
+  let (code, diagnostics) = diagnostics(tmp.path(), &[]);
+
+  assert_eq!(code, Some(0));
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0]["severity"], "warning");
+  assert_eq!(diagnostics[0]["code"], "W0001");
+  assert_eq!(diagnostics[0]["line"], 1);
+  assert_eq!(diagnostics[0]["column"], 3);
+}
+
+#[test]
+fn runtime_error() {
+  let tmp = tmptree! {
+    justfile: "build:\n  @true\n",
+  };
+
+  assert_eq!(
+    diagnostics(tmp.path(), &["buidl"]),
+    (
+      Some(1),
+      vec![json!({
+        "severity": "error",
+        "code": "E1015",
+        "message": "Justfile does not contain recipe `buidl`.",
+        "file": tmp.path().join("justfile").display().to_string(),
+        "line": null,
+        "column": null,
+        "length": null,
+        "suggestion": "Did you mean `build`?",
+      })]
+    )
+  );
+}
+
+#[test]
+fn quiet() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  @exit 2\n",
+  };
+
+  assert_eq!(diagnostics(tmp.path(), &["--quiet"]), (Some(2), Vec::new()));
+}
+
+#[test]
+fn recipe_failure_line() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  @true\n  @exit 3\n",
+  };
+
+  let (code, diagnostics) = diagnostics(tmp.path(), &[]);
+
+  assert_eq!(code, Some(3));
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0]["code"], "E1003");
+  assert_eq!(diagnostics[0]["line"], 3);
+  assert_eq!(diagnostics[0]["column"], Value::Null);
+}
+
+#[test]
+fn ignored_error() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  -@exit 4\n",
+  };
+
+  assert_eq!(
+    diagnostics(tmp.path(), &[]),
+    (
+      Some(0),
+      vec![json!({
+        "severity": "warning",
+        "code": "W0002",
+        "message": "Recipe `foo` failed on line 2 with exit code 4, ignoring",
+        "file": tmp.path().join("justfile").display().to_string(),
+        "line": 2,
+        "column": null,
+        "length": null,
+        "suggestion": null,
+      })]
+    )
+  );
+}
+
+#[test]
+fn search_error() {
+  let tmp = tmptree! {
+    sub: {},
+  };
+
+  assert_eq!(
+    diagnostics(&tmp.path().join("sub"), &[]),
+    (
+      Some(1),
+      vec![json!({
+        "severity": "error",
+        "code": "E2003",
+        "message": "No justfile found",
+        "file": null,
+        "line": null,
+        "column": null,
+        "length": null,
+        "suggestion": null,
+      })]
+    )
+  );
+}
+
+#[test]
+fn load_error() {
+  let tmp = tmptree! {
+    sub: {},
+  };
+
+  assert_eq!(
+    diagnostics(&tmp.path().join("sub"), &["--justfile", "missing", "--working-directory", "."]),
+    (
+      Some(1),
+      vec![json!({
+        "severity": "error",
+        "code": "E2006",
+        "message": format!(
+          "Failed to read justfile at `{}`: No such file or directory (os error 2)",
+          tmp.path().join("sub/missing").display()
+        ),
+        "file": tmp.path().join("sub/missing").display().to_string(),
+        "line": null,
+        "column": null,
+        "length": null,
+        "suggestion": null,
+      })]
+    )
+  );
+}
@@ -145,3 +145,59 @@ fn single_upwards() {
 
   search_test(&path, &["../"]);
 }
+
+#[test]
+fn hidden_justfile() {
+  let tmp = tmptree! {
+    ".justfile": "default:\n\techo ok",
+    child: {},
+  };
+
+  search_test(tmp.path().join("child"), &[]);
+}
+
+#[test]
+fn just_extension() {
+  let tmp = tmptree! {
+    "build.just": "default:\n\techo ok",
+    child: {},
+  };
+
+  search_test(tmp.path().join("child"), &[]);
+}
+
+#[test]
+fn justfile_names_from_environment() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    "Taskfile": "default:\n\techo ok",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .env("JUST_JUSTFILE_NAMES", "taskfile, justfile")
+    .output()
+    .unwrap();
+
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "ok\n");
+}
+
+#[test]
+fn stop_at_root_from_environment() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    project: {
+      ".git": {},
+      child: {},
+    },
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path().join("project/child"))
+    .env("JUST_SEARCH_STOP_AT_ROOT", "1")
+    .output()
+    .unwrap();
+
+  assert_eq!(output.status.code().unwrap(), 1);
+  assert_eq!(str::from_utf8(&output.stderr).unwrap(), "error: No justfile found\n");
+}
@@ -0,0 +1,69 @@
+use std::{
+  process::{Command, Stdio},
+  str, thread,
+  time::{Duration, Instant},
+};
+
+use executable_path::executable_path;
+
+use test_utilities::tmptree;
+
+#[test]
+#[cfg(unix)]
+fn descendants_are_terminated() {
+  let tmp = tmptree! {
+    justfile: "
+[timeout('200ms')]
+foo:
+  sh -c '(sleep 1 && touch survived) & wait'
+",
+  };
+
+  let status = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap()
+    .status;
+
+  assert_eq!(status.code(), Some(1));
+
+  thread::sleep(Duration::from_millis(1500));
+
+  assert!(!tmp.path().join("survived").exists());
+}
+
+#[test]
+#[cfg(unix)]
+fn interrupt_terminates_child() {
+  let tmp = tmptree! {
+    justfile: "
+[timeout('10s')]
+foo:
+  @sleep 4; echo child-finished
+",
+  };
+
+  let start = Instant::now();
+
+  let child = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+
+  thread::sleep(Duration::from_millis(500));
+
+  unsafe {
+    libc::kill(child.id() as i32, libc::SIGINT);
+  }
+
+  let output = child.wait_with_output().unwrap();
+
+  let elapsed = start.elapsed();
+
+  assert!(elapsed < Duration::from_secs(2), "returned too late: {:?}", elapsed);
+  assert_eq!(output.status.code(), Some(130));
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "");
+}
@@ -0,0 +1,154 @@
+#[cfg(unix)]
+mod unix {
+  use executable_path::executable_path;
+  use std::{
+    fs,
+    path::Path,
+    process::{Child, Command},
+    thread,
+    time::{Duration, Instant},
+  };
+  use test_utilities::tempdir;
+
+  fn interrupt(child: &Child) {
+    unsafe {
+      libc::kill(child.id() as i32, libc::SIGINT);
+    }
+  }
+
+  /// Wait until the file at `path` has `lines` lines
+  fn wait_for_lines(path: &Path, lines: usize) {
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_secs(10) {
+      if let Ok(log) = fs::read_to_string(path) {
+        if log.lines().count() >= lines {
+          return;
+        }
+      }
+
+      thread::sleep(Duration::from_millis(50));
+    }
+
+    panic!("timed out waiting for {} lines in {}", lines, path.display());
+  }
+
+  #[test]
+  fn rerun_on_change() {
+    let tmp = tempdir();
+
+    fs::write(
+      tmp.path().join("justfile"),
+      "
+[inputs(\"src\")]
+build:
+  @echo ran >> log
+",
+    )
+    .unwrap();
+
+    fs::create_dir(tmp.path().join("src")).unwrap();
+    fs::write(tmp.path().join("src/lib.rs"), "").unwrap();
+
+    let mut child = Command::new(&executable_path("just"))
+      .arg("--watch")
+      .current_dir(&tmp)
+      .spawn()
+      .expect("just invocation failed");
+
+    let log = tmp.path().join("log");
+
+    wait_for_lines(&log, 1);
+
+    fs::write(tmp.path().join("src/lib.rs"), "fn main() {}").unwrap();
+
+    wait_for_lines(&log, 2);
+
+    interrupt(&child);
+
+    let status = child.wait().unwrap();
+
+    assert_eq!(status.code(), Some(130));
+    assert_eq!(fs::read_to_string(&log).unwrap(), "ran\nran\n");
+  }
+
+  #[test]
+  fn stop_running_recipe_on_change() {
+    let tmp = tempdir();
+
+    fs::write(
+      tmp.path().join("justfile"),
+      "
+serve:
+  @echo started >> log
+  @sleep 60
+  @echo finished >> log
+",
+    )
+    .unwrap();
+
+    fs::write(tmp.path().join("input"), "").unwrap();
+
+    let mut child = Command::new(&executable_path("just"))
+      .arg("--watch=input")
+      .current_dir(&tmp)
+      .spawn()
+      .expect("just invocation failed");
+
+    let log = tmp.path().join("log");
+
+    wait_for_lines(&log, 1);
+
+    fs::write(tmp.path().join("input"), "changed").unwrap();
+
+    wait_for_lines(&log, 2);
+
+    interrupt(&child);
+
+    let status = child.wait().unwrap();
+
+    assert_eq!(status.code(), Some(130));
+    assert_eq!(fs::read_to_string(&log).unwrap(), "started\nstarted\n");
+  }
+
+  #[test]
+  fn ignored_directories() {
+    let tmp = tempdir();
+    let output = tempdir();
+
+    let log = output.path().join("log");
+
+    fs::write(
+      tmp.path().join("justfile"),
+      format!("build:\n  @echo ran >> {}\n", log.display()),
+    )
+    .unwrap();
+
+    fs::create_dir(tmp.path().join("target")).unwrap();
+    fs::create_dir_all(tmp.path().join("sub/target")).unwrap();
+
+    let mut child = Command::new(&executable_path("just"))
+      .arg("--watch")
+      .current_dir(&tmp)
+      .spawn()
+      .expect("just invocation failed");
+
+    wait_for_lines(&log, 1);
+
+    // `target` is only ignored in the working directory
+    fs::write(tmp.path().join("target/output"), "").unwrap();
+
+    thread::sleep(Duration::from_secs(1));
+
+    fs::write(tmp.path().join("sub/target/input"), "").unwrap();
+
+    wait_for_lines(&log, 2);
+
+    interrupt(&child);
+
+    let status = child.wait().unwrap();
+
+    assert_eq!(status.code(), Some(130));
+    assert_eq!(fs::read_to_string(&log).unwrap(), "ran\nran\n");
+  }
+}